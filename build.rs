@@ -107,8 +107,24 @@ fn main() {
         println!("cargo:warning=Building with SoundCloud credentials");
     }
 
+    // Build details for --build-info. The date is only passed on from
+    // SOURCE_DATE_EPOCH: this script doesn't rerun on every build, so a clock
+    // reading taken here would go stale
+    if let Ok(source_date) = std::env::var("SOURCE_DATE_EPOCH") {
+        println!("cargo:rustc-env=BUILD_TIMESTAMP={}", source_date);
+    }
+    println!(
+        "cargo:rustc-env=BUILD_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+    println!(
+        "cargo:rustc-env=BUILD_PROFILE={}",
+        std::env::var("PROFILE").unwrap_or_default()
+    );
+
     // Rebuild if config.json changes
     println!("cargo:rerun-if-changed=config.json");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
 
     println!("cargo:warning=Building with AES-256-GCM encrypted credentials");
 }
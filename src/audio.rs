@@ -1,47 +1,256 @@
 use anyhow::{Context, Result};
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use symphonia::core::audio::{AudioBufferRef, Signal};
-use symphonia::core::codecs::DecoderOptions;
-use symphonia::core::formats::{FormatOptions, SeekMode, SeekTo};
+use symphonia::core::audio::{AudioBufferRef, SampleBuffer, Signal};
+use symphonia::core::codecs::{CODEC_TYPE_MP3, CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo, Track};
 use symphonia::core::io::MediaSourceStream;
-use symphonia::core::meta::MetadataOptions;
-use symphonia::core::probe::Hint;
-use symphonia::core::units::Time;
-
-/// Creates preview snippets of an audio file at 30, 60, and 90 seconds
-/// Each snippet takes 10-second chunks from intro, middle, and end with fade effects
-pub fn create_preview_snippets(file_path: &Path) -> Result<Vec<PathBuf>> {
-    let durations = vec![30, 60, 90]; // seconds
+use symphonia::core::meta::{MetadataOptions, MetadataRevision};
+use symphonia::core::probe::{Hint, ProbeResult};
+use symphonia::core::units::{Time, TimeBase};
+
+use crate::cli::{ExistingOutput, FadeCurve, PreviewFormat, PreviewStrategy, WavSampleFormat};
+
+/// Default preview lengths in seconds
+pub const DEFAULT_PREVIEW_DURATIONS: [u64; 3] = [30, 60, 90];
+/// Length of each chunk a preview is made of, in seconds
+pub const PREVIEW_CHUNK_SECS: u64 = 10;
+
+/// How far `create_preview_snippets` has got
+#[derive(Debug, Clone, Copy)]
+pub struct PreviewProgress {
+    /// 1-based index of the snippet being generated
+    pub snippet: usize,
+    pub snippets: usize,
+    /// Length of the current snippet in seconds
+    pub duration: u64,
+    /// Progress through the current snippet, 0.0 to 1.0
+    pub snippet_fraction: f64,
+}
+
+impl PreviewProgress {
+    /// Progress through all snippets, 0.0 to 1.0
+    pub fn overall(&self) -> f64 {
+        ((self.snippet - 1) as f64 + self.snippet_fraction) / self.snippets as f64
+    }
+}
+
+/// Where in the track preview chunks are taken from
+#[derive(Debug, Clone, Default)]
+pub struct PreviewPlacement {
+    pub strategy: PreviewStrategy,
+    /// Center of the climax run in seconds; defaults to 75% of the track
+    pub climax_at: Option<f64>,
+    /// Explicit chunk start times in seconds, replacing the strategy
+    pub timestamps: Option<Vec<f64>>,
+}
+
+/// Shortening of long quiet passages inside preview chunks
+#[derive(Debug, Clone, Copy)]
+pub struct SilenceCompression {
+    /// RMS level in dBFS below which audio counts as quiet
+    pub threshold_db: f64,
+    /// Quiet passages longer than this are cut down to it
+    pub max_gap_secs: f64,
+}
+
+/// Creates preview snippets of an audio file for each of the given durations
+/// in `output_dir`. Each snippet takes 10-second chunks from intro, middle,
+/// and end with fade effects.
+#[allow(clippy::too_many_arguments)]
+pub fn create_preview_snippets(
+    file_path: &Path,
+    output_dir: &Path,
+    format: PreviewFormat,
+    sample_format: WavSampleFormat,
+    durations: &[u64],
+    placement: &PreviewPlacement,
+    fade_curve: FadeCurve,
+    compress_silence: Option<SilenceCompression>,
+    existing: ExistingOutput,
+    on_progress: &dyn Fn(&PreviewProgress),
+) -> Result<Vec<PathBuf>> {
+    if let Some(&duration) = durations
+        .iter()
+        .find(|&&duration| duration == 0 || duration % PREVIEW_CHUNK_SECS != 0)
+    {
+        anyhow::bail!(
+            "Preview durations must be multiples of {}s, got {}s",
+            PREVIEW_CHUNK_SECS,
+            duration
+        );
+    }
     let mut output_files = Vec::new();
 
-    // Get the total duration first
-    let total_duration = get_audio_duration(file_path)?;
+    // Exact rather than a bitrate estimate, which is off for VBR MP3s
+    // without a Xing header and would move every chunk position
+    let total_duration = audio_duration(file_path)?.as_secs_f64();
+    if let Some(&longest) = durations.iter().max()
+        && longest as f64 > total_duration
+    {
+        eprintln!(
+            "⚠ Warning: A {}s preview is longer than the track ({:.0}s), so it repeats parts of it",
+            longest, total_duration
+        );
+    }
+
+    for (index, &duration) in durations.iter().enumerate() {
+        let mut progress = PreviewProgress {
+            snippet: index + 1,
+            snippets: durations.len(),
+            duration,
+            snippet_fraction: 0.0,
+        };
+        on_progress(&progress);
 
-    for duration in durations {
-        let output_path = generate_snippet_path(file_path, duration)?;
-        create_snippet(file_path, &output_path, duration, total_duration)?;
+        // Existing outputs kept by the policy count as done
+        let output_path = generate_snippet_path(file_path, output_dir, format, duration)?;
+        if crate::output::should_write(&output_path, existing)? {
+            let (samples, sample_rate) = create_snippet(
+                file_path,
+                duration,
+                total_duration,
+                placement,
+                fade_curve,
+                compress_silence,
+                &mut |fraction| {
+                    progress.snippet_fraction = fraction;
+                    on_progress(&progress);
+                },
+            )?;
+            match format {
+                PreviewFormat::Wav => {
+                    write_wav(&output_path, &samples, sample_rate, sample_format)?
+                }
+                PreviewFormat::Mp3 => write_mp3(&output_path, &samples, sample_rate)?,
+            }
+        } else {
+            progress.snippet_fraction = 1.0;
+            on_progress(&progress);
+        }
         output_files.push(output_path);
     }
 
     Ok(output_files)
 }
 
+/// Pick preview lengths that make sense for a track of `total_secs`
+/// Short tracks skip the longer montages, long sets get an extra 120s one
+pub fn auto_preview_durations(total_secs: f64) -> Vec<u64> {
+    let candidates: &[u64] = if total_secs < 2.0 * 60.0 {
+        &[30]
+    } else if total_secs < 10.0 * 60.0 {
+        &[30, 60]
+    } else if total_secs < 90.0 * 60.0 {
+        &[30, 60, 90]
+    } else {
+        &[30, 60, 90, 120]
+    };
+
+    // A preview should always be shorter than the track itself
+    candidates
+        .iter()
+        .copied()
+        .filter(|&d| (d as f64) < total_secs)
+        .collect()
+}
+
 /// Generate output path for snippet
-fn generate_snippet_path(original: &Path, duration: u64) -> Result<PathBuf> {
-    let parent = original.parent().unwrap_or(Path::new("."));
+fn generate_snippet_path(
+    original: &Path,
+    output_dir: &Path,
+    format: PreviewFormat,
+    duration: u64,
+) -> Result<PathBuf> {
     let stem = original
         .file_stem()
         .and_then(|s| s.to_str())
         .context("Invalid file name")?;
 
-    // Always output as WAV to avoid encoding complexity
-    let output_name = format!("{}_preview_{}s.wav", stem, duration);
-    Ok(parent.join(output_name))
+    let output_name = format!("{}_preview_{}s.{}", stem, duration, format.extension());
+    Ok(output_dir.join(output_name))
 }
 
-/// Get the duration of an audio file in seconds
-fn get_audio_duration(file_path: &Path) -> Result<f64> {
+/// File extension for audio data, guessed from its first bytes
+pub fn extension_from_header(header: &[u8]) -> Option<&'static str> {
+    // ID3v2 tags also front AAC and FLAC, so look at what follows; MP3 is
+    // the best guess when the tag runs past `header`
+    if header.starts_with(b"ID3") {
+        return match header.get(crate::id3::tag_len(header)..) {
+            Some(audio) if !audio.is_empty() => extension_from_header(audio),
+            _ => Some("mp3"),
+        };
+    }
+    // Frame sync, then the version and layer bits
+    if header.len() >= 2 && header[0] == 0xff && header[1] & 0xe0 == 0xe0 {
+        let version = (header[1] >> 3) & 0b11;
+        let layer = (header[1] >> 1) & 0b11;
+        return match (version, layer) {
+            // ADTS keeps the layer bits at zero
+            (0b10 | 0b11, 0b00) => Some("aac"),
+            (0b01, _) | (_, 0b00) => None,
+            (_, 0b01) => Some("mp3"),
+            (_, 0b10) => Some("mp2"),
+            _ => Some("mp1"),
+        };
+    }
+    if header.len() >= 12 {
+        match (&header[..4], &header[8..12]) {
+            (b"RIFF", b"WAVE") => return Some("wav"),
+            (b"FORM", b"AIFF" | b"AIFC") => return Some("aiff"),
+            (_, _) if &header[4..8] == b"ftyp" => return Some("m4a"),
+            _ => {}
+        }
+    }
+    if header.starts_with(b"fLaC") {
+        return Some("flac");
+    }
+    if header.starts_with(b"OggS") {
+        return Some("ogg");
+    }
+    None
+}
+
+/// MIME type for an audio file, from the codec symphonia finds inside it
+/// rather than the file extension. AAC and ALAC in an MP4 container are
+/// `audio/mp4`, whatever the file happens to be called.
+pub fn audio_mime(file_path: &Path) -> Result<&'static str> {
+    use symphonia::core::codecs::*;
+
+    let mut header = [0u8; 12];
+    let read = std::io::Read::read(&mut File::open(file_path)?, &mut header)?;
+    let container = extension_from_header(&header[..read]);
+
+    let format = probe_audio(file_path)?;
+    let codec = format
+        .default_track()
+        .context("No default audio track found")?
+        .codec_params
+        .codec;
+
+    let mime = match (container, codec) {
+        (Some("m4a"), _) => "audio/mp4",
+        (_, CODEC_TYPE_MP1 | CODEC_TYPE_MP2 | CODEC_TYPE_MP3) => "audio/mpeg",
+        (_, CODEC_TYPE_AAC) => "audio/aac",
+        (_, CODEC_TYPE_ALAC) => "audio/mp4",
+        (_, CODEC_TYPE_FLAC) => "audio/flac",
+        (Some("ogg"), _) => "audio/ogg",
+        (Some("wav"), _) => "audio/wav",
+        (Some("aiff"), _) => "audio/aiff",
+        (_, CODEC_TYPE_VORBIS | CODEC_TYPE_OPUS) => "audio/webm",
+        _ => "application/octet-stream",
+    };
+    Ok(mime)
+}
+
+/// Open an audio file and probe its container format
+fn probe_audio(file_path: &Path) -> Result<Box<dyn FormatReader>> {
+    Ok(probe_file(file_path)?.format)
+}
+
+/// Probe an audio file, keeping any metadata found ahead of the container
+fn probe_file(file_path: &Path) -> Result<ProbeResult> {
     let file = File::open(file_path)?;
     let mss = MediaSourceStream::new(Box::new(file), Default::default());
 
@@ -53,140 +262,699 @@ fn get_audio_duration(file_path: &Path) -> Result<f64> {
     let meta_opts = MetadataOptions::default();
     let fmt_opts = FormatOptions::default();
 
-    let probed = symphonia::default::get_probe()
+    symphonia::default::get_probe()
         .format(&hint, mss, &fmt_opts, &meta_opts)
-        .context("Failed to probe audio file")?;
+        .context("Failed to probe audio file")
+}
+
+/// Basic technical details and tags of an audio file
+#[derive(Debug, Clone, Default)]
+pub struct AudioMetadata {
+    pub duration_secs: Option<f64>,
+    pub duration_source: Option<DurationSource>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+    /// Average bitrate derived from file size and duration
+    pub bitrate_kbps: Option<u64>,
+    /// Short codec name, e.g. "mp3" or "aac"
+    pub codec: Option<String>,
+    /// Embedded tags as (key, value) pairs
+    pub tags: Vec<(String, String)>,
+    /// Whether the file carries embedded artwork
+    pub has_cover: bool,
+    /// Number of audio tracks in the container; only the default one is read
+    pub audio_tracks: usize,
+    /// Id of the track that is read
+    pub track_id: Option<u32>,
+}
+
+impl AudioMetadata {
+    /// Warning for files whose other audio tracks are ignored
+    pub fn track_warning(&self) -> Option<String> {
+        (self.audio_tracks > 1).then(|| {
+            format!(
+                "File has {} audio tracks; only track {} is used",
+                self.audio_tracks,
+                self.track_id
+                    .map_or_else(|| "?".to_string(), |id| format!("#{}", id))
+            )
+        })
+    }
+}
+
+/// Tracks that carry audio, as opposed to e.g. cue or data tracks
+fn audio_track_count(tracks: &[Track]) -> usize {
+    tracks
+        .iter()
+        .filter(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .count()
+}
+
+/// Stops decoding when the sample rate changes mid-stream, since everything
+/// written from the file assumes one rate and would play at the wrong speed
+#[derive(Debug)]
+struct RateCheck(Option<u32>);
+
+impl RateCheck {
+    fn check(&mut self, rate: u32) -> Result<()> {
+        match self.0 {
+            Some(expected) if expected != rate => anyhow::bail!(
+                "Sample rate changes mid-stream from {} Hz to {} Hz; re-export the file at a single rate",
+                expected,
+                rate
+            ),
+            Some(_) => Ok(()),
+            None => {
+                self.0 = Some(rate);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Read duration, format details and embedded tags without decoding audio
+pub fn probe_metadata(file_path: &Path) -> Result<AudioMetadata> {
+    let mut probed = probe_file(file_path)?;
+
+    let mut metadata = AudioMetadata::default();
+    {
+        let track = probed
+            .format
+            .default_track()
+            .context("No default audio track found")?;
+        let params = &track.codec_params;
+
+        metadata.track_id = Some(track.id);
+        metadata.sample_rate = params.sample_rate;
+        metadata.channels = params.channels.map(|c| c.count());
+        metadata.codec = symphonia::default::get_codecs()
+            .get_codec(params.codec)
+            .map(|descriptor| descriptor.short_name.to_string());
+        if let (Some(tb), Some(frames)) = (params.time_base, params.n_frames) {
+            let time = tb.calc_time(frames);
+            metadata.duration_secs = Some(time.seconds as f64 + time.frac);
+            metadata.duration_source = Some(DurationSource::Container);
+        }
+    }
+    metadata.audio_tracks = audio_track_count(probed.format.tracks());
+
+    // No frame count in the header: fall back to an estimate
+    if metadata.duration_secs.is_none()
+        && let Ok(duration) = measure_duration(file_path, false)
+    {
+        metadata.duration_secs = Some(duration.secs);
+        metadata.duration_source = Some(duration.source);
+    }
 
-    let format = probed.format;
+    if let Some(duration) = metadata.duration_secs
+        && duration > 0.0
+    {
+        let file_size = std::fs::metadata(file_path)?.len();
+        metadata.bitrate_kbps = Some((file_size as f64 * 8.0 / duration / 1000.0) as u64);
+    }
+
+    // Tags can live ahead of the container (e.g. ID3) or inside it
+    if let Some(container_meta) = probed.metadata.get()
+        && let Some(revision) = container_meta.current()
+    {
+        metadata.tags.extend(collect_tags(revision));
+        metadata.has_cover |= !revision.visuals().is_empty();
+    }
+    let format_meta = probed.format.metadata();
+    if let Some(revision) = format_meta.current() {
+        metadata.tags.extend(collect_tags(revision));
+        metadata.has_cover |= !revision.visuals().is_empty();
+    }
+
+    Ok(metadata)
+}
+
+/// Rough size in bytes of the 16-bit WAVs written from this file: previews are
+/// mono and `secs` long in total, a normalized copy keeps every channel.
+pub fn estimate_wav_bytes(metadata: &AudioMetadata, secs: f64, keep_channels: bool) -> u64 {
+    let rate = metadata.sample_rate.unwrap_or(44100) as f64;
+    let channels = if keep_channels {
+        metadata.channels.unwrap_or(2) as f64
+    } else {
+        1.0
+    };
+    (secs * rate * channels * 2.0) as u64 + 44
+}
+
+/// [`estimate_wav_bytes`] for a whole file with every channel, estimating
+/// its length from the bitrate when the container doesn't give one. None
+/// when the length can't be worked out at all.
+pub fn estimate_full_wav_bytes(file_path: &Path, metadata: &AudioMetadata) -> Option<u64> {
+    let secs = match metadata.duration_secs {
+        Some(secs) => secs,
+        None => measure_duration(file_path, false).ok()?.secs,
+    };
+    Some(estimate_wav_bytes(metadata, secs, true))
+}
+
+fn collect_tags(revision: &MetadataRevision) -> Vec<(String, String)> {
+    revision
+        .tags()
+        .iter()
+        .map(|tag| {
+            let key = tag
+                .std_key
+                .map(|k| format!("{:?}", k))
+                .unwrap_or_else(|| tag.key.clone());
+            (key, tag.value.to_string())
+        })
+        .collect()
+}
+
+/// Packets read to work out the bitrate when the container has no frame count
+const BITRATE_SAMPLE_PACKETS: usize = 500;
+
+/// Where a duration came from, most reliable first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationSource {
+    /// Frame count in the container header
+    Container,
+    /// File size divided by the bitrate of the opening packets; close for
+    /// constant bitrate files, approximate for VBR
+    Bitrate,
+    /// Every packet counted; exact, but reads the whole file
+    Counted,
+}
+
+impl DurationSource {
+    pub fn is_estimate(self) -> bool {
+        self == DurationSource::Bitrate
+    }
+}
+
+impl std::fmt::Display for DurationSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationSource::Container => write!(f, "container"),
+            DurationSource::Bitrate => write!(f, "bitrate estimate"),
+            DurationSource::Counted => write!(f, "counted"),
+        }
+    }
+}
+
+/// A duration and how it was determined
+#[derive(Debug, Clone, Copy)]
+pub struct AudioDuration {
+    pub secs: f64,
+    pub source: DurationSource,
+}
+
+/// Work out the duration of an audio file: the container's frame count if
+/// it has one, then an estimate from the file size and bitrate, and finally,
+/// if `count_packets` allows it, by reading every packet. Streaming-style
+/// MP3s without a Xing/VBRI header have no frame count.
+pub fn measure_duration(file_path: &Path, count_packets: bool) -> Result<AudioDuration> {
+    let mut format = probe_audio(file_path)?;
     let track = format
         .default_track()
         .context("No default audio track found")?;
+    let track_id = track.id;
+    let params = &track.codec_params;
 
-    let time_base = track.codec_params.time_base;
-    let n_frames = track.codec_params.n_frames;
+    let time_base = params
+        .time_base
+        .or_else(|| params.sample_rate.map(|rate| TimeBase::new(1, rate)));
+    let Some(time_base) = time_base else {
+        anyhow::bail!("Could not determine duration: no time base or sample rate");
+    };
+    let to_secs = |ts: u64| {
+        let time = time_base.calc_time(ts);
+        time.seconds as f64 + time.frac
+    };
 
-    if let (Some(tb), Some(frames)) = (time_base, n_frames) {
-        Ok(tb.calc_time(frames).seconds as f64)
-    } else {
-        // Fallback
-        Ok(180.0) // Assume 3 minutes if we can't determine
+    // Without a header symphonia still reports a frame count for MP3s, but
+    // it's guessed from the first frame's bitrate
+    let trust_frame_count = params.codec != CODEC_TYPE_MP3 || has_mp3_frame_count(file_path)?;
+    if let Some(frames) = params.n_frames.filter(|_| trust_frame_count) {
+        return Ok(AudioDuration {
+            secs: to_secs(frames),
+            source: DurationSource::Container,
+        });
+    }
+
+    // Bitrate of the first packets, applied to the audio part of the file
+    let mut bytes = 0u64;
+    let mut ts = 0u64;
+    let mut packets = 0;
+    while packets < BITRATE_SAMPLE_PACKETS {
+        let Some(packet) = next_packet_or_end(format.as_mut())? else {
+            break;
+        };
+        if packet.track_id() == track_id {
+            bytes += packet.data.len() as u64;
+            ts += packet.dur;
+            packets += 1;
+        }
+    }
+
+    // Every packet was read, so this is already an exact count
+    if packets < BITRATE_SAMPLE_PACKETS && ts > 0 {
+        return Ok(AudioDuration {
+            secs: to_secs(ts),
+            source: DurationSource::Counted,
+        });
+    }
+
+    if count_packets {
+        while let Some(packet) = next_packet_or_end(format.as_mut())? {
+            if packet.track_id() == track_id {
+                ts += packet.dur;
+            }
+        }
+        return Ok(AudioDuration {
+            secs: to_secs(ts),
+            source: DurationSource::Counted,
+        });
+    }
+
+    let sampled_secs = to_secs(ts);
+    if bytes > 0 && sampled_secs > 0.0 {
+        let mut header = [0u8; 10];
+        let read = std::io::Read::read(&mut File::open(file_path)?, &mut header)?;
+        let audio_bytes = std::fs::metadata(file_path)?
+            .len()
+            .saturating_sub(crate::id3::tag_len(&header[..read]) as u64);
+        return Ok(AudioDuration {
+            secs: audio_bytes as f64 / (bytes as f64 / sampled_secs),
+            source: DurationSource::Bitrate,
+        });
     }
+
+    anyhow::bail!("Could not determine duration: no frame count and no readable audio packets")
+}
+
+/// The next packet, or None at the end of the stream. A read error part way
+/// through is returned, so a damaged file isn't measured as a short one.
+fn next_packet_or_end(
+    format: &mut dyn FormatReader,
+) -> Result<Option<symphonia::core::formats::Packet>> {
+    match format.next_packet() {
+        Ok(packet) => Ok(Some(packet)),
+        Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            Ok(None)
+        }
+        // A chained Ogg stream starting over, not damage
+        Err(SymphoniaError::ResetRequired) => Ok(None),
+        Err(e) => Err(e).context("Failed to read audio packet"),
+    }
+}
+
+/// Whether an MP3 opens with a Xing/Info or VBRI header, which is where a
+/// real frame count comes from
+fn has_mp3_frame_count(file_path: &Path) -> Result<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = File::open(file_path)?;
+    let mut header = [0u8; 10];
+    let read = file.read(&mut header)?;
+    file.seek(SeekFrom::Start(crate::id3::tag_len(&header[..read]) as u64))?;
+
+    // Both sit in the first frame, at most 36 bytes in
+    let mut frame = Vec::new();
+    file.take(64).read_to_end(&mut frame)?;
+    Ok(frame
+        .windows(4)
+        .any(|id| matches!(id, b"Xing" | b"Info" | b"VBRI")))
+}
+
+/// Get the duration of an audio file in seconds, estimating from the bitrate
+/// when the container doesn't say
+pub fn get_audio_duration(file_path: &Path) -> Result<f64> {
+    let duration = measure_duration(file_path, false)?;
+    if duration.source.is_estimate() {
+        log::info!(
+            "Duration of {} estimated from its bitrate: {:.1}s",
+            file_path.display(),
+            duration.secs
+        );
+    }
+    Ok(duration.secs)
+}
+
+/// Duration and format of an audio file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioInfo {
+    pub duration: std::time::Duration,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+}
+
+/// Exact duration of an audio file. Without a frame count in the container
+/// every packet is counted instead of estimating from the bitrate, and a
+/// duration that can't be worked out is an error.
+pub fn audio_duration(file_path: &Path) -> Result<std::time::Duration> {
+    let duration = measure_duration(file_path, true)?;
+    if !(duration.secs > 0.0 && duration.secs.is_finite()) {
+        anyhow::bail!(
+            "Could not determine duration of {}: no audio packets",
+            file_path.display()
+        );
+    }
+    std::time::Duration::try_from_secs_f64(duration.secs).with_context(|| {
+        format!(
+            "Could not determine duration of {}: {}s is out of range",
+            file_path.display(),
+            duration.secs
+        )
+    })
+}
+
+/// [`audio_duration`] with the sample rate and channel count of the
+/// default track, where the container gives them
+pub fn audio_info(file_path: &Path) -> Result<AudioInfo> {
+    let format = probe_audio(file_path)?;
+    let params = &format
+        .default_track()
+        .context("No default audio track found")?
+        .codec_params;
+    Ok(AudioInfo {
+        duration: audio_duration(file_path)?,
+        sample_rate: params.sample_rate,
+        channels: params.channels.map(|channels| channels.count()),
+    })
 }
 
 /// Create a snippet from the audio file
-/// Takes 10-second chunks from intro, middle, and end with fade effects
+/// Takes 10-second chunks from intro, middle, and end with fade effects;
+/// a track shorter than one chunk is used whole.
+/// Returns the mono samples and their sample rate.
 fn create_snippet(
     input_path: &Path,
-    output_path: &Path,
     duration_secs: u64,
     total_duration: f64,
-) -> Result<()> {
-    let chunk_duration = 10.0; // Always 10 seconds per chunk
+    placement: &PreviewPlacement,
+    fade_curve: FadeCurve,
+    compress_silence: Option<SilenceCompression>,
+    on_chunk: &mut dyn FnMut(f64),
+) -> Result<(Vec<f32>, u32)> {
+    let chunk_duration = PREVIEW_CHUNK_SECS as f64;
     let num_chunks = (duration_secs as f64 / chunk_duration) as usize;
 
-    // Calculate start positions for each chunk
-    let mut positions = Vec::new();
-
-    match num_chunks {
-        3 => {
-            // 30s: intro (0s), middle, end
-            positions.push(0.0);
-            positions.push((total_duration / 2.0) - (chunk_duration / 2.0));
-            positions.push((total_duration - chunk_duration).max(20.0));
-        }
-        6 => {
-            // 60s: 2 chunks from intro, 2 from middle, 2 from end
-            positions.push(0.0);
-            positions.push(10.0);
-            positions.push((total_duration / 2.0) - chunk_duration);
-            positions.push(total_duration / 2.0);
-            positions.push((total_duration - (2.0 * chunk_duration)).max(40.0));
-            positions.push((total_duration - chunk_duration).max(50.0));
-        }
-        9 => {
-            // 90s: 3 chunks from intro, 3 from middle, 3 from end
-            positions.push(0.0);
-            positions.push(10.0);
-            positions.push(20.0);
-            positions.push((total_duration / 2.0) - (1.5 * chunk_duration));
-            positions.push((total_duration / 2.0) - (0.5 * chunk_duration));
-            positions.push((total_duration / 2.0) + (0.5 * chunk_duration));
-            positions.push((total_duration - (3.0 * chunk_duration)).max(60.0));
-            positions.push((total_duration - (2.0 * chunk_duration)).max(70.0));
-            positions.push((total_duration - chunk_duration).max(80.0));
-        }
-        _ => {
-            anyhow::bail!("Unsupported duration: {}s", duration_secs);
-        }
-    }
+    let (chunk_duration, positions) = if total_duration < chunk_duration {
+        (total_duration, vec![0.0])
+    } else if let Some(timestamps) = &placement.timestamps {
+        let positions = explicit_positions(timestamps, num_chunks, total_duration, chunk_duration)?;
+        (chunk_duration, positions)
+    } else {
+        let positions = chunk_positions(num_chunks, total_duration, chunk_duration, placement)?;
+        (chunk_duration, positions)
+    };
 
     // Extract all chunks
     let mut all_samples = Vec::new();
     let mut sample_rate = 44100;
 
-    for &start_pos in &positions {
-        let (samples, sr) = extract_chunk(input_path, start_pos, chunk_duration)?;
-        sample_rate = sr;
+    for (index, &start_pos) in positions.iter().enumerate() {
+        let chunk = extract_chunk(input_path, start_pos, chunk_duration)?;
+        sample_rate = chunk.sample_rate;
+        if chunk.seek_method != SeekMethod::Accurate {
+            log::info!(
+                "Chunk at {:.1}s is approximate ({}, started at {})",
+                start_pos,
+                chunk.seek_method,
+                chunk
+                    .actual_start_secs
+                    .map_or_else(|| "0.0s".to_string(), |s| format!("{:.1}s", s))
+            );
+        }
+
+        let samples = match compress_silence {
+            Some(compression) => compress_silences(chunk.samples, sample_rate, compression),
+            None => chunk.samples,
+        };
 
         // Apply fade in/out
-        let faded = apply_fades(samples, sample_rate);
+        let faded = apply_fades(samples, sample_rate, fade_curve);
         all_samples.extend(faded);
+        on_chunk((index + 1) as f64 / positions.len() as f64);
     }
 
-    // Write to WAV file
-    write_wav(output_path, &all_samples, sample_rate)?;
+    Ok((all_samples, sample_rate))
+}
+
+/// The first `num_chunks` of the user's start times, checked against the
+/// track: each chunk has to fit before the end and come after the previous
+/// one. Overlapping chunks repeat audio, so they only warn.
+fn explicit_positions(
+    timestamps: &[f64],
+    num_chunks: usize,
+    total_duration: f64,
+    chunk_duration: f64,
+) -> Result<Vec<f64>> {
+    let Some(positions) = timestamps.get(..num_chunks) else {
+        anyhow::bail!(
+            "--preview-timestamps gives {} start times but a {:.0}s preview needs {}",
+            timestamps.len(),
+            num_chunks as f64 * chunk_duration,
+            num_chunks
+        );
+    };
 
-    Ok(())
+    for (index, &start) in positions.iter().enumerate() {
+        if start + chunk_duration > total_duration {
+            anyhow::bail!(
+                "Preview timestamp {} leaves less than {:.0}s before the end of the track ({})",
+                format_timestamp(start),
+                chunk_duration,
+                format_timestamp(total_duration)
+            );
+        }
+        if index > 0 {
+            let previous = positions[index - 1];
+            if start <= previous {
+                anyhow::bail!(
+                    "Preview timestamps must be in order: {} comes after {}",
+                    format_timestamp(start),
+                    format_timestamp(previous)
+                );
+            }
+            if start < previous + chunk_duration {
+                log::warn!(
+                    "Preview chunks at {} and {} overlap, some audio will play twice",
+                    format_timestamp(previous),
+                    format_timestamp(start)
+                );
+            }
+        }
+    }
+
+    Ok(positions.to_vec())
 }
 
-/// Extract a chunk of audio starting at a specific position
-fn extract_chunk(
-    input_path: &Path,
-    start_secs: f64,
-    duration_secs: f64,
-) -> Result<(Vec<f32>, u32)> {
-    let file = File::open(input_path)?;
-    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+/// `H:MM:SS` or `M:SS`
+fn format_timestamp(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
 
-    let mut hint = Hint::new();
-    if let Some(ext) = input_path.extension().and_then(|e| e.to_str()) {
-        hint.with_extension(ext);
+/// Start positions of each chunk in a preview of `num_chunks` chunks
+fn chunk_positions(
+    num_chunks: usize,
+    total_duration: f64,
+    chunk_duration: f64,
+    placement: &PreviewPlacement,
+) -> Result<Vec<f64>> {
+    let last_start = (total_duration - chunk_duration).max(0.0);
+    let mut positions = Vec::new();
+
+    match placement.strategy {
+        PreviewStrategy::Even => {
+            // A third of the chunks each from the intro, middle and end; the
+            // middle gets any spare one, then the intro. 30s, 60s and 90s
+            // previews are 1+1+1, 2+2+2 and 3+3+3 chunks.
+            let base = num_chunks / 3;
+            let intro = base + usize::from(num_chunks % 3 == 2);
+            let middle = base + usize::from(!num_chunks.is_multiple_of(3));
+            let end = base;
+
+            positions.extend((0..intro).map(|i| i as f64 * chunk_duration));
+            let middle_start = total_duration / 2.0 - middle as f64 / 2.0 * chunk_duration;
+            positions.extend((0..middle).map(|i| middle_start + i as f64 * chunk_duration));
+            // End chunks stay after the others on tracks too short to hold them all
+            positions.extend((0..end).map(|i| {
+                (total_duration - (end - i) as f64 * chunk_duration)
+                    .max((intro + middle + i) as f64 * chunk_duration)
+            }));
+        }
+        PreviewStrategy::IntroHeavy => {
+            // Half the chunks play the intro straight through, the rest are
+            // spread evenly over the remainder ending on the last chunk
+            let intro = num_chunks.div_ceil(2);
+            let rest = num_chunks - intro;
+            let intro_end = intro as f64 * chunk_duration;
+            positions.extend((0..intro).map(|i| i as f64 * chunk_duration));
+            positions.extend(
+                (1..=rest).map(|i| {
+                    intro_end + (last_start - intro_end).max(0.0) * i as f64 / rest as f64
+                }),
+            );
+        }
+        PreviewStrategy::Climax => {
+            // One intro chunk for context, then a continuous run around the peak
+            let peak = placement.climax_at.unwrap_or(total_duration * 0.75);
+            if peak >= total_duration {
+                anyhow::bail!(
+                    "--climax-at {:.0}s is past the end of the track ({:.0}s)",
+                    peak,
+                    total_duration
+                );
+            }
+            let rest = num_chunks.saturating_sub(1);
+            let run = rest as f64 * chunk_duration;
+            let start = (peak - run / 2.0)
+                .min(total_duration - run)
+                .max(chunk_duration.min(last_start));
+            positions.push(0.0);
+            positions.extend((0..rest).map(|i| start + i as f64 * chunk_duration));
+        }
     }
 
-    let meta_opts = MetadataOptions::default();
-    let fmt_opts = FormatOptions::default();
+    // On short tracks, or previews longer than the track, chunks repeat
+    // audio rather than seeking before the start or past the end
+    for position in &mut positions {
+        *position = position.clamp(0.0, last_start);
+    }
+    Ok(positions)
+}
 
-    let probed = symphonia::default::get_probe()
-        .format(&hint, mss, &fmt_opts, &meta_opts)
-        .context("Failed to probe audio file")?;
+/// How `extract_chunk` got to the requested start position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekMethod {
+    /// Sample-accurate seek
+    Accurate,
+    /// Nearest seek point the format offers, usually a little early
+    Coarse,
+    /// No seeking; packets were skipped from the start of the file
+    DecodeForward,
+}
 
-    let mut format = probed.format;
+impl std::fmt::Display for SeekMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeekMethod::Accurate => write!(f, "accurate seek"),
+            SeekMethod::Coarse => write!(f, "coarse seek"),
+            SeekMethod::DecodeForward => write!(f, "decode forward"),
+        }
+    }
+}
+
+/// Decoded mono audio for one preview chunk
+struct Chunk {
+    samples: Vec<f32>,
+    sample_rate: u32,
+    /// Position the decoder actually landed on after seeking
+    actual_start_secs: Option<f64>,
+    /// Anything but `Accurate` means the boundaries are approximate
+    seek_method: SeekMethod,
+}
+
+/// Extract a chunk of audio starting at a specific position
+fn extract_chunk(input_path: &Path, start_secs: f64, duration_secs: f64) -> Result<Chunk> {
+    let mut format = probe_audio(input_path)?;
     let track = format
         .default_track()
         .context("No default audio track found")?;
 
     let track_id = track.id;
     let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
+    let time_base = track.codec_params.time_base;
+    let mut rate_check = RateCheck(track.codec_params.sample_rate);
 
     let mut decoder = symphonia::default::get_codecs()
         .make(&track.codec_params, &DecoderOptions::default())
         .context("Failed to create decoder")?;
 
-    // Seek to start position
-    let seek_to = SeekTo::Time {
+    // Seek to start position; `seek` takes the target by value
+    let seek_to = || SeekTo::Time {
         time: Time::from(start_secs),
         track_id: Some(track_id),
     };
 
-    let _ = format.seek(SeekMode::Accurate, seek_to);
+    let ts_to_secs = |ts: u64| {
+        time_base.map(|tb| {
+            let time = tb.calc_time(ts);
+            time.seconds as f64 + time.frac
+        })
+    };
+
+    // Seeks land on a packet boundary at or before the requested time; this
+    // many leading frames have to be dropped to start exactly on it. Measured
+    // from `start_secs` rather than `required_ts`, which symphonia truncates
+    // and so can sit a frame early
+    let frames_before =
+        |seeked: &symphonia::core::formats::SeekedTo| match ts_to_secs(seeked.actual_ts) {
+            Some(actual) if start_secs > actual => {
+                ((start_secs - actual) * sample_rate as f64).round() as usize
+            }
+            _ => 0,
+        };
+    // Where the chunk really starts once leading frames are dropped
+    let start_after_skip = |seeked: &symphonia::core::formats::SeekedTo| {
+        ts_to_secs(seeked.actual_ts).map(|actual| actual.max(start_secs))
+    };
+
+    // Not every format/decoder supports accurate seeking, so fall back to a
+    // coarse seek, and failing that, skip packets from the start
+    let mut first_packet = None;
+    let mut skip_frames = 0;
+    let (seek_method, actual_start_secs) = match format.seek(SeekMode::Accurate, seek_to()) {
+        Ok(seeked) => {
+            skip_frames = frames_before(&seeked);
+            (SeekMethod::Accurate, start_after_skip(&seeked))
+        }
+        Err(accurate_err) => match format.seek(SeekMode::Coarse, seek_to()) {
+            Ok(seeked) => {
+                log::debug!("Accurate seek failed ({}), used coarse seek", accurate_err);
+                skip_frames = frames_before(&seeked);
+                (SeekMethod::Coarse, start_after_skip(&seeked))
+            }
+            Err(coarse_err) => {
+                log::debug!(
+                    "Seeking failed (accurate: {}, coarse: {}), decoding forward",
+                    accurate_err,
+                    coarse_err
+                );
+                // Without a time base there's no way to tell where a packet
+                // is, so decoding starts from the beginning of the file
+                let mut reached = None;
+                if time_base.is_some() && start_secs > 0.0 {
+                    while let Ok(packet) = format.next_packet() {
+                        if packet.track_id() != track_id {
+                            continue;
+                        }
+                        let packet_secs = ts_to_secs(packet.ts()).unwrap_or(0.0);
+                        if packet_secs >= start_secs {
+                            reached = Some(packet_secs);
+                            first_packet = Some(packet);
+                            break;
+                        }
+                    }
+                }
+                (SeekMethod::DecodeForward, reached)
+            }
+        },
+    };
+    log::debug!("Chunk at {:.3}s positioned by {}", start_secs, seek_method);
 
     let mut samples = Vec::new();
-    let target_samples = (duration_secs * sample_rate as f64) as usize;
+    let target_samples = (duration_secs * sample_rate as f64).round() as usize;
 
     while samples.len() < target_samples {
-        let packet = match format.next_packet() {
-            Ok(packet) => packet,
-            Err(_) => break,
+        let packet = match first_packet.take() {
+            Some(packet) => packet,
+            None => match format.next_packet() {
+                Ok(packet) => packet,
+                Err(_) => break,
+            },
         };
 
         if packet.track_id() != track_id {
@@ -195,17 +963,146 @@ fn extract_chunk(
 
         match decoder.decode(&packet) {
             Ok(decoded) => {
+                rate_check.check(decoded.spec().rate)?;
                 let chunk = convert_to_f32_mono(&decoded);
-                samples.extend(chunk);
+                let skip = skip_frames.min(chunk.len());
+                skip_frames -= skip;
+                samples.extend_from_slice(&chunk[skip..]);
             }
             Err(_) => continue,
         }
     }
 
-    // Trim to exact length
-    samples.truncate(target_samples);
+    // Exactly `target_samples` long so chunks join without drift; a chunk that
+    // runs past the end of the file is padded with silence
+    if samples.len() < target_samples {
+        log::debug!(
+            "Chunk at {:.3}s ended {} frames early, padding with silence",
+            start_secs,
+            target_samples - samples.len()
+        );
+    }
+    samples.resize(target_samples, 0.0);
+
+    Ok(Chunk {
+        samples,
+        sample_rate,
+        actual_start_secs,
+        seek_method,
+    })
+}
+
+/// Extract a single chunk with fades and write it to `output_path`, exactly as
+/// it would appear in a preview. Returns where decoding actually started (None
+/// if it began at the start of the file) and how that position was reached.
+pub fn debug_chunk(
+    input_path: &Path,
+    start_secs: f64,
+    duration_secs: f64,
+    output_path: &Path,
+) -> Result<(Option<f64>, SeekMethod)> {
+    let chunk = extract_chunk(input_path, start_secs, duration_secs)?;
+    let faded = apply_fades(chunk.samples, chunk.sample_rate, FadeCurve::default());
+    write_wav(
+        output_path,
+        &faded,
+        chunk.sample_rate,
+        WavSampleFormat::Int16,
+    )?;
+
+    Ok((chunk.actual_start_secs, chunk.seek_method))
+}
+
+/// Bad packets described in a [`DecodeReport`]; the rest are only counted
+const MAX_REPORTED_DECODE_ERRORS: usize = 5;
+
+/// What a full decode of a file found
+#[derive(Debug, Clone, Default)]
+pub struct DecodeReport {
+    /// Length of the audio that decoded
+    pub decoded_secs: f64,
+    /// Packets the decoder rejected; their audio is missing
+    pub bad_packets: u64,
+    /// Where the first few bad packets are and why
+    pub errors: Vec<String>,
+}
+
+/// Decode every packet of the file, the way chunks are decoded for
+/// previews, to catch corruption that probing the headers misses. Packets
+/// that fail to decode are counted in the report; a file that can't be read
+/// to the end is an error.
+pub fn verify_decode(input_path: &Path) -> Result<DecodeReport> {
+    let mut format = probe_audio(input_path)?;
+    let track = format
+        .default_track()
+        .context("No default audio track found")?;
+
+    let track_id = track.id;
+    let time_base = track.codec_params.time_base;
+    let mut rate_check = RateCheck(track.codec_params.sample_rate);
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .context("Failed to create decoder")?;
+
+    let mut report = DecodeReport::default();
+    let mut frames = 0u64;
+    loop {
+        let decoded_secs = rate_check.0.map_or(0.0, |rate| frames as f64 / rate as f64);
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            // A chained Ogg stream starting over, not damage
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Reading stopped after {} of audio",
+                        format_timestamp(decoded_secs)
+                    )
+                });
+            }
+        };
 
-    Ok((samples, sample_rate))
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                rate_check.check(decoded.spec().rate)?;
+                frames += decoded.frames() as u64;
+            }
+            Err(SymphoniaError::DecodeError(reason)) => {
+                report.bad_packets += 1;
+                if report.errors.len() < MAX_REPORTED_DECODE_ERRORS {
+                    let at = time_base.map_or(decoded_secs, |tb| {
+                        let time = tb.calc_time(packet.ts());
+                        time.seconds as f64 + time.frac
+                    });
+                    report
+                        .errors
+                        .push(format!("{}: {}", format_timestamp(at), reason));
+                }
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Decoding stopped after {} of audio",
+                        format_timestamp(decoded_secs)
+                    )
+                });
+            }
+        }
+    }
+
+    match rate_check.0 {
+        Some(rate) if frames > 0 => report.decoded_secs = frames as f64 / rate as f64,
+        _ => anyhow::bail!("No audio decoded from file"),
+    }
+    Ok(report)
 }
 
 /// Convert AudioBufferRef to mono f32 samples
@@ -254,47 +1151,1178 @@ fn convert_to_f32_mono(decoded: &AudioBufferRef) -> Vec<f32> {
     }
 }
 
-/// Apply 1-second fade in and fade out
-fn apply_fades(mut samples: Vec<f32>, sample_rate: u32) -> Vec<f32> {
-    let fade_samples = sample_rate as usize; // 1 second
+/// Fade-in gain at `t` (0.0 to 1.0) through the fade; a fade-out is the
+/// same curve run backwards
+fn fade_gain(curve: FadeCurve, t: f32) -> f32 {
+    match curve {
+        FadeCurve::Linear => t,
+        FadeCurve::EqualPower => (t * std::f32::consts::FRAC_PI_2).sin(),
+    }
+}
+
+/// Apply 1-second fade in and fade out, or half the length each for
+/// snippets under two seconds
+fn apply_fades(mut samples: Vec<f32>, sample_rate: u32, curve: FadeCurve) -> Vec<f32> {
     let len = samples.len();
+    let fade_samples = (sample_rate as usize).min(len / 2);
 
-    if len <= fade_samples * 2 {
+    if fade_samples == 0 {
         return samples;
     }
 
     // Fade in
     for (i, sample) in samples.iter_mut().enumerate().take(fade_samples) {
-        let factor = i as f32 / fade_samples as f32;
+        let factor = fade_gain(curve, i as f32 / fade_samples as f32);
         *sample *= factor;
     }
 
-    // Fade out
-    for (i, sample) in samples.iter_mut().enumerate().take(fade_samples) {
-        let factor = 1.0 - (i as f32 / fade_samples as f32);
+    // Fade out, mirroring the fade in so the last sample is silent
+    for (i, sample) in samples[len - fade_samples..].iter_mut().enumerate() {
+        let factor = fade_gain(curve, (fade_samples - 1 - i) as f32 / fade_samples as f32);
         *sample *= factor;
     }
 
     samples
 }
 
+/// Length of the level blocks used to find quiet passages in a chunk
+const SILENCE_BLOCK_SECS: f64 = 0.01;
+/// Fade either side of a cut in a quiet passage, so the join can't click
+const SILENCE_CUT_FADE_SECS: f64 = 0.005;
+
+/// Sample ranges quieter than `threshold_db` for longer than `min_secs`,
+/// found from the RMS level of short blocks
+fn find_silences(
+    samples: &[f32],
+    sample_rate: u32,
+    threshold_db: f64,
+    min_secs: f64,
+) -> Vec<std::ops::Range<usize>> {
+    let block = ((sample_rate as f64 * SILENCE_BLOCK_SECS) as usize).max(1);
+    let threshold = 10f32.powf(threshold_db as f32 / 20.0);
+    let min_len = (sample_rate as f64 * min_secs) as usize;
+
+    let mut silences = Vec::new();
+    let mut start = None;
+    for (index, block_samples) in samples.chunks(block).enumerate() {
+        let power = block_samples.iter().map(|s| s * s).sum::<f32>() / block_samples.len() as f32;
+        let position = index * block;
+        if power.sqrt() < threshold {
+            start.get_or_insert(position);
+        } else if let Some(from) = start.take()
+            && position - from > min_len
+        {
+            silences.push(from..position);
+        }
+    }
+    if let Some(from) = start
+        && samples.len() - from > min_len
+    {
+        silences.push(from..samples.len());
+    }
+    silences
+}
+
+/// Cut the middle out of every quiet passage longer than the maximum gap,
+/// leaving the gap's length of it, with a few milliseconds of fade either
+/// side of each cut
+fn compress_silences(
+    samples: Vec<f32>,
+    sample_rate: u32,
+    compression: SilenceCompression,
+) -> Vec<f32> {
+    let silences = find_silences(
+        &samples,
+        sample_rate,
+        compression.threshold_db,
+        compression.max_gap_secs,
+    );
+    if silences.is_empty() {
+        return samples;
+    }
+
+    // Keep half of the allowed gap at each end of the passage
+    let max_gap = (sample_rate as f64 * compression.max_gap_secs) as usize;
+    let mut kept = Vec::with_capacity(silences.len() + 1);
+    let mut from = 0;
+    for silence in silences {
+        kept.push(from..silence.start + max_gap / 2);
+        from = silence.end - (max_gap - max_gap / 2);
+    }
+    kept.push(from..samples.len());
+
+    let fade = ((sample_rate as f64 * SILENCE_CUT_FADE_SECS) as usize).max(1);
+    let mut compressed = Vec::with_capacity(kept.iter().map(|range| range.len()).sum());
+    for (index, range) in kept.iter().enumerate() {
+        let start = compressed.len();
+        compressed.extend_from_slice(&samples[range.clone()]);
+        let piece = &mut compressed[start..];
+        let fade = fade.min(piece.len() / 2);
+        if index > 0 {
+            for (i, sample) in piece[..fade].iter_mut().enumerate() {
+                *sample *= i as f32 / fade as f32;
+            }
+        }
+        if index + 1 < kept.len() {
+            let end = piece.len() - fade;
+            for (i, sample) in piece[end..].iter_mut().rev().enumerate() {
+                *sample *= i as f32 / fade as f32;
+            }
+        }
+    }
+    compressed
+}
+
 /// Write samples to WAV file
-fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) -> Result<()> {
+fn write_wav(
+    path: &Path,
+    samples: &[f32],
+    sample_rate: u32,
+    sample_format: WavSampleFormat,
+) -> Result<()> {
+    let (bits_per_sample, format) = match sample_format {
+        WavSampleFormat::Int16 => (16, hound::SampleFormat::Int),
+        WavSampleFormat::Float => (32, hound::SampleFormat::Float),
+    };
     let spec = hound::WavSpec {
         channels: 1,
         sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
+        bits_per_sample,
+        sample_format: format,
     };
 
     let mut writer = hound::WavWriter::create(path, spec).context("Failed to create WAV writer")?;
 
-    for &sample in samples {
-        let sample_i16 = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
-        writer.write_sample(sample_i16)?;
+    match sample_format {
+        WavSampleFormat::Int16 => {
+            for &sample in samples {
+                writer.write_sample(to_i16(sample))?;
+            }
+        }
+        // Float keeps the samples as decoded, overs included
+        WavSampleFormat::Float => {
+            for &sample in samples {
+                writer.write_sample(sample)?;
+            }
+        }
     }
 
     writer.finalize()?;
 
     Ok(())
 }
+
+/// Bitrate of MP3 previews
+pub const MP3_PREVIEW_KBPS: u64 = 192;
+
+/// Encode mono samples as a constant bitrate MP3, see [`MP3_PREVIEW_KBPS`]
+#[cfg(feature = "mp3")]
+fn write_mp3(path: &Path, samples: &[f32], sample_rate: u32) -> Result<()> {
+    use mp3lame_encoder::{Bitrate, Builder, FlushNoGap, MonoPcm, Quality};
+
+    let mut builder = Builder::new().context("Failed to create MP3 encoder")?;
+    builder.set_num_channels(1).map_err(lame_error)?;
+    builder
+        .set_sample_rate(sample_rate)
+        .map_err(lame_error)
+        .with_context(|| format!("Can't encode {} Hz audio as MP3", sample_rate))?;
+    builder.set_brate(Bitrate::Kbps192).map_err(lame_error)?;
+    builder.set_quality(Quality::Best).map_err(lame_error)?;
+    let mut encoder = builder.build().map_err(lame_error)?;
+
+    let pcm: Vec<i16> = samples.iter().map(|&sample| to_i16(sample)).collect();
+    let mut mp3 = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(pcm.len()));
+    encoder
+        .encode_to_vec(MonoPcm(&pcm), &mut mp3)
+        .map_err(lame_error)
+        .context("Failed to encode MP3")?;
+    encoder
+        .flush_to_vec::<FlushNoGap>(&mut mp3)
+        .map_err(lame_error)
+        .context("Failed to encode MP3")?;
+
+    std::fs::write(path, mp3).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(not(feature = "mp3"))]
+fn write_mp3(_path: &Path, _samples: &[f32], _sample_rate: u32) -> Result<()> {
+    anyhow::bail!("MP3 previews need a build with the `mp3` feature; use --preview-format wav")
+}
+
+/// The LAME wrapper's errors are plain enums
+#[cfg(feature = "mp3")]
+fn lame_error(e: impl std::fmt::Debug) -> anyhow::Error {
+    anyhow::anyhow!("MP3 encoder: {:?}", e)
+}
+
+/// A float sample as 16-bit PCM, clipping overs
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+/// Peak-normalize an audio file so its loudest sample hits `target_dbfs`.
+/// A single gain is applied to all channels so the stereo balance is preserved.
+/// Files that are already hotter than the target are turned down.
+/// Writes `<stem>_normalized.wav` in `sample_format` into `output_dir` and
+/// returns its path.
+pub fn normalize_peak(
+    input_path: &Path,
+    target_dbfs: f64,
+    sample_format: WavSampleFormat,
+    output_dir: &Path,
+    existing: ExistingOutput,
+) -> Result<PathBuf> {
+    if target_dbfs > 0.0 {
+        anyhow::bail!(
+            "Peak target must be at or below 0 dBFS, got {}",
+            target_dbfs
+        );
+    }
+
+    let stem = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid file name")?;
+    let output_path = output_dir.join(format!("{}_normalized.wav", stem));
+    if !crate::output::should_write(&output_path, existing)? {
+        return Ok(output_path);
+    }
+
+    // First pass: find the absolute peak across all channels
+    let mut peak = 0.0f32;
+    for_each_interleaved(input_path, |samples, _| {
+        for &sample in samples {
+            peak = peak.max(sample.abs());
+        }
+        Ok(())
+    })?;
+
+    if peak <= 0.0 {
+        anyhow::bail!("Audio file is silent, nothing to normalize");
+    }
+
+    let target_linear = 10f64.powf(target_dbfs / 20.0) as f32;
+    let gain = target_linear / peak;
+
+    log::info!(
+        "Peak {:.2} dBFS, applying {:+.2} dB of gain",
+        20.0 * (peak as f64).log10(),
+        20.0 * (gain as f64).log10()
+    );
+
+    // Second pass: apply the gain and write the result
+    let (bits_per_sample, hound_format) = match sample_format {
+        WavSampleFormat::Int16 => (16, hound::SampleFormat::Int),
+        WavSampleFormat::Float => (32, hound::SampleFormat::Float),
+    };
+    let mut writer = None;
+    for_each_interleaved(input_path, |samples, spec| {
+        if writer.is_none() {
+            let wav_spec = hound::WavSpec {
+                channels: spec.channels.count() as u16,
+                sample_rate: spec.rate,
+                bits_per_sample,
+                sample_format: hound_format,
+            };
+            writer = Some(
+                hound::WavWriter::create(&output_path, wav_spec)
+                    .context("Failed to create WAV writer")?,
+            );
+        }
+
+        if let Some(writer) = writer.as_mut() {
+            for &sample in samples {
+                let scaled = (sample * gain).clamp(-1.0, 1.0);
+                match sample_format {
+                    WavSampleFormat::Int16 => writer.write_sample(to_i16(scaled))?,
+                    WavSampleFormat::Float => writer.write_sample(scaled)?,
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    writer.context("No audio decoded from file")?.finalize()?;
+
+    Ok(output_path)
+}
+
+/// Levels reported for digital silence instead of -inf; quieter than
+/// anything 24-bit audio can hold
+pub const SILENCE_DBFS: f64 = -144.0;
+
+/// Sample levels across a whole file, all channels together. Both levels
+/// bottom out at [`SILENCE_DBFS`].
+#[derive(Debug, Clone, Copy)]
+pub struct Levels {
+    pub peak_dbfs: f64,
+    pub rms_dbfs: f64,
+    /// Samples at or beyond full scale
+    pub clipped_samples: u64,
+}
+
+/// Decode the whole file and measure its peak, RMS level and clipping
+pub fn analyze_levels(input_path: &Path) -> Result<Levels> {
+    let mut peak = 0.0f32;
+    let mut sum = 0.0f64;
+    let mut count = 0u64;
+    let mut clipped = 0u64;
+    for_each_interleaved(input_path, |samples, _| {
+        for &sample in samples {
+            let level = sample.abs();
+            peak = peak.max(level);
+            sum += (sample * sample) as f64;
+            if level >= 1.0 {
+                clipped += 1;
+            }
+        }
+        count += samples.len() as u64;
+        Ok(())
+    })?;
+
+    if count == 0 {
+        anyhow::bail!("No audio decoded from file");
+    }
+
+    Ok(Levels {
+        peak_dbfs: (20.0 * (peak as f64).log10()).max(SILENCE_DBFS),
+        rms_dbfs: (10.0 * (sum / count as f64).log10()).max(SILENCE_DBFS),
+        clipped_samples: clipped,
+    })
+}
+
+/// Frames per block when reading waveform peaks
+const WAVEFORM_BLOCK_FRAMES: usize = 1024;
+
+/// Loudest sample in each of `columns` equal slices of the file, from 0.0 to
+/// 1.0, for drawing a waveform
+pub fn waveform_peaks(input_path: &Path, columns: usize) -> Result<Vec<f32>> {
+    let mut blocks = Vec::new();
+    let mut block_len = 0;
+    let mut peak = 0.0f32;
+    let mut count = 0;
+    for_each_interleaved(input_path, |samples, spec| {
+        if block_len == 0 {
+            block_len = WAVEFORM_BLOCK_FRAMES * spec.channels.count();
+        }
+        for &sample in samples {
+            peak = peak.max(sample.abs());
+            count += 1;
+            if count == block_len {
+                blocks.push(peak);
+                peak = 0.0;
+                count = 0;
+            }
+        }
+        Ok(())
+    })?;
+    if count > 0 {
+        blocks.push(peak);
+    }
+
+    if blocks.is_empty() {
+        anyhow::bail!("No audio decoded from file");
+    }
+    Ok(pool_peaks(&blocks, columns))
+}
+
+/// Reduce block peaks to `columns` values, keeping the loudest of each group
+fn pool_peaks(blocks: &[f32], columns: usize) -> Vec<f32> {
+    (0..columns)
+        .map(|column| {
+            let start = column * blocks.len() / columns;
+            let end = ((column + 1) * blocks.len() / columns).max(start + 1);
+            blocks[start.min(blocks.len() - 1)..end.min(blocks.len())]
+                .iter()
+                .copied()
+                .fold(0.0_f32, f32::max)
+        })
+        .collect()
+}
+
+/// Hop between energy readings for tempo detection
+const BPM_HOP_SECS: f64 = 0.01;
+/// Tempo range searched, wide enough for most DJ sets
+const BPM_RANGE: (f64, f64) = (70.0, 180.0);
+
+/// Estimate the tempo of a file from the periodicity of its energy onsets.
+/// Returns None when no beat stands out, e.g. for ambient or spoken audio.
+pub fn estimate_bpm(input_path: &Path) -> Result<Option<f64>> {
+    let mut envelope = Vec::new();
+    let mut hop_len = 0;
+    let mut sum = 0.0f64;
+    let mut count = 0;
+    for_each_interleaved(input_path, |samples, spec| {
+        if hop_len == 0 {
+            hop_len = ((spec.rate as f64 * BPM_HOP_SECS) as usize * spec.channels.count()).max(1);
+        }
+        for &sample in samples {
+            sum += (sample * sample) as f64;
+            count += 1;
+            if count == hop_len {
+                envelope.push((sum / count as f64) as f32);
+                sum = 0.0;
+                count = 0;
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(bpm_from_envelope(&envelope, BPM_HOP_SECS))
+}
+
+/// Tempo from an energy envelope: autocorrelate the rises in energy and pick
+/// the strongest lag within `BPM_RANGE`
+fn bpm_from_envelope(envelope: &[f32], hop_secs: f64) -> Option<f64> {
+    let onsets: Vec<f32> = envelope
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).max(0.0))
+        .collect();
+
+    let min_lag = (60.0 / BPM_RANGE.1 / hop_secs).round() as usize;
+    let max_lag = (60.0 / BPM_RANGE.0 / hop_secs).round() as usize;
+    if onsets.len() <= max_lag * 4 {
+        return None;
+    }
+
+    let energy: f32 = onsets.iter().map(|o| o * o).sum();
+    if energy <= 0.0 {
+        return None;
+    }
+
+    let (best_lag, best_score) = (min_lag..=max_lag)
+        .map(|lag| {
+            let score: f32 = onsets.iter().zip(&onsets[lag..]).map(|(a, b)| a * b).sum();
+            (lag, score)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    // Require the beat to carry a reasonable share of the onset energy
+    if best_score < energy * 0.1 {
+        return None;
+    }
+
+    Some(60.0 / (best_lag as f64 * hop_secs))
+}
+
+/// Length of the loudness blocks used to find quiet split points
+const SPLIT_BLOCK_SECS: f64 = 0.1;
+/// How far back from each nominal boundary to look for a quiet spot
+const SPLIT_SEARCH_SECS: f64 = 30.0;
+
+/// Find where to cut a file into parts of at most `part_secs`.
+/// Each cut is moved back to the quietest point within 30s of the nominal
+/// boundary, so a part never runs long and tracks are rarely cut mid-phrase.
+pub fn find_split_points(input_path: &Path, part_secs: f64) -> Result<Vec<f64>> {
+    if part_secs <= 0.0 {
+        anyhow::bail!("Part length must be greater than zero");
+    }
+
+    // RMS level of each block, all channels together
+    let mut levels = Vec::new();
+    let mut block_len = 0;
+    let mut sum = 0.0f64;
+    let mut count = 0;
+    for_each_interleaved(input_path, |samples, spec| {
+        if block_len == 0 {
+            block_len =
+                ((spec.rate as f64 * SPLIT_BLOCK_SECS) as usize * spec.channels.count()).max(1);
+        }
+        for &sample in samples {
+            sum += (sample * sample) as f64;
+            count += 1;
+            if count == block_len {
+                levels.push((sum / count as f64).sqrt() as f32);
+                sum = 0.0;
+                count = 0;
+            }
+        }
+        Ok(())
+    })?;
+    if count > 0 {
+        levels.push((sum / count as f64).sqrt() as f32);
+    }
+    if levels.is_empty() {
+        anyhow::bail!("No audio decoded from file");
+    }
+
+    Ok(choose_split_points(
+        &levels,
+        SPLIT_BLOCK_SECS,
+        part_secs,
+        SPLIT_SEARCH_SECS,
+    ))
+}
+
+/// Pick cut points from per-block levels: the quietest block in the
+/// `search_secs` before each nominal boundary, the latest one on a tie
+fn choose_split_points(
+    levels: &[f32],
+    block_secs: f64,
+    part_secs: f64,
+    search_secs: f64,
+) -> Vec<f64> {
+    let total_blocks = levels.len();
+    let part_blocks = ((part_secs / block_secs) as usize).max(1);
+    // Never search back more than half a part, or parts could shrink to nothing
+    let search_blocks = ((search_secs / block_secs) as usize).min(part_blocks / 2);
+
+    let mut cuts = Vec::new();
+    let mut nominal = part_blocks;
+    while nominal < total_blocks {
+        let cut = (nominal - search_blocks..=nominal)
+            .filter(|&i| i < total_blocks)
+            .min_by(|&a, &b| levels[a].total_cmp(&levels[b]).then(b.cmp(&a)))
+            .unwrap_or(nominal);
+        cuts.push(cut as f64 * block_secs);
+        nominal = cut + part_blocks;
+    }
+    cuts
+}
+
+/// Split an MP3 at `cuts` (seconds) into `<stem>_part<N>.mp3` files in
+/// `output_dir`. Parts are cut between frames and copied without re-encoding,
+/// so they add up to the original. Other formats are refused: decoded parts
+/// would be larger than the mix. Returns the part paths in order.
+pub fn split_audio(
+    input_path: &Path,
+    cuts: &[f64],
+    output_dir: &Path,
+    existing: ExistingOutput,
+) -> Result<Vec<PathBuf>> {
+    let stem = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid file name")?;
+    let is_mp3 = probe_audio(input_path)?
+        .default_track()
+        .is_some_and(|track| track.codec_params.codec == CODEC_TYPE_MP3);
+    if !is_mp3 {
+        anyhow::bail!(
+            "Only MP3s can be split without re-encoding; {} would become larger WAV parts",
+            input_path.display()
+        );
+    }
+    let paths: Vec<PathBuf> = (1..=cuts.len() + 1)
+        .map(|n| output_dir.join(format!("{}_part{}.mp3", stem, n)))
+        .collect();
+
+    // Parts only make sense as a set, so either all are kept or all rewritten
+    let mut any_to_write = false;
+    for path in &paths {
+        any_to_write |= crate::output::should_write(path, existing)?;
+    }
+    if !any_to_write {
+        return Ok(paths);
+    }
+    split_mp3_frames(input_path, cuts, paths)
+}
+
+/// Copy the MP3 frames of a file into `paths`, starting each part with the
+/// first frame at or after its cut
+fn split_mp3_frames(
+    input_path: &Path,
+    cuts: &[f64],
+    mut paths: Vec<PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    use std::io::{BufWriter, Write};
+
+    let mut format = probe_audio(input_path)?;
+    let track = format
+        .default_track()
+        .context("No default audio track found")?;
+    let track_id = track.id;
+    let params = &track.codec_params;
+    let time_base = params
+        .time_base
+        .or_else(|| params.sample_rate.map(|rate| TimeBase::new(1, rate)))
+        .context("Could not determine MP3 frame timing")?;
+    let boundaries: Vec<u64> = cuts
+        .iter()
+        .map(|&secs| time_base.calc_timestamp(Time::from(secs)))
+        .collect();
+
+    let create = |path: &Path| {
+        File::create(path)
+            .map(BufWriter::new)
+            .with_context(|| format!("Failed to create {}", path.display()))
+    };
+    let mut part = 0;
+    let mut frames = 0u64;
+    let mut writer = create(&paths[0])?;
+    while let Ok(packet) = format.next_packet() {
+        if packet.track_id() != track_id {
+            continue;
+        }
+        if boundaries
+            .get(part)
+            .is_some_and(|&boundary| packet.ts() >= boundary)
+        {
+            writer.flush()?;
+            part += 1;
+            writer = create(&paths[part])?;
+        }
+        // Packets are whole frames, header included
+        writer.write_all(&packet.data)?;
+        frames += 1;
+    }
+    writer.flush()?;
+
+    if frames == 0 {
+        anyhow::bail!("No audio frames found in file");
+    }
+    paths.truncate(part + 1);
+    Ok(paths)
+}
+
+/// Decode the whole default track, handing interleaved f32 samples to `f`
+fn for_each_interleaved<F>(input_path: &Path, mut f: F) -> Result<()>
+where
+    F: FnMut(&[f32], &symphonia::core::audio::SignalSpec) -> Result<()>,
+{
+    let mut format = probe_audio(input_path)?;
+    let track = format
+        .default_track()
+        .context("No default audio track found")?;
+
+    let track_id = track.id;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .context("Failed to create decoder")?;
+
+    let mut sample_buf: Option<SampleBuffer<f32>> = None;
+    let mut rate_check = RateCheck(track.codec_params.sample_rate);
+
+    while let Ok(packet) = format.next_packet() {
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(_) => continue,
+        };
+
+        let spec = *decoded.spec();
+        rate_check.check(spec.rate)?;
+        let needed = decoded.capacity() * spec.channels.count();
+        if sample_buf
+            .as_ref()
+            .is_none_or(|buf| buf.capacity() < needed)
+        {
+            sample_buf = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+
+        if let Some(buf) = sample_buf.as_mut() {
+            buf.copy_interleaved_ref(decoded);
+            f(buf.samples(), &spec)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Container formats symphonia is built with (the `all` feature set)
+pub const INPUT_CONTAINERS: &[(&str, &str)] = &[
+    ("MP3 / MP2 / MP1", "mp3, mp2, mp1"),
+    ("WAV", "wav"),
+    ("AIFF", "aif, aiff, aifc"),
+    ("FLAC", "flac"),
+    ("Ogg", "ogg, oga"),
+    ("MP4 / M4A", "mp4, m4a, m4b, alac"),
+    ("Matroska / WebM", "mkv, mka, webm"),
+    ("Core Audio Format", "caf"),
+    ("ADTS AAC", "aac"),
+];
+
+/// Short and long names of every codec the default registry can decode
+pub fn supported_codecs() -> Vec<(&'static str, &'static str)> {
+    use symphonia::core::codecs::*;
+
+    let candidates = [
+        CODEC_TYPE_MP1,
+        CODEC_TYPE_MP2,
+        CODEC_TYPE_MP3,
+        CODEC_TYPE_AAC,
+        CODEC_TYPE_ALAC,
+        CODEC_TYPE_FLAC,
+        CODEC_TYPE_VORBIS,
+        CODEC_TYPE_OPUS,
+        CODEC_TYPE_WAVPACK,
+        CODEC_TYPE_ADPCM_IMA_WAV,
+        CODEC_TYPE_ADPCM_MS,
+        CODEC_TYPE_PCM_S16LE,
+        CODEC_TYPE_PCM_S24LE,
+        CODEC_TYPE_PCM_S32LE,
+        CODEC_TYPE_PCM_F32LE,
+        CODEC_TYPE_PCM_F64LE,
+        CODEC_TYPE_PCM_S16BE,
+        CODEC_TYPE_PCM_S24BE,
+        CODEC_TYPE_PCM_S32BE,
+        CODEC_TYPE_PCM_U8,
+        CODEC_TYPE_PCM_ALAW,
+        CODEC_TYPE_PCM_MULAW,
+    ];
+
+    let registry = symphonia::default::get_codecs();
+    candidates
+        .iter()
+        .filter_map(|&codec| registry.get_codec(codec))
+        .map(|descriptor| (descriptor.short_name, descriptor.long_name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytes in each frame written by [`write_silent_mp3`]
+    const SILENT_FRAME_LEN: usize = 417;
+
+    /// Write `frames` silent MPEG-1 Layer III frames (128 kbps, 44.1 kHz,
+    /// mono) with no Xing header or tags, 1152 samples per frame
+    fn write_silent_mp3(path: &Path, frames: usize) {
+        let mut frame = vec![0u8; SILENT_FRAME_LEN];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC4]);
+        std::fs::write(path, frame.repeat(frames)).unwrap();
+    }
+
+    #[test]
+    fn test_rate_check_rejects_changing_rate() {
+        let mut check = RateCheck(None);
+        assert!(check.check(44100).is_ok());
+        assert!(check.check(44100).is_ok());
+        assert!(check.check(48000).is_err());
+
+        let mut check = RateCheck(Some(48000));
+        assert!(check.check(44100).is_err());
+    }
+
+    #[test]
+    fn test_pool_peaks() {
+        let blocks = [0.1, 0.5, 0.2, 0.9, 0.3, 0.4];
+        assert_eq!(pool_peaks(&blocks, 3), vec![0.5, 0.9, 0.4]);
+        assert_eq!(pool_peaks(&blocks, 1), vec![0.9]);
+        // More columns than blocks repeats blocks instead of leaving gaps
+        assert_eq!(pool_peaks(&[0.2, 0.8], 4), vec![0.2, 0.2, 0.8, 0.8]);
+    }
+
+    #[test]
+    fn test_bpm_from_envelope() {
+        // A pulse every 0.5s is 120 BPM
+        let envelope: Vec<f32> = (0..3000)
+            .map(|i| if i % 50 == 0 { 1.0 } else { 0.0 })
+            .collect();
+        let bpm = bpm_from_envelope(&envelope, 0.01).unwrap();
+        assert!((bpm - 120.0).abs() < 0.5, "got {}", bpm);
+
+        assert_eq!(bpm_from_envelope(&vec![0.5; 3000], 0.01), None);
+        assert_eq!(bpm_from_envelope(&[1.0, 0.0, 1.0], 0.01), None);
+    }
+
+    #[test]
+    fn test_auto_preview_durations() {
+        assert_eq!(auto_preview_durations(20.0), Vec::<u64>::new());
+        assert_eq!(auto_preview_durations(90.0), vec![30]);
+        assert_eq!(auto_preview_durations(5.0 * 60.0), vec![30, 60]);
+        assert_eq!(auto_preview_durations(60.0 * 60.0), vec![30, 60, 90]);
+        assert_eq!(auto_preview_durations(3.0 * 3600.0), vec![30, 60, 90, 120]);
+    }
+
+    #[test]
+    fn test_fade_curves() {
+        // Summed power of a fade-out and fade-in overlapping at `t`
+        let crossfade_power =
+            |curve, t: f32| fade_gain(curve, t).powi(2) + fade_gain(curve, 1.0 - t).powi(2);
+
+        assert!((crossfade_power(FadeCurve::Linear, 0.5) - 0.5).abs() < 1e-6);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!((crossfade_power(FadeCurve::EqualPower, t) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_apply_fades_in_and_out() {
+        let rate = 100;
+        let faded = apply_fades(vec![1.0; 5 * rate as usize], rate, FadeCurve::Linear);
+        assert!(faded[0].abs() < 1e-6);
+        assert!(faded[faded.len() - 1].abs() < 1e-6);
+        assert_eq!(faded[faded.len() / 2], 1.0);
+        // Both fades stay within their second
+        assert_eq!(faded[rate as usize], 1.0);
+        assert_eq!(faded[faded.len() - rate as usize - 1], 1.0);
+    }
+
+    #[test]
+    fn test_compress_silences() {
+        // 1s of audio, 3s of silence, 1s of audio at 1kHz
+        let rate = 1000;
+        let mut samples = vec![0.5; 5000];
+        samples[1000..4000].fill(0.0);
+        let compression = SilenceCompression {
+            threshold_db: -45.0,
+            max_gap_secs: 1.0,
+        };
+
+        let compressed = compress_silences(samples.clone(), rate, compression);
+        assert_eq!(compressed.len(), 3000);
+        assert_eq!(compressed[0], 0.5);
+        assert_eq!(compressed[2999], 0.5);
+        // The cut is in silence, faded, so nothing jumps
+        assert!(compressed[1490..1510].iter().all(|s| *s == 0.0));
+
+        // A gap within the limit is left alone
+        samples[1000..4000].fill(0.5);
+        samples[2000..2800].fill(0.0);
+        assert_eq!(
+            compress_silences(samples.clone(), rate, compression),
+            samples
+        );
+    }
+
+    #[test]
+    fn test_choose_split_points() {
+        // 150s of 0.5s blocks with quiet spots at 55s and 110s
+        let mut levels = vec![1.0f32; 300];
+        levels[110] = 0.0;
+        levels[220] = 0.0;
+        assert_eq!(
+            choose_split_points(&levels, 0.5, 60.0, 10.0),
+            vec![55.0, 110.0]
+        );
+
+        // Without silence the cuts stay on the nominal boundaries
+        let levels = vec![1.0f32; 300];
+        assert_eq!(
+            choose_split_points(&levels, 0.5, 60.0, 10.0),
+            vec![60.0, 120.0]
+        );
+        assert!(choose_split_points(&levels, 0.5, 200.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_split_mp3_keeps_frames() {
+        let dir = crate::temp::TestDir::new("split");
+        let path = dir.join("mix.mp3");
+        // About 2.6s; 128 kbps is 16000 bytes a second
+        write_silent_mp3(&path, 100);
+        let cap = 16000 + SILENT_FRAME_LEN as u64;
+
+        let parts = split_audio(&path, &[1.0, 2.0], &dir, ExistingOutput::Overwrite).unwrap();
+        assert_eq!(parts.len(), 3);
+        let sizes: Vec<u64> = parts
+            .iter()
+            .map(|part| {
+                assert_eq!(part.extension().unwrap(), "mp3");
+                std::fs::metadata(part).unwrap().len()
+            })
+            .collect();
+        for &size in &sizes {
+            assert!(size > 0 && size <= cap, "part of {} bytes", size);
+            assert_eq!(size % SILENT_FRAME_LEN as u64, 0);
+        }
+        assert_eq!(
+            sizes.iter().sum::<u64>(),
+            std::fs::metadata(&path).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_chunk_lengths_are_exact() {
+        let dir = crate::temp::TestDir::new("chunks");
+        let path = dir.join("tone.wav");
+
+        // 25s ramp at 44.1 kHz where every sample is its frame index / FRAMES,
+        // so a chunk's first sample tells where it really starts
+        const FRAMES: u32 = 25 * 44100;
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 44100,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..FRAMES {
+            writer.write_sample(i as f32 / FRAMES as f32).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        // Includes an unaligned start, a fractional length, and one past the end
+        for (start, len) in [(0.0, 10.0), (7.3, 10.0), (12.5, 2.5), (20.0, 10.0)] {
+            let chunk = extract_chunk(&path, start, len).unwrap();
+            assert_eq!(chunk.sample_rate, 44100);
+            assert_eq!(
+                chunk.samples.len(),
+                (len * 44100.0) as usize,
+                "chunk at {}s",
+                start
+            );
+            let first_frame = (chunk.samples[0] as f64 * FRAMES as f64).round();
+            assert_eq!(
+                first_frame,
+                (start * 44100.0).round(),
+                "chunk at {}s",
+                start
+            );
+        }
+    }
+
+    #[test]
+    fn test_chunk_positions() {
+        let even = PreviewPlacement::default();
+        assert_eq!(
+            chunk_positions(3, 600.0, 10.0, &even).unwrap(),
+            vec![0.0, 295.0, 590.0]
+        );
+        assert_eq!(
+            chunk_positions(9, 600.0, 10.0, &even).unwrap(),
+            vec![0.0, 10.0, 20.0, 285.0, 295.0, 305.0, 570.0, 580.0, 590.0]
+        );
+        assert_eq!(chunk_positions(1, 600.0, 10.0, &even).unwrap(), vec![295.0]);
+        assert_eq!(
+            chunk_positions(2, 600.0, 10.0, &even).unwrap(),
+            vec![0.0, 295.0]
+        );
+        assert_eq!(
+            chunk_positions(5, 600.0, 10.0, &even).unwrap(),
+            vec![0.0, 10.0, 290.0, 300.0, 590.0]
+        );
+        // A 120s preview of a 60s track stays within it
+        let positions = chunk_positions(12, 60.0, 10.0, &even).unwrap();
+        assert!(positions.iter().all(|&p| (0.0..=50.0).contains(&p)));
+
+        let intro_heavy = PreviewPlacement {
+            strategy: PreviewStrategy::IntroHeavy,
+            ..Default::default()
+        };
+        assert_eq!(
+            chunk_positions(6, 340.0, 10.0, &intro_heavy).unwrap(),
+            vec![0.0, 10.0, 20.0, 130.0, 230.0, 330.0]
+        );
+
+        let climax = PreviewPlacement {
+            strategy: PreviewStrategy::Climax,
+            climax_at: Some(400.0),
+            timestamps: None,
+        };
+        assert_eq!(
+            chunk_positions(3, 600.0, 10.0, &climax).unwrap(),
+            vec![0.0, 390.0, 400.0]
+        );
+        let past_end = PreviewPlacement {
+            climax_at: Some(700.0),
+            ..climax
+        };
+        assert!(chunk_positions(3, 600.0, 10.0, &past_end).is_err());
+    }
+
+    #[test]
+    fn test_preview_of_track_shorter_than_a_chunk() {
+        let dir = crate::temp::TestDir::new("short");
+        let path = dir.join("jingle.wav");
+        write_wav(&path, &vec![0.5; 5 * 8000], 8000, WavSampleFormat::Int16).unwrap();
+
+        let snippets = create_preview_snippets(
+            &path,
+            &dir,
+            PreviewFormat::Wav,
+            WavSampleFormat::Int16,
+            &[30],
+            &PreviewPlacement::default(),
+            FadeCurve::default(),
+            None,
+            ExistingOutput::Overwrite,
+            &|_| {},
+        )
+        .unwrap();
+
+        // The whole jingle once, faded at both ends
+        let mut reader = hound::WavReader::open(&snippets[0]).unwrap();
+        assert_eq!(reader.spec().sample_rate, 8000);
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        assert_eq!(samples.len(), 5 * 8000);
+        assert_eq!(samples[0], 0);
+        assert_eq!(samples[samples.len() - 1], 0);
+        // Decoding reads 16-bit samples back over 32768, so allow one step
+        assert!((samples[samples.len() / 2] - to_i16(0.5)).abs() <= 1);
+    }
+
+    #[test]
+    fn test_verify_decode() {
+        let dir = crate::temp::TestDir::new("verify");
+        let path = dir.join("tone.wav");
+        write_wav(&path, &vec![0.25; 4 * 8000], 8000, WavSampleFormat::Int16).unwrap();
+
+        let report = verify_decode(&path).unwrap();
+        assert!((report.decoded_secs - 4.0).abs() < 1e-9);
+        assert_eq!(report.bad_packets, 0);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn test_measure_duration_sources() {
+        let dir = crate::temp::TestDir::new("duration");
+        let frame_secs = 1152.0 / 44100.0;
+
+        // WAV headers give the exact length
+        let wav = dir.join("tone.wav");
+        write_wav(&wav, &vec![0.25; 3 * 8000], 8000, WavSampleFormat::Int16).unwrap();
+        let duration = measure_duration(&wav, false).unwrap();
+        assert_eq!(duration.source, DurationSource::Container);
+        assert_eq!(duration.secs, 3.0);
+
+        // A short MP3 without a Xing header is read to the end anyway
+        let short = dir.join("short.mp3");
+        write_silent_mp3(&short, 100);
+        let duration = measure_duration(&short, false).unwrap();
+        assert_eq!(duration.source, DurationSource::Counted);
+        assert!((duration.secs - 100.0 * frame_secs).abs() < 1e-6);
+
+        // A longer one is estimated unless counting is allowed
+        let long = dir.join("long.mp3");
+        write_silent_mp3(&long, BITRATE_SAMPLE_PACKETS + 100);
+        let expected = (BITRATE_SAMPLE_PACKETS + 100) as f64 * frame_secs;
+        let duration = measure_duration(&long, false).unwrap();
+        assert_eq!(duration.source, DurationSource::Bitrate);
+        assert!((duration.secs - expected).abs() < 0.01);
+        let duration = measure_duration(&long, true).unwrap();
+        assert_eq!(duration.source, DurationSource::Counted);
+        assert!((duration.secs - expected).abs() < 1e-6);
+
+        // With a Xing header the frame count is used as is
+        let mut xing = vec![0u8; SILENT_FRAME_LEN];
+        xing[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC4]);
+        // After 17 bytes of mono side info: id, flags (frame count), count
+        xing[21..33].copy_from_slice(b"Xing\0\0\0\x01\0\0\0\x64");
+        let tagged = dir.join("tagged.mp3");
+        write_silent_mp3(&tagged, 100);
+        let mut bytes = xing;
+        bytes.extend(std::fs::read(&tagged).unwrap());
+        std::fs::write(&tagged, bytes).unwrap();
+        let duration = measure_duration(&tagged, false).unwrap();
+        assert_eq!(duration.source, DurationSource::Container);
+        assert!((duration.secs - 100.0 * frame_secs).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_extension_from_header() {
+        let id3 = b"ID3\x04\x00\x00\x00\x00\x00\x00";
+        assert_eq!(
+            extension_from_header(&[0xFF, 0xFB, 0x90, 0xC4]),
+            Some("mp3")
+        );
+        assert_eq!(
+            extension_from_header(&[0xFF, 0xFD, 0x90, 0xC4]),
+            Some("mp2")
+        );
+        assert_eq!(
+            extension_from_header(&[0xFF, 0xF1, 0x50, 0x80]),
+            Some("aac")
+        );
+        assert_eq!(extension_from_header(&[0xFF, 0xE9, 0x50, 0x80]), None);
+        assert_eq!(
+            extension_from_header(&[id3.as_slice(), &[0xFF, 0xFB]].concat()),
+            Some("mp3")
+        );
+        assert_eq!(
+            extension_from_header(&[id3.as_slice(), &[0xFF, 0xF1]].concat()),
+            Some("aac")
+        );
+        assert_eq!(
+            extension_from_header(&[id3.as_slice(), b"fLaC"].concat()),
+            Some("flac")
+        );
+        // A tag longer than the header read
+        assert_eq!(
+            extension_from_header(b"ID3\x04\x00\x00\x00\x00\x10\x00"),
+            Some("mp3")
+        );
+    }
+
+    #[test]
+    fn test_silent_levels() {
+        let dir = crate::temp::TestDir::new("levels");
+        let path = dir.join("silence.wav");
+        write_wav(&path, &[0.0; 8000], 8000, WavSampleFormat::Int16).unwrap();
+
+        let levels = analyze_levels(&path).unwrap();
+        assert_eq!(levels.peak_dbfs, SILENCE_DBFS);
+        assert_eq!(levels.rms_dbfs, SILENCE_DBFS);
+    }
+
+    #[test]
+    fn test_normalize_peak_to_float() {
+        let dir = crate::temp::TestDir::new("normalize");
+        let path = dir.join("quiet.wav");
+        write_wav(&path, &vec![0.25; 8000], 8000, WavSampleFormat::Float).unwrap();
+
+        let normalized = normalize_peak(
+            &path,
+            0.0,
+            WavSampleFormat::Float,
+            &dir,
+            ExistingOutput::Overwrite,
+        )
+        .unwrap();
+        let mut reader = hound::WavReader::open(&normalized).unwrap();
+        assert_eq!(reader.spec().sample_format, hound::SampleFormat::Float);
+        let samples: Vec<f32> = reader.samples::<f32>().map(Result::unwrap).collect();
+        assert_eq!(samples.len(), 8000);
+        assert!(samples.iter().all(|&sample| sample == 1.0));
+    }
+
+    #[test]
+    fn test_audio_info() {
+        let dir = crate::temp::TestDir::new("info");
+        let path = dir.join("tone.wav");
+        write_wav(&path, &vec![0.25; 3 * 8000], 8000, WavSampleFormat::Int16).unwrap();
+
+        let info = audio_info(&path).unwrap();
+        assert_eq!(info.duration, std::time::Duration::from_secs(3));
+        assert_eq!(info.sample_rate, Some(8000));
+        assert_eq!(info.channels, Some(1));
+        assert_eq!(audio_duration(&path).unwrap(), info.duration);
+    }
+
+    #[test]
+    fn test_float_wav_roundtrip() {
+        let dir = crate::temp::TestDir::new("wav");
+        let path = dir.join("float.wav");
+        let samples = [0.0, 0.123_456_7, -0.5, 0.999_9, -1.0, 1.2];
+
+        write_wav(&path, &samples, 48000, WavSampleFormat::Float).unwrap();
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.sample_format, hound::SampleFormat::Float);
+        assert_eq!(spec.bits_per_sample, 32);
+        assert_eq!(spec.sample_rate, 48000);
+        let read: Vec<f32> = reader.samples::<f32>().map(Result::unwrap).collect();
+        assert_eq!(read.len(), samples.len());
+        for (read, written) in read.iter().zip(samples) {
+            assert!((read - written).abs() < 1e-6, "{} != {}", read, written);
+        }
+    }
+
+    #[test]
+    fn test_explicit_positions() {
+        let timestamps = [30.0, 2700.0, 4800.0];
+        assert_eq!(
+            explicit_positions(&timestamps, 3, 5400.0, 10.0).unwrap(),
+            timestamps.to_vec()
+        );
+        // Extra start times are ignored by shorter previews
+        assert_eq!(
+            explicit_positions(&timestamps, 2, 5400.0, 10.0).unwrap(),
+            vec![30.0, 2700.0]
+        );
+        // Too few, past the end, or out of order
+        assert!(explicit_positions(&timestamps, 6, 5400.0, 10.0).is_err());
+        assert!(explicit_positions(&[30.0, 5395.0], 2, 5400.0, 10.0).is_err());
+        assert!(explicit_positions(&[60.0, 30.0], 2, 5400.0, 10.0).is_err());
+        // Overlap is allowed
+        assert!(explicit_positions(&[30.0, 35.0], 2, 5400.0, 10.0).is_ok());
+    }
+}
@@ -0,0 +1,898 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(not(feature = "async"))]
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(not(feature = "async"))]
+use std::thread;
+
+use crate::cli::Platform;
+use crate::config::{Settings, TokenStorage};
+use crate::platforms::RateLimited;
+#[cfg(not(feature = "async"))]
+use crate::platforms::UploadRequest;
+#[cfg(feature = "async")]
+use crate::platforms::{mixcloud, soundcloud};
+use crate::rate_limit::RateLimiters;
+use crate::template::Value;
+
+/// Times one item is retried after the platform rate-limits it
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Shared settings applied to every file in a batch
+#[derive(Clone)]
+pub struct BatchOptions {
+    /// Description template; `{title}` and `{filename}` are filled in per file
+    pub description_template: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub account: Option<String>,
+    pub concurrency: usize,
+    pub fail_fast: bool,
+    /// Loaded once for the whole batch
+    pub settings: Settings,
+}
+
+/// One file to upload, the title it will get and where it goes
+#[derive(Debug, Clone)]
+pub struct BatchItem {
+    pub file: PathBuf,
+    pub title: String,
+    pub platform: Platform,
+}
+
+/// Outcome of uploading one batch item
+#[derive(Debug)]
+pub struct BatchResult {
+    pub item: BatchItem,
+    /// Ok with the resulting URL/key, Err with the failure reason,
+    /// or None if the item was skipped after a --fail-fast abort
+    pub outcome: Option<Result<String, String>>,
+}
+
+/// Expand a glob like `mixes/*.mp3` into matching files, sorted by name.
+/// Wildcards (`*`, `?`) are supported in the file name only.
+pub fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>> {
+    let pattern_path = Path::new(pattern);
+    let file_pattern = pattern_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Glob must end in a file name pattern")?;
+    let dir = match pattern_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    if dir.to_string_lossy().contains(['*', '?']) {
+        bail!("Wildcards are only supported in the file name, not in directories");
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file()
+            && let Some(name) = path.file_name().and_then(|n| n.to_str())
+            && wildcard_match(file_pattern, name)
+        {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Match `name` against a pattern with `*` (any run) and `?` (any one char)
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the last star swallow one more character and retry
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Read a CSV manifest with a header row and these columns:
+///
+/// - `file` (required): path to the audio file, relative to the manifest
+/// - `title` (optional): defaults to a title derived from the file name
+/// - `platform` (optional): `mixcloud`, `soundcloud` or `both`; overrides
+///   the command-line platform for that row
+///
+/// Every row is validated before anything is uploaded.
+pub fn load_manifest(path: &Path, default_platform: Platform) -> Result<Vec<BatchItem>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    parse_manifest(&contents, base_dir, default_platform)
+}
+
+fn parse_manifest(
+    contents: &str,
+    base_dir: &Path,
+    default_platform: Platform,
+) -> Result<Vec<BatchItem>> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().context("Manifest is empty")?;
+    let header: Vec<String> = split_csv_line(header)
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let file_col = column("file").context("Manifest needs a `file` column")?;
+    let title_col = column("title");
+    let platform_col = column("platform");
+
+    let mut items = Vec::new();
+    for (index, line) in lines {
+        let line_no = index + 1;
+        let fields = split_csv_line(line);
+        let field = |col: Option<usize>| {
+            col.and_then(|c| fields.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+
+        let file = field(Some(file_col))
+            .with_context(|| format!("Manifest line {}: missing file", line_no))?;
+        let file = base_dir.join(file);
+        let title = field(title_col)
+            .map(str::to_string)
+            .unwrap_or_else(|| title_from_filename(&file));
+
+        let platforms = match field(platform_col) {
+            None => vec![default_platform],
+            Some(value) => match value.to_lowercase().as_str() {
+                "mixcloud" => vec![Platform::Mixcloud],
+                "soundcloud" => vec![Platform::Soundcloud],
+                "both" => vec![Platform::Mixcloud, Platform::Soundcloud],
+                _ => bail!(
+                    "Manifest line {}: unknown platform \"{}\" (expected mixcloud, soundcloud or both)",
+                    line_no,
+                    value
+                ),
+            },
+        };
+
+        for platform in platforms {
+            items.push(BatchItem {
+                file: file.clone(),
+                title: title.clone(),
+                platform,
+            });
+        }
+    }
+
+    Ok(items)
+}
+
+/// Split one CSV line, honouring double-quoted fields with `""` escapes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+/// Derive a display title from a file name: `late_night-mix_01.mp3`
+/// becomes `late night-mix 01`
+pub fn title_from_filename(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+
+    stem.replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Placeholders available in `--title-template`
+pub const TITLE_PLACEHOLDERS: &[&str] =
+    &["number", "stem", "filename", "title", "date", "duration"];
+
+/// Replace each item's title with `template` rendered for its file:
+/// `{number}` (1-based file position), `{stem}`, `{filename}`, `{title}` (the
+/// manifest or filename-derived title), `{date}` (file modified, YYYY-MM-DD)
+/// and `{duration}` (e.g. 1h05m). All titles are rendered before any upload.
+pub fn apply_title_template(items: &mut [BatchItem], template: &str) -> Result<()> {
+    crate::template::validate(template, TITLE_PLACEHOLDERS)?;
+    let wants_duration = template.contains("{duration");
+
+    let mut number = 0;
+    let mut previous_file: Option<PathBuf> = None;
+    for item in items.iter_mut() {
+        // Rows uploading one file to both platforms share a number
+        if previous_file.as_ref() != Some(&item.file) {
+            number += 1;
+            previous_file = Some(item.file.clone());
+        }
+
+        let stem = item
+            .file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let filename = item
+            .file
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let date = fs::metadata(&item.file)
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| {
+                chrono::DateTime::<chrono::Local>::from(modified)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .with_context(|| format!("Failed to read {}", item.file.display()))?;
+        let duration = if wants_duration {
+            let secs = crate::audio::get_audio_duration(&item.file)
+                .with_context(|| format!("Failed to read duration of {}", item.file.display()))?;
+            crate::title::format_duration_label(secs)
+        } else {
+            String::new()
+        };
+
+        let values = [
+            ("number", Value::Number(number)),
+            ("stem", Value::Text(stem)),
+            ("filename", Value::Text(filename)),
+            ("title", Value::Text(item.title.clone())),
+            ("date", Value::Text(date)),
+            ("duration", Value::Text(duration)),
+        ];
+        item.title = crate::template::render(template, &values)?;
+    }
+
+    Ok(())
+}
+
+/// Upload every item, `concurrency` at a time, and return one result per item
+pub fn run_batch(items: Vec<BatchItem>, options: &BatchOptions) -> Result<Vec<BatchResult>> {
+    if let Some(template) = &options.description_template {
+        crate::template::validate(template, &["title", "filename"])?;
+    }
+
+    // Each worker would otherwise start its own OAuth flow on the same port
+    let storage = TokenStorage::load()?;
+    for platform in [Platform::Mixcloud, Platform::Soundcloud] {
+        if !items.iter().any(|item| item.platform == platform) {
+            continue;
+        }
+        if storage
+            .token(platform, options.account.as_deref())
+            .is_none()
+        {
+            bail!(
+                "Not authorized with {}. Run 'dj-uploader auth {}' first",
+                platform,
+                platform.to_string().to_lowercase()
+            );
+        }
+
+        // Refresh once up front so workers don't race to refresh the same token
+        crate::platforms::client_for(platform, options.account.as_deref())?.refresh()?;
+    }
+
+    let throttle = Arc::new(Throttle::new(&options.settings, options.concurrency));
+    let results = run_uploads(&items, options, throttle)?;
+
+    let mut outcomes: Vec<Option<Result<String, String>>> = items.iter().map(|_| None).collect();
+    for (index, outcome) in results {
+        outcomes[index] = Some(outcome);
+    }
+
+    Ok(items
+        .into_iter()
+        .zip(outcomes)
+        .map(|(item, outcome)| BatchResult { item, outcome })
+        .collect())
+}
+
+/// Set by Ctrl-C: uploads in progress finish, nothing new starts
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Let Ctrl-C stop a batch cleanly. The first press lets uploads in progress
+/// finish so the summary and resume manifest can be written; a second press
+/// quits immediately.
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+        eprintln!("\nInterrupted: finishing uploads in progress, press Ctrl-C again to quit now");
+    })
+    .context("Failed to install Ctrl-C handler")
+}
+
+/// Whether the batch was interrupted with Ctrl-C
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Add a finished upload to the history so `--resume` can skip it
+fn record_outcome(item: &BatchItem, outcome: &Result<String, String>) {
+    if let Ok(url) = outcome
+        && let Err(e) = crate::history::record(&item.file, item.platform, &item.title, url, None)
+    {
+        log::warn!(
+            "Failed to record {} in history: {:#}",
+            item.file.display(),
+            e
+        );
+    }
+}
+
+/// Drop items the history says were already uploaded to their platform.
+/// Returns the remaining items and how many were skipped.
+pub fn skip_uploaded(items: Vec<BatchItem>) -> Result<(Vec<BatchItem>, usize)> {
+    let history = crate::history::load()?;
+    let total = items.len();
+    let remaining: Vec<BatchItem> = items
+        .into_iter()
+        .filter(|item| !crate::history::contains(&history, &item.file, item.platform))
+        .collect();
+    let skipped = total - remaining.len();
+    Ok((remaining, skipped))
+}
+
+/// Write the items that failed or never started as a manifest that
+/// `batch --resume` accepts. Returns how many rows were written.
+pub fn write_remaining(results: &[BatchResult], path: &Path) -> Result<usize> {
+    let rows = results
+        .iter()
+        .filter(|result| !matches!(result.outcome, Some(Ok(_))))
+        .map(|result| (&result.item, None));
+    write_manifest(rows, false, path)
+}
+
+/// Write the items that failed, with the reason in an `error` column, as a
+/// manifest that `batch --retry-failed` accepts. Returns how many rows were
+/// written.
+pub fn write_failures(results: &[BatchResult], path: &Path) -> Result<usize> {
+    let rows = results.iter().filter_map(|result| match &result.outcome {
+        Some(Err(reason)) => Some((&result.item, Some(reason.as_str()))),
+        _ => None,
+    });
+    write_manifest(rows, true, path)
+}
+
+/// Write `rows` in the manifest format, only if there are any
+fn write_manifest<'a>(
+    rows: impl Iterator<Item = (&'a BatchItem, Option<&'a str>)>,
+    with_error: bool,
+    path: &Path,
+) -> Result<usize> {
+    let mut contents = String::from(if with_error {
+        "file,title,platform,error\n"
+    } else {
+        "file,title,platform\n"
+    });
+    let mut written = 0;
+    for (item, error) in rows {
+        let file = std::path::absolute(&item.file).unwrap_or_else(|_| item.file.clone());
+        contents.push_str(&format!(
+            "{},{},{}",
+            csv_field(&file.to_string_lossy()),
+            csv_field(&item.title),
+            item.platform.to_string().to_lowercase()
+        ));
+        if with_error {
+            // One line per row, so multi-line errors are flattened
+            let error = error.unwrap_or_default().replace(['\r', '\n'], " ");
+            contents.push_str(&format!(",{}", csv_field(&error)));
+        }
+        contents.push('\n');
+        written += 1;
+    }
+
+    if written > 0 {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(written)
+}
+
+/// Quote a CSV field when it contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Rate limits and worker count shared by every worker in a batch
+struct Throttle {
+    limiters: RateLimiters,
+    /// Uploads allowed to run at once; halved on every 429
+    workers: AtomicUsize,
+    #[cfg(feature = "async")]
+    running: AtomicUsize,
+}
+
+impl Throttle {
+    fn new(settings: &Settings, concurrency: usize) -> Self {
+        Self {
+            limiters: RateLimiters::from_settings(settings),
+            workers: AtomicUsize::new(concurrency.max(1)),
+            #[cfg(feature = "async")]
+            running: AtomicUsize::new(0),
+        }
+    }
+
+    /// Back off after a 429 from `platform`: pause its uploads until the reset
+    /// time and run fewer at once. Returns false if `err` isn't a 429.
+    fn rate_limited(&self, platform: Platform, err: &anyhow::Error) -> bool {
+        let Some(limited) = err.downcast_ref::<RateLimited>() else {
+            return false;
+        };
+        self.limiters.get(platform).pause(limited.retry_after);
+
+        let previous = self
+            .workers
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| Some((n / 2).max(1)))
+            .unwrap_or(1);
+        if previous > 1 {
+            log::info!("Reducing batch concurrency to {}", (previous / 2).max(1));
+        }
+        true
+    }
+
+    /// Claim a slot to start an upload, if fewer than `workers` are running
+    #[cfg(feature = "async")]
+    fn try_start(&self) -> bool {
+        let workers = self.workers.load(Ordering::SeqCst);
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < workers).then_some(n + 1)
+            })
+            .is_ok()
+    }
+
+    #[cfg(feature = "async")]
+    fn finish(&self) {
+        self.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Run uploads on `concurrency` worker threads.
+/// Returns (item index, outcome) for every item that was attempted.
+#[cfg(not(feature = "async"))]
+fn run_uploads(
+    items: &[BatchItem],
+    options: &BatchOptions,
+    throttle: Arc<Throttle>,
+) -> Result<Vec<(usize, Result<String, String>)>> {
+    let queue = Mutex::new(items.iter().enumerate());
+    let results: Mutex<Vec<(usize, Result<String, String>)>> = Mutex::new(Vec::new());
+    let aborted = AtomicBool::new(false);
+    let throttle = &*throttle;
+
+    thread::scope(|scope| {
+        for worker in 0..options.concurrency.max(1) {
+            let (queue, results, aborted) = (&queue, &results, &aborted);
+            scope.spawn(move || {
+                loop {
+                    // Workers beyond the current limit retire after a 429
+                    if aborted.load(Ordering::SeqCst)
+                        || interrupted()
+                        || worker >= throttle.workers.load(Ordering::SeqCst)
+                    {
+                        break;
+                    }
+                    let Some((index, item)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+
+                    println!("Uploading {} to {}...", item.file.display(), item.platform);
+                    let mut retries = 0;
+                    let outcome = loop {
+                        throttle.limiters.get(item.platform).acquire();
+                        match upload_one(item, options) {
+                            Err(e)
+                                if retries < MAX_RATE_LIMIT_RETRIES
+                                    && throttle.rate_limited(item.platform, &e) =>
+                            {
+                                retries += 1;
+                                println!("Rate limited, will retry {}", item.file.display());
+                            }
+                            result => break result.map_err(|e| e.to_string()),
+                        }
+                    };
+                    record_outcome(item, &outcome);
+                    if outcome.is_err() && options.fail_fast {
+                        aborted.store(true, Ordering::SeqCst);
+                    }
+                    results.lock().unwrap().push((index, outcome));
+                }
+            });
+        }
+    });
+
+    Ok(results.into_inner().unwrap())
+}
+
+/// Run uploads as tasks on one tokio runtime, `concurrency` at a time.
+/// Returns (item index, outcome) for every item that was attempted.
+#[cfg(feature = "async")]
+fn run_uploads(
+    items: &[BatchItem],
+    options: &BatchOptions,
+    throttle: Arc<Throttle>,
+) -> Result<Vec<(usize, Result<String, String>)>> {
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinSet;
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    runtime.block_on(async {
+        let options = Arc::new(options.clone());
+        let permits = Arc::new(Semaphore::new(options.concurrency.max(1)));
+        let aborted = Arc::new(AtomicBool::new(false));
+        let mut tasks = JoinSet::new();
+
+        for (index, item) in items.iter().cloned().enumerate() {
+            let options = Arc::clone(&options);
+            let permits = Arc::clone(&permits);
+            let aborted = Arc::clone(&aborted);
+            let throttle = Arc::clone(&throttle);
+
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await.ok()?;
+                if aborted.load(Ordering::SeqCst) || interrupted() {
+                    return None;
+                }
+                // Fewer uploads run at once after a 429
+                while !throttle.try_start() {
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                }
+
+                println!("Uploading {} to {}...", item.file.display(), item.platform);
+                let mut retries = 0;
+                let outcome = loop {
+                    throttle.limiters.get(item.platform).acquire_async().await;
+                    match upload_one_async(&item, &options).await {
+                        Err(e)
+                            if retries < MAX_RATE_LIMIT_RETRIES
+                                && throttle.rate_limited(item.platform, &e) =>
+                        {
+                            retries += 1;
+                            println!("Rate limited, will retry {}", item.file.display());
+                        }
+                        result => break result.map_err(|e| e.to_string()),
+                    }
+                };
+                throttle.finish();
+                record_outcome(&item, &outcome);
+                if outcome.is_err() && options.fail_fast {
+                    aborted.store(true, Ordering::SeqCst);
+                }
+                Some((index, outcome))
+            });
+        }
+
+        let mut results = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            if let Some(result) = joined.context("Upload task panicked")? {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    })
+}
+
+/// Title, description and tags to send for one item
+fn item_metadata(
+    item: &BatchItem,
+    options: &BatchOptions,
+) -> Result<(String, Option<String>, Option<Vec<String>>)> {
+    let file_name = item
+        .file
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let description = options
+        .description_template
+        .as_deref()
+        .map(|template| {
+            crate::template::render(
+                template,
+                &[
+                    ("title", Value::Text(item.title.clone())),
+                    ("filename", Value::Text(file_name.to_string())),
+                ],
+            )
+        })
+        .transpose()?;
+
+    let limits = crate::limits::Limits::for_platform(item.platform, &options.settings);
+    let title = crate::title::validate_title(item.platform, &limits, &item.title)?;
+    // Tags were checked against the command-line platform; manifest rows may differ
+    let tags = options
+        .tags
+        .as_deref()
+        .map(|t| crate::tags::validate_tags(item.platform, &limits, t, false))
+        .transpose()?;
+
+    Ok((title, description, tags))
+}
+
+#[cfg(not(feature = "async"))]
+fn upload_one(item: &BatchItem, options: &BatchOptions) -> Result<String> {
+    let (title, description, tags) = item_metadata(item, options)?;
+
+    let outcome = crate::platforms::upload(
+        item.platform,
+        options.account.as_deref(),
+        &UploadRequest {
+            file_path: &item.file,
+            title: &title,
+            description: description.as_deref(),
+            image_paths: &[],
+            tags,
+            publish_date: None,
+            isrc: None,
+            language: None,
+            remix_of: None,
+            assume_pro: false,
+            unlisted: false,
+            deadline: None,
+            progress: None,
+        },
+    )?;
+    Ok(outcome.link())
+}
+
+/// The clients hold a blocking reqwest client, which starts and shuts down a
+/// runtime of its own; both have to happen off the async worker threads
+#[cfg(feature = "async")]
+async fn upload_one_async(item: &BatchItem, options: &BatchOptions) -> Result<String> {
+    let (title, description, tags) = item_metadata(item, options)?;
+
+    match item.platform {
+        Platform::Mixcloud => {
+            let mut client = tokio::task::block_in_place(|| {
+                mixcloud::MixcloudClient::for_account(options.account.as_deref())
+            })?;
+            let result = client
+                .upload_async(
+                    &item.file,
+                    &title,
+                    description.as_deref(),
+                    &[],
+                    tags,
+                    None,
+                    false,
+                    false,
+                )
+                .await;
+            tokio::task::block_in_place(|| drop(client));
+            Ok(format!("https://www.mixcloud.com{}", result?.result.key))
+        }
+        Platform::Soundcloud => {
+            let mut client = tokio::task::block_in_place(|| {
+                soundcloud::SoundcloudClient::for_account(options.account.as_deref())
+            })?;
+            let result = client
+                .upload_async(
+                    &item.file,
+                    &title,
+                    description.as_deref(),
+                    &[],
+                    tags,
+                    None,
+                    None,
+                    false,
+                )
+                .await;
+            tokio::task::block_in_place(|| drop(client));
+            let response = result?;
+            Ok(response
+                .permalink_url
+                .unwrap_or_else(|| format!("Track #{}", response.id)))
+        }
+    }
+}
+
+/// Print a table of batch outcomes
+pub fn print_summary(results: &[BatchResult]) {
+    let width = results
+        .iter()
+        .map(|r| r.item.title.chars().count())
+        .max()
+        .unwrap_or(5)
+        .max(5);
+
+    println!("\n=== Batch Summary ===\n");
+    println!(
+        "{:<width$}  {:<10}  {:<9}  Details",
+        "Title",
+        "Platform",
+        "Status",
+        width = width
+    );
+    for result in results {
+        let (status, details) = match &result.outcome {
+            Some(Ok(url)) => ("✓ ok", url.as_str()),
+            Some(Err(e)) => ("✗ failed", e.as_str()),
+            None => ("- skipped", ""),
+        };
+        println!(
+            "{:<width$}  {:<10}  {:<9}  {}",
+            result.item.title,
+            result.item.platform.to_string(),
+            status,
+            details,
+            width = width
+        );
+    }
+
+    let succeeded = results
+        .iter()
+        .filter(|r| matches!(r.outcome, Some(Ok(_))))
+        .count();
+    let failed = results
+        .iter()
+        .filter(|r| matches!(r.outcome, Some(Err(_))))
+        .count();
+    println!("\n{} of {} uploaded", succeeded, results.len());
+    if failed > 0 || succeeded + failed < results.len() {
+        println!(
+            "{} failed, {} not started",
+            failed,
+            results.len() - succeeded - failed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.mp3", "mix.mp3"));
+        assert!(wildcard_match("mix_??.mp3", "mix_01.mp3"));
+        assert!(wildcard_match("*live*", "2024 live set.wav"));
+        assert!(!wildcard_match("*.mp3", "mix.wav"));
+        assert!(!wildcard_match("mix_?.mp3", "mix_01.mp3"));
+    }
+
+    #[test]
+    fn test_remaining_manifest_roundtrip() {
+        let dir = crate::temp::TestDir::new("batch");
+        let item = |file: &str, title: &str, platform| BatchItem {
+            file: dir.join(file),
+            title: title.to_string(),
+            platform,
+        };
+        let results = vec![
+            BatchResult {
+                item: item("a.mp3", "Done", Platform::Mixcloud),
+                outcome: Some(Ok("https://example.com/a".to_string())),
+            },
+            BatchResult {
+                item: item("b.mp3", "Live, \"Part 2\"", Platform::Soundcloud),
+                outcome: Some(Err("Upload failed".to_string())),
+            },
+            BatchResult {
+                item: item("c.mp3", "Pending", Platform::Mixcloud),
+                outcome: None,
+            },
+        ];
+
+        let path = dir.join("remaining.csv");
+        assert_eq!(write_remaining(&results, &path).unwrap(), 2);
+
+        let items = load_manifest(&path, Platform::Mixcloud).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].file, dir.join("b.mp3"));
+        assert_eq!(items[0].title, "Live, \"Part 2\"");
+        assert_eq!(items[0].platform, Platform::Soundcloud);
+        assert_eq!(items[1].title, "Pending");
+
+        // Only the failure goes in the failure report, with its reason
+        let path = dir.join("failures.csv");
+        assert_eq!(write_failures(&results, &path).unwrap(), 1);
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("file,title,platform,error\n"));
+        assert!(contents.trim_end().ends_with(",Upload failed"));
+        let items = load_manifest(&path, Platform::Mixcloud).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Live, \"Part 2\"");
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "file,title,platform\n\
+                        a.mp3,\"Live, Part 1\",\n\
+                        b.mp3,,soundcloud\n\
+                        c.mp3,Both Ways,Both\n";
+        let items = parse_manifest(manifest, Path::new("mixes"), Platform::Mixcloud).unwrap();
+
+        let summary: Vec<_> = items
+            .iter()
+            .map(|i| (i.file.clone(), i.title.as_str(), i.platform.to_string()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    PathBuf::from("mixes/a.mp3"),
+                    "Live, Part 1",
+                    "Mixcloud".to_string()
+                ),
+                (PathBuf::from("mixes/b.mp3"), "b", "SoundCloud".to_string()),
+                (
+                    PathBuf::from("mixes/c.mp3"),
+                    "Both Ways",
+                    "Mixcloud".to_string()
+                ),
+                (
+                    PathBuf::from("mixes/c.mp3"),
+                    "Both Ways",
+                    "SoundCloud".to_string()
+                ),
+            ]
+        );
+
+        let err = parse_manifest(
+            "file,platform\nd.mp3,bandcamp\n",
+            Path::new(""),
+            Platform::Mixcloud,
+        );
+        assert!(err.unwrap_err().to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_title_from_filename() {
+        assert_eq!(
+            title_from_filename(Path::new("mixes/late_night__mix.mp3")),
+            "late night mix"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::DateTime;
+
+use crate::audio;
+use crate::cli::Platform;
+use crate::config;
+
+/// SOURCE_DATE_EPOCH at build time, if it was set
+const BUILD_TIMESTAMP: Option<&str> = option_env!("BUILD_TIMESTAMP");
+const BUILD_TARGET: &str = env!("BUILD_TARGET");
+const BUILD_PROFILE: &str = env!("BUILD_PROFILE");
+
+/// Optional Cargo features and whether this binary was built with them
+const FEATURES: &[(&str, bool)] = &[
+    ("async", cfg!(feature = "async")),
+    ("mp3", cfg!(feature = "mp3")),
+];
+
+/// Print how this binary was built, for bug reports. Built-in credentials
+/// are only reported as there or not.
+pub fn print_build_info() {
+    println!("dj-uploader {}", env!("CARGO_PKG_VERSION"));
+    print_row("Source date", &source_date());
+    print_row("Target", &format!("{} ({})", BUILD_TARGET, BUILD_PROFILE));
+
+    for platform in [Platform::Mixcloud, Platform::Soundcloud] {
+        let state = if config::has_compiled_credentials(platform) {
+            "built in"
+        } else {
+            "placeholders (set them in credentials.json or the environment)"
+        };
+        print_row(&format!("{} credentials", platform), state);
+    }
+
+    let features: Vec<String> = FEATURES
+        .iter()
+        .map(|(name, enabled)| format!("{}{}", if *enabled { "+" } else { "-" }, name))
+        .collect();
+    print_row("Features", &features.join(" "));
+
+    let codecs: Vec<&str> = audio::supported_codecs()
+        .into_iter()
+        .map(|(short_name, _)| short_name)
+        .collect();
+    print_row("Codecs", &codecs.join(", "));
+}
+
+/// SOURCE_DATE_EPOCH as UTC, as given if it isn't a number of seconds, or
+/// "unknown" when the build didn't set it
+fn source_date() -> String {
+    let Some(timestamp) = BUILD_TIMESTAMP else {
+        return "unknown".to_string();
+    };
+    timestamp
+        .parse()
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map_or_else(
+            || timestamp.to_string(),
+            |time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        )
+}
+
+fn print_row(label: &str, value: &str) {
+    println!("  {:<24} {}", label, value);
+}
@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::cli::PreviewFormat;
+use crate::history;
+use crate::output::format_size;
+
+/// What `dj-uploader cleanup` should look at
+pub struct CleanupOptions {
+    pub older_than_days: u32,
+    /// Leftover working directories from earlier runs
+    pub sessions: bool,
+    /// Trim history.jsonl to the retention window
+    pub history: bool,
+    /// Folder to clear of generated preview snippets
+    pub previews_in: Option<PathBuf>,
+    /// Where working directories were created, if not the system temp dir
+    pub temp_dir: Option<PathBuf>,
+    /// Actually delete; otherwise only report what would go
+    pub apply: bool,
+}
+
+/// Remove old session directories, history entries and preview files.
+/// Nothing in the config directory other than history.jsonl is touched,
+/// so tokens and settings are always kept.
+pub fn run_cleanup(options: &CleanupOptions) -> Result<()> {
+    let cutoff = SystemTime::now()
+        - std::time::Duration::from_secs(u64::from(options.older_than_days) * 24 * 60 * 60);
+    let verb = if options.apply {
+        "Removed"
+    } else {
+        "Would remove"
+    };
+    let mut removed = 0;
+    let mut reclaimed = 0;
+
+    let mut stale = Vec::new();
+    if options.sessions {
+        let base = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        stale.extend(stale_entries(&base, cutoff, is_session_dir)?);
+    }
+    if let Some(dir) = &options.previews_in {
+        stale.extend(stale_entries(dir, cutoff, is_preview_file)?);
+    }
+
+    for path in stale {
+        let size = disk_usage(&path);
+        if options.apply {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                eprintln!("⚠ Failed to remove {}: {}", path.display(), e);
+                continue;
+            }
+        }
+        println!("  {} {} ({})", verb, path.display(), format_size(size));
+        removed += 1;
+        reclaimed += size;
+    }
+
+    if options.history {
+        let cutoff = Utc::now() - Duration::days(i64::from(options.older_than_days));
+        let pruned = history::prune(cutoff, options.apply)?;
+        if pruned.removed > 0 {
+            println!(
+                "  {} {} history entries before {} ({})",
+                verb,
+                pruned.removed,
+                cutoff.format("%Y-%m-%d"),
+                format_size(pruned.bytes)
+            );
+            removed += pruned.removed;
+            reclaimed += pruned.bytes;
+        }
+    }
+
+    if removed == 0 {
+        println!(
+            "Nothing older than {} days to clean up",
+            options.older_than_days
+        );
+    } else if options.apply {
+        println!(
+            "✓ Removed {} items, {} reclaimed",
+            removed,
+            format_size(reclaimed)
+        );
+    } else {
+        println!(
+            "\n{} items, {} would be reclaimed. Run again with --yes to delete them.",
+            removed,
+            format_size(reclaimed)
+        );
+    }
+
+    Ok(())
+}
+
+/// Entries of `dir` accepted by `matches` that were last modified before `cutoff`
+fn stale_entries(
+    dir: &Path,
+    cutoff: SystemTime,
+    matches: fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>> {
+    let mut stale = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let path = entry.path();
+        if !matches(&path) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified());
+        if modified.is_ok_and(|modified| modified < cutoff) {
+            stale.push(path);
+        }
+    }
+    stale.sort();
+    Ok(stale)
+}
+
+/// A working directory left by `TempDir`: dj-uploader-<YYYYmmdd>-<HHMMSS>-<pid>
+fn is_session_dir(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let Some(rest) = name.strip_prefix("dj-uploader-") else {
+        return false;
+    };
+    let parts: Vec<&str> = rest.split('-').collect();
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    path.is_dir()
+        && parts.len() == 3
+        && digits(parts[0], 8)
+        && digits(parts[1], 6)
+        && !parts[2].is_empty()
+        && parts[2].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A snippet written by `create_preview_snippets`: <stem>_preview_<secs>s
+/// with the extension of any [`PreviewFormat`]
+fn is_preview_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let Some((_, suffix)) = name.rsplit_once("_preview_") else {
+        return false;
+    };
+    path.is_file()
+        && PreviewFormat::value_variants()
+            .iter()
+            .find_map(|format| suffix.strip_suffix(&format!("s.{}", format.extension())))
+            .is_some_and(|secs| !secs.is_empty() && secs.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Size of a file, or of everything under a directory
+fn disk_usage(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| disk_usage(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_only_generated_names() {
+        let dir = crate::temp::TestDir::new("cleanup");
+        let session = dir.join("dj-uploader-20260101-120000-4242");
+        fs::create_dir_all(&session).unwrap();
+        fs::create_dir_all(dir.join("dj-uploader-notes")).unwrap();
+        for name in [
+            "set_preview_30s.wav",
+            "set_preview_.wav",
+            "set.wav",
+            "tokens.json",
+        ] {
+            fs::write(dir.join(name), b"x").unwrap();
+        }
+
+        assert!(is_session_dir(&session));
+        assert!(!is_session_dir(&dir.join("dj-uploader-notes")));
+        assert!(is_preview_file(&dir.join("set_preview_30s.wav")));
+        assert!(!is_preview_file(&dir.join("set_preview_.wav")));
+        assert!(!is_preview_file(&dir.join("set.wav")));
+
+        // Everything is older than a cutoff in the future, but only the
+        // generated files are picked
+        let future = SystemTime::now() + std::time::Duration::from_secs(60);
+        let stale = stale_entries(&dir, future, is_preview_file).unwrap();
+        assert_eq!(stale, vec![dir.join("set_preview_30s.wav")]);
+        let stale = stale_entries(&dir, future, is_session_dir).unwrap();
+        assert_eq!(stale, vec![session]);
+
+        for format in PreviewFormat::value_variants() {
+            let path = dir.join(format!("set_preview_60s.{}", format.extension()));
+            fs::write(&path, b"x").unwrap();
+            assert!(is_preview_file(&path));
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "dj-uploader")]
@@ -10,17 +12,51 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub gui: bool,
 
+    /// Answer yes to every prompt (large uploads, re-authorizing, deleting
+    /// in cleanup). Without a terminal, prompts take their safe default.
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+
+    /// Write every HTTP request and response of this run to FILE, with
+    /// tokens and secrets removed, for attaching to a bug report
+    #[arg(long, global = true, value_name = "FILE")]
+    pub trace_to: Option<PathBuf>,
+
+    /// Show the version, source date, built-in credentials, features and
+    /// codecs of this binary
+    #[arg(long)]
+    pub build_info: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Authorize with a platform
     Auth {
         /// Platform to authorize with
         #[arg(value_enum)]
         platform: Platform,
+
+        /// Store the token under this account alias instead of the default
+        #[arg(long)]
+        account: Option<String>,
+
+        /// Print the authorization URL and exit, without opening a browser
+        /// or starting the callback server
+        #[arg(long)]
+        print_auth_url: bool,
+
+        /// With --print-auth-url, also print the SoundCloud PKCE code verifier
+        /// (secret: anyone holding it can finish the authorization)
+        #[arg(long, requires = "print_auth_url")]
+        show_verifier: bool,
+
+        /// Start a new authorization even if the stored token still works
+        #[arg(long)]
+        force: bool,
     },
     /// Upload a mix to a platform
     Upload {
@@ -28,40 +64,751 @@ pub enum Commands {
         #[arg(value_enum)]
         platform: Platform,
 
-        /// Path to the audio file
+        /// Path to the audio file, or - to read it from stdin
         #[arg(short, long)]
         file: PathBuf,
 
-        /// Title of the mix
+        /// Title of the mix; required unless a sidecar file supplies it
         #[arg(short, long)]
-        title: String,
+        title: Option<String>,
+
+        /// Ignore a metadata sidecar next to the audio (mix.json, mix.yaml or
+        /// mix.yml for mix.mp3). Otherwise it fills in title, description,
+        /// tags, genre and publish date wherever no flag was given
+        #[arg(long)]
+        no_sidecar: bool,
+
+        /// Read metadata from this sidecar file instead of looking next to
+        /// the audio; unlike a sidecar found there, it must be valid
+        #[arg(long, value_name = "PATH", conflicts_with = "no_sidecar")]
+        sidecar: Option<PathBuf>,
 
         /// Description of the mix
         #[arg(short, long)]
         description: Option<String>,
 
-        /// Path to cover image
+        /// Read the description from a UTF-8 text file
+        /// If --description is also given, it is placed above the file contents
+        #[arg(long, value_name = "FILE")]
+        description_file: Option<PathBuf>,
+
+        /// Credit the original work, e.g. "Artist - Title"; added to the
+        /// description as a "Remix of:" line, and SoundCloud also marks the
+        /// track as a remix
+        #[arg(long, value_name = "WORK")]
+        remix_of: Option<String>,
+
+        /// ISRC of the recording (CC-XXX-YY-NNNNN); SoundCloud only
+        #[arg(long, value_parser = parse_isrc)]
+        isrc: Option<String>,
+
+        /// Language of the mix as a BCP 47 tag, e.g. en, es or pt-BR
+        /// (SoundCloud only)
+        #[arg(long, value_parser = parse_language)]
+        language: Option<String>,
+
+        /// Path to cover image; repeat to attach more where the platform
+        /// allows it (the first is the primary cover)
         #[arg(short = 'i', long)]
-        image: Option<PathBuf>,
+        image: Vec<PathBuf>,
+
+        /// Append the mix length (e.g. "2h15m") to the title or description
+        #[arg(long)]
+        append_duration: bool,
+
+        /// Append the publish date (or today's date) to the title or description
+        #[arg(long)]
+        append_date: bool,
+
+        /// Where --append-duration/--append-date text goes
+        #[arg(long, value_enum, default_value_t = AppendTarget::Title)]
+        append_to: AppendTarget,
+
+        /// Format of the appended text; {duration} and {date} are filled in
+        /// (default: "({duration})", "({date})" or "({date} — {duration})")
+        #[arg(long, value_name = "FORMAT")]
+        append_format: Option<String>,
+
+        /// Format of generated or re-encoded artwork
+        #[arg(long, value_enum, default_value_t = CoverFormat::Jpeg)]
+        image_format: CoverFormat,
+
+        /// Crop non-square artwork to a square before uploading, keeping
+        /// this part of the image
+        #[arg(long, value_enum)]
+        crop_gravity: Option<CropGravity>,
+
+        /// Also embed the artwork in the audio file (MP3 and M4A); a tagged
+        /// copy is uploaded and the original is left untouched
+        #[arg(long)]
+        embed_cover: bool,
+
+        /// What to do with artwork in a format that can't be read here
+        /// (HEIC, AVIF, TIFF): stop, or upload the file unchanged
+        #[arg(long, value_enum, default_value_t = UnsupportedImage::Error)]
+        unsupported_image: UnsupportedImage,
+
+        /// Links and excess emoji in the title: warn, or remove them.
+        /// Control characters are always removed
+        #[arg(long, value_enum, default_value_t = SanitizeMode::Warn)]
+        sanitize: SanitizeMode,
+
+        /// Cut a title or tags that go over the platform limits instead of
+        /// only warning (long descriptions are always cut); limits can be
+        /// changed in settings.json
+        #[arg(long)]
+        auto_truncate: bool,
+
+        /// Generate a cover showing the title when --image is not given
+        #[arg(long)]
+        generate_cover: bool,
+
+        /// Use the mix's waveform as the cover when --image is not given
+        #[arg(long)]
+        cover_from_waveform: bool,
+
+        /// Leave the title off the waveform cover
+        #[arg(long, requires = "cover_from_waveform")]
+        no_cover_title: bool,
+
+        /// Color scheme for --generate-cover and --cover-from-waveform
+        #[arg(long, value_enum, default_value_t = CoverScheme::Sunset)]
+        cover_scheme: CoverScheme,
+
+        /// Font for --generate-cover; common system fonts are tried otherwise
+        #[arg(long, value_name = "FILE")]
+        cover_font: Option<PathBuf>,
 
         /// Tags (comma-separated)
         #[arg(long)]
         tags: Option<String>,
 
+        /// How to merge duplicate tags
+        #[arg(long, value_enum, default_value_t = TagDedupe::Exact)]
+        tag_dedupe: TagDedupe,
+
         /// Scheduled publish date in local time (format: YYYY-MM-DD HH:MM)
         /// Will be converted to UTC. Mixcloud Pro accounts only.
         #[arg(long)]
         publish_date: Option<String>,
 
+        /// Use a leading date/time in the file name as the publish date
+        /// (e.g. "2024-06-01 2000 Mix.mp3"). Ignored if --publish-date is given.
+        #[arg(long)]
+        schedule_from_filename: bool,
+
+        /// Skip the Mixcloud Pro account check when scheduling
+        #[arg(long, visible_alias = "mixcloud-pro")]
+        assume_pro: bool,
+
+        /// Upload as unlisted so it's only reachable by link (Mixcloud only)
+        #[arg(long)]
+        unlisted: bool,
+
         /// Generate preview snippets (30s, 60s, 90s) in the same folder
         #[arg(long)]
         generate_previews: bool,
+
+        /// Preview selection mode; `auto` picks durations from the track length
+        #[arg(long, value_enum)]
+        previews: Option<PreviewMode>,
+
+        /// Preview durations in seconds, any multiple of 10 (e.g. 20,120);
+        /// overrides --previews
+        #[arg(long, value_delimiter = ',')]
+        preview_durations: Option<Vec<u64>>,
+
+        /// Which parts of the track the preview chunks come from
+        #[arg(long, value_enum, default_value_t = PreviewStrategy::Even)]
+        preview_strategy: PreviewStrategy,
+
+        /// Climax position in seconds for --preview-strategy climax
+        /// (default: 75% of the way through)
+        #[arg(long, value_name = "SECS")]
+        climax_at: Option<f64>,
+
+        /// Start each preview chunk at these times instead of placing them
+        /// automatically, e.g. 0:30,45:00,1:20:00
+        #[arg(long, value_delimiter = ',', value_parser = parse_timestamp)]
+        preview_timestamps: Option<Vec<f64>>,
+
+        /// Gain curve for the fades at each preview chunk boundary
+        #[arg(long, value_enum, default_value_t = FadeCurve::Linear)]
+        preview_fade_curve: FadeCurve,
+
+        /// Shorten long quiet passages inside preview chunks, e.g. breakdowns.
+        /// Previews come out shorter by the time removed.
+        #[arg(long)]
+        preview_compress_silence: bool,
+
+        /// Level below which --preview-compress-silence counts audio as quiet
+        #[arg(
+            long,
+            value_name = "DBFS",
+            default_value_t = -45.0,
+            allow_negative_numbers = true,
+            requires = "preview_compress_silence"
+        )]
+        preview_silence_threshold: f64,
+
+        /// Longest quiet passage --preview-compress-silence leaves in place
+        #[arg(
+            long,
+            value_name = "SECS",
+            default_value_t = 1.0,
+            requires = "preview_compress_silence"
+        )]
+        preview_max_gap: f64,
+
+        /// File format of the preview snippets
+        #[arg(long, value_enum, default_value_t = PreviewFormat::Wav)]
+        preview_format: PreviewFormat,
+
+        /// Sample format of the preview WAVs
+        #[arg(long, value_enum, default_value_t = WavSampleFormat::Int16)]
+        preview_sample_format: WavSampleFormat,
+
+        /// Generate previews only once the upload has succeeded, so a failed
+        /// upload leaves no preview files behind
+        #[arg(long, visible_alias = "no-previews-on-failure")]
+        previews_after_upload: bool,
+
+        /// Decode the whole file before uploading and fail if any of it
+        /// doesn't decode; slower than the usual header check
+        #[arg(long)]
+        verify_decode: bool,
+
+        /// Peak-normalize before upload to the given dBFS (default: -1.0)
+        /// Writes a normalized WAV to the temp dir and uploads that instead,
+        /// which from an MP3 or AAC source is several times larger
+        #[arg(
+            long,
+            value_name = "DBFS",
+            num_args = 0..=1,
+            default_missing_value = "-1.0",
+            allow_negative_numbers = true
+        )]
+        normalize_peak: Option<f64>,
+
+        /// Sample format of the --normalize-peak WAV; float keeps the gain
+        /// change without rounding to 16 bits, at twice the size
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = WavSampleFormat::Int16,
+            requires = "normalize_peak"
+        )]
+        normalize_sample_format: WavSampleFormat,
+
+        /// Tracklist file to embed as chapters (lines of "MM:SS Artist - Song")
+        /// MP3 only; uploads a tagged copy of the file
+        #[arg(long, value_name = "FILE")]
+        embed_tracklist: Option<PathBuf>,
+
+        /// Split the mix into parts of at most this length (e.g. 60m, 2h) and
+        /// upload each as "Title (Part N)"; cuts move to a quiet spot nearby.
+        /// MP3 only: parts are cut between frames without re-encoding
+        #[arg(
+            long,
+            value_name = "DURATION",
+            value_parser = parse_duration,
+            conflicts_with = "normalize_peak"
+        )]
+        split: Option<Duration>,
+
+        /// Fail instead of warning when tags need sanitizing
+        #[arg(long)]
+        strict: bool,
+
+        /// Show every form field and the request size without uploading
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the upload (and exit successfully) if the upload history has
+        /// this file on this platform and it hasn't been modified since.
+        /// Safe to run from cron
+        #[arg(long)]
+        only_if_newer: bool,
+
+        /// With --only-if-newer, also skip a file whose modification time
+        /// moved but whose contents hash the same as at the last upload
+        #[arg(long, requires = "only_if_newer")]
+        compare_hash: bool,
+
+        /// Account alias to upload with (see `auth --account`)
+        #[arg(long)]
+        account: Option<String>,
+
+        /// Give up if token refresh, retries and the upload together take
+        /// longer than this (e.g. 90s, 10m, 1h)
+        #[arg(long, value_parser = parse_duration)]
+        deadline: Option<Duration>,
+
+        /// Directory for intermediate files (normalized audio, tagged copies,
+        /// generated covers); defaults to the system temp dir
+        #[arg(long, value_name = "DIR")]
+        temp_dir: Option<PathBuf>,
+
+        /// Keep intermediate files after the upload instead of deleting them
+        #[arg(long)]
+        keep_temp: bool,
+
+        /// Check that the platform's API is up before uploading
+        /// (always done for uploads above the large-upload threshold)
+        #[arg(long)]
+        preflight: bool,
+
+        /// Disk space in MB to leave free when writing previews or a
+        /// normalized copy; generation stops early if it wouldn't fit
+        #[arg(long, value_name = "MB", default_value_t = 100)]
+        min_free_space: u64,
+
+        /// What to do when a preview snippet already exists next to the mix.
+        /// Other generated files go into a fresh temp directory each run
+        #[arg(long, value_enum, default_value_t = ExistingOutput::Overwrite)]
+        replace_if_exists: ExistingOutput,
+
+        /// POST a notification to this URL after the upload
+        /// (defaults to `notify_webhook` from settings)
+        #[arg(long, value_name = "URL")]
+        notify_webhook: Option<String>,
+
+        /// Webhook payload format
+        #[arg(long, value_enum)]
+        webhook_format: Option<WebhookFormat>,
+
+        /// Message for Slack/Discord webhooks; {platform}, {title}, {url},
+        /// {status}, {status_icon} and {error} are filled in
+        #[arg(long)]
+        webhook_template: Option<String>,
+
+        /// Show a desktop notification when the upload finishes
+        #[arg(long)]
+        notify: bool,
+    },
+    /// Upload every file matching a glob with shared settings
+    Batch {
+        /// Platform to upload to
+        #[arg(value_enum)]
+        platform: Platform,
+
+        /// Files to upload, e.g. "mixes/*.mp3"
+        #[arg(
+            long,
+            required_unless_present_any = ["manifest", "resume", "retry_failed"],
+            conflicts_with_all = ["manifest", "resume", "retry_failed"]
+        )]
+        input_glob: Option<String>,
+
+        /// CSV manifest with `file` and optional `title` and `platform` columns.
+        /// A row's platform (mixcloud, soundcloud or both) overrides PLATFORM.
+        #[arg(long, value_name = "CSV", conflicts_with_all = ["resume", "retry_failed"])]
+        manifest: Option<PathBuf>,
+
+        /// Continue an interrupted batch from the manifest it wrote, skipping
+        /// rows the upload history already has
+        #[arg(long, value_name = "CSV")]
+        resume: Option<PathBuf>,
+
+        /// Re-run only the rows of a failure report from an earlier batch
+        /// (fix them in the file first), skipping rows already uploaded
+        #[arg(long, value_name = "CSV", conflicts_with = "resume")]
+        retry_failed: Option<PathBuf>,
+
+        /// Description template; {title} and {filename} are filled in per file
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Title pattern, e.g. "{number:02} - {stem} ({date})"; placeholders are
+        /// {number}, {stem}, {filename}, {title}, {date} and {duration}
+        #[arg(long)]
+        title_template: Option<String>,
+
+        /// Tags (comma-separated); defaults to `default_tags` from settings
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// How to merge duplicate tags
+        #[arg(long, value_enum, default_value_t = TagDedupe::Exact)]
+        tag_dedupe: TagDedupe,
+
+        /// Number of uploads to run at once
+        #[arg(long, default_value_t = 1)]
+        concurrency: usize,
+
+        /// Stop starting new uploads after the first failure
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// POST a notification to this URL after the upload
+        /// (defaults to `notify_webhook` from settings)
+        #[arg(long, value_name = "URL")]
+        notify_webhook: Option<String>,
+
+        /// Webhook payload format
+        #[arg(long, value_enum)]
+        webhook_format: Option<WebhookFormat>,
+
+        /// Message for Slack/Discord webhooks; {platform}, {title}, {url},
+        /// {status}, {status_icon} and {error} are filled in
+        #[arg(long)]
+        webhook_template: Option<String>,
+
+        /// Account alias to upload with (see `auth --account`)
+        #[arg(long)]
+        account: Option<String>,
+    },
+    /// Extract one preview chunk to check seeking and decoding
+    #[command(hide = true)]
+    DebugChunk {
+        /// Path to the audio file
+        file: PathBuf,
+
+        /// Requested start position in seconds
+        #[arg(long)]
+        start: f64,
+
+        /// Chunk length in seconds
+        #[arg(long, default_value_t = 10.0)]
+        len: f64,
+
+        /// Where to write the WAV chunk
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Show format details, tags and optional analysis of an audio file
+    Describe {
+        /// Path to the audio file
+        file: PathBuf,
+
+        /// Print machine-readable JSON instead of text
+        #[arg(long)]
+        json: bool,
+
+        /// Also measure peak, RMS and clipping (decodes the whole file)
+        #[arg(long)]
+        levels: bool,
+
+        /// Also estimate the tempo (decodes the whole file)
+        #[arg(long)]
+        bpm: bool,
+
+        /// Count every packet when the container doesn't store the duration,
+        /// instead of estimating it from the bitrate (reads the whole file)
+        #[arg(long)]
+        exact_duration: bool,
+    },
+    /// Estimate how long previews, normalizing and the upload will take for a file
+    Estimate {
+        /// Path to the audio file
+        file: PathBuf,
+
+        /// Estimate the upload to this platform from its own recent uploads,
+        /// and whether the file needs transcoding for it (repeatable)
+        #[arg(long, value_enum)]
+        platform: Vec<Platform>,
+    },
+    /// List the audio formats and codecs that can be read, and what gets written
+    ListFormats,
+    /// Generate preview snippets next to a file without uploading it
+    Previews {
+        /// Path to the audio file
+        file: PathBuf,
+
+        /// Preview selection mode; `auto` picks durations from the track length
+        #[arg(long, value_enum)]
+        previews: Option<PreviewMode>,
+
+        /// Preview durations in seconds, any multiple of 10 (e.g. 20,120);
+        /// overrides --previews
+        #[arg(long, visible_alias = "durations", value_delimiter = ',')]
+        preview_durations: Option<Vec<u64>>,
+
+        /// File format of the snippets
+        #[arg(long, value_enum, default_value_t = PreviewFormat::Wav)]
+        format: PreviewFormat,
+
+        /// Folder to write the snippets to (default: next to the file)
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+
+        /// Which parts of the track the preview chunks come from
+        #[arg(long, value_enum, default_value_t = PreviewStrategy::Even)]
+        preview_strategy: PreviewStrategy,
+
+        /// Climax position in seconds for --preview-strategy climax
+        /// (default: 75% of the way through)
+        #[arg(long, value_name = "SECS")]
+        climax_at: Option<f64>,
+
+        /// Start each preview chunk at these times instead of placing them
+        /// automatically, e.g. 0:30,45:00,1:20:00
+        #[arg(long, value_delimiter = ',', value_parser = parse_timestamp)]
+        preview_timestamps: Option<Vec<f64>>,
+
+        /// Gain curve for the fades at each preview chunk boundary
+        #[arg(long, value_enum, default_value_t = FadeCurve::Linear)]
+        preview_fade_curve: FadeCurve,
+
+        /// Shorten long quiet passages inside preview chunks, e.g. breakdowns.
+        /// Previews come out shorter by the time removed.
+        #[arg(long)]
+        preview_compress_silence: bool,
+
+        /// Level below which --preview-compress-silence counts audio as quiet
+        #[arg(
+            long,
+            value_name = "DBFS",
+            default_value_t = -45.0,
+            allow_negative_numbers = true,
+            requires = "preview_compress_silence"
+        )]
+        preview_silence_threshold: f64,
+
+        /// Longest quiet passage --preview-compress-silence leaves in place
+        #[arg(
+            long,
+            value_name = "SECS",
+            default_value_t = 1.0,
+            requires = "preview_compress_silence"
+        )]
+        preview_max_gap: f64,
+
+        /// Sample format of the preview WAVs
+        #[arg(long, value_enum, default_value_t = WavSampleFormat::Int16)]
+        preview_sample_format: WavSampleFormat,
+
+        /// Disk space in MB to leave free; generation stops early if it wouldn't fit
+        #[arg(long, value_name = "MB", default_value_t = 100)]
+        min_free_space: u64,
+
+        /// What to do when a preview already exists
+        #[arg(long, value_enum, default_value_t = ExistingOutput::Overwrite)]
+        replace_if_exists: ExistingOutput,
+    },
+    /// Remove old working directories, history entries and preview files.
+    /// Only reports what would go unless --yes is given; tokens and
+    /// settings are never touched.
+    Cleanup {
+        /// Only remove things last changed more than this many days ago
+        /// (at most 36500, a century)
+        #[arg(
+            long,
+            value_name = "DAYS",
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u32).range(..=36500)
+        )]
+        older_than: u32,
+
+        /// Remove working directories left by earlier runs (--keep-temp or crashes)
+        #[arg(long)]
+        sessions: bool,
+
+        /// Drop upload history entries older than --older-than
+        #[arg(long)]
+        history: bool,
+
+        /// Remove generated preview snippets (*_preview_<N>s.wav) from this folder
+        #[arg(long, value_name = "DIR")]
+        previews_in: Option<PathBuf>,
+
+        /// Where working directories were created, if --temp-dir was used
+        #[arg(long, value_name = "DIR")]
+        temp_dir: Option<PathBuf>,
+    },
+    /// Check a credentials file (config.json) before building
+    CheckConfig {
+        /// Path to the credentials file
+        #[arg(default_value = "config.json")]
+        path: PathBuf,
+
+        /// Also check that the OAuth endpoints are reachable
+        #[arg(long)]
+        check_endpoints: bool,
+    },
+    /// Allow uploads again after repeated auth failures paused them
+    ResetBreaker {
+        /// Only reset this platform
+        #[arg(value_enum)]
+        platform: Option<Platform>,
     },
+    /// Print the config directory (tokens, settings, history) and open it
+    /// in the file manager when there's a desktop
+    OpenConfigDir,
+    /// Show the settings in effect and where each comes from
+    /// (default, config, env, flag or built-in), with secrets redacted
+    PrintConfig,
     /// Show current configuration status
-    Status,
+    Status {
+        /// Print machine-readable JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// File format for preview snippets
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum PreviewFormat {
+    /// Mono WAV, see --preview-sample-format
+    #[default]
+    Wav,
+    /// Mono MP3 at 192 kbps, about a quarter of the size of a 16-bit WAV
+    /// (builds with the `mp3` feature only)
+    #[cfg_attr(not(feature = "mp3"), value(skip))]
+    Mp3,
+}
+
+/// Sample format of WAV previews
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum WavSampleFormat {
+    /// 16-bit integer PCM: smaller and plays everywhere
+    #[default]
+    Int16,
+    /// 32-bit float: the decoded samples as they are, without quantization
+    Float,
+}
+
+impl WavSampleFormat {
+    pub fn bytes_per_sample(self) -> u64 {
+        match self {
+            WavSampleFormat::Int16 => 2,
+            WavSampleFormat::Float => 4,
+        }
+    }
+}
+
+impl PreviewFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            PreviewFormat::Wav => "wav",
+            PreviewFormat::Mp3 => "mp3",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreviewMode {
+    /// Always 30s, 60s and 90s
+    Standard,
+    /// Choose durations based on the track length
+    Auto,
+}
+
+/// Where --append-duration/--append-date text goes
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AppendTarget {
+    Title,
+    Description,
+}
+
+/// How preview chunks are spread over the track
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum PreviewStrategy {
+    /// Equal parts intro, middle and end
+    #[default]
+    Even,
+    /// Half the preview from the intro, the rest spread over the track
+    IntroHeavy,
+    /// An intro chunk, then a continuous run around the climax
+    Climax,
+}
+
+/// Which part of a non-square cover to keep when cropping it to a square
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CropGravity {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Handling of artwork the image decoder doesn't support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UnsupportedImage {
+    /// Stop and ask for a JPEG or PNG
+    Error,
+    /// Upload the original bytes without checking them, with a warning
+    UploadRaw,
+}
+
+/// What to do about links and excess emoji in titles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SanitizeMode {
+    /// Upload as written and print a warning
+    #[default]
+    Warn,
+    /// Remove the offending parts before uploading
+    Strip,
+}
+
+/// Gain ramp used for preview chunk fades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum FadeCurve {
+    /// Gain changes at a constant rate
+    #[default]
+    Linear,
+    /// Sine/cosine ramps; overlapping fades keep a constant loudness
+    EqualPower,
+}
+
+/// How aggressively to merge near-duplicate tags
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum TagDedupe {
+    /// Merge tags that differ only in case or surrounding spaces
+    Exact,
+    /// Also ignore punctuation, hyphens and plural endings
+    Normalized,
+}
+
+/// What to do when a generated file already exists
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExistingOutput {
+    /// Stop with an error instead of replacing it
+    Error,
+    /// Replace it
+    Overwrite,
+    /// Keep and reuse it
+    Skip,
+}
+
+/// Payload shape for --notify-webhook
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// Raw JSON with platform, title, url, status and timestamp
+    Json,
+    /// Slack incoming webhook ({"text": ...})
+    Slack,
+    /// Discord webhook ({"content": ...})
+    Discord,
+}
+
+/// File format for generated or re-encoded artwork
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CoverFormat {
+    /// Smallest files, best for photos
+    Jpeg,
+    /// Lossless, best for text and flat graphics
+    Png,
+    /// PNG for images with few colors, JPEG otherwise
+    Auto,
+}
+
+/// Background/text colors for generated covers
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CoverScheme {
+    /// Orange to purple gradient
+    Sunset,
+    /// Teal to navy gradient
+    Ocean,
+    /// Dark green gradient
+    Forest,
+    /// Solid black with white text
+    Mono,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum Platform {
     Mixcloud,
     Soundcloud,
@@ -75,3 +822,41 @@ impl std::fmt::Display for Platform {
         }
     }
 }
+
+/// Validate an ISRC, returning it without hyphens
+fn parse_isrc(value: &str) -> Result<String, String> {
+    crate::isrc::normalize_isrc(value).map_err(|e| e.to_string())
+}
+
+fn parse_language(value: &str) -> Result<String, String> {
+    crate::language::normalize_language(value).map_err(|e| e.to_string())
+}
+
+/// Parse `SS`, `MM:SS` or `HH:MM:SS` into seconds
+fn parse_timestamp(value: &str) -> Result<f64, String> {
+    crate::tracklist::parse_timestamp(value.trim())
+        .map(|secs| secs as f64)
+        .map_err(|e| format!("invalid timestamp \"{}\": {}", value, e))
+}
+
+/// Parse a duration like `90s`, `10m` or `1h`; a bare number is seconds
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((value, ""), |i| value.split_at(i));
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration: {}", value))?;
+    let multiplier: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return Err(format!("unknown unit \"{}\" (use s, m or h)", unit)),
+    };
+    let seconds = number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("duration too long: {}", value))?;
+    Ok(Duration::from_secs(seconds))
+}
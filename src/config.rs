@@ -2,12 +2,17 @@ use aes_gcm::{
     Aes256Gcm, Nonce,
     aead::{Aead, KeyInit},
 };
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::cli::{Platform, WebhookFormat};
+use crate::webhook::Webhook;
 
 // AES-256-GCM encrypted compile-time credentials (read from config.json during build)
 const ENCRYPTED_MIXCLOUD_CLIENT_ID: &str = env!("MIXCLOUD_CLIENT_ID");
@@ -42,34 +47,160 @@ fn decrypt_string(ciphertext_hex: &str) -> String {
     String::from_utf8(plaintext).expect("Invalid UTF-8 after decryption")
 }
 
-#[derive(Debug, Clone)]
-pub struct MixcloudCredentials {
+/// OAuth client credentials for one platform
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Credentials {
     pub client_id: String,
     pub client_secret: String,
 }
 
-impl MixcloudCredentials {
-    pub fn new() -> Self {
-        Self {
-            client_id: decrypt_string(ENCRYPTED_MIXCLOUD_CLIENT_ID),
-            client_secret: decrypt_string(ENCRYPTED_MIXCLOUD_CLIENT_SECRET),
+impl Credentials {
+    /// Placeholders from config.json.example or build.rs don't count as credentials
+    fn is_placeholder(&self) -> bool {
+        [&self.client_id, &self.client_secret].iter().any(|value| {
+            value.trim().is_empty() || value.starts_with("YOUR_") || value.ends_with("_PLACEHOLDER")
+        })
+    }
+}
+
+/// Where a platform's credentials came from, highest precedence first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// DJ_UPLOADER_<PLATFORM>_CLIENT_ID and DJ_UPLOADER_<PLATFORM>_CLIENT_SECRET
+    Env,
+    /// credentials.json in the config directory, same layout as config.json
+    File(PathBuf),
+    /// Encrypted into the binary from config.json at build time
+    Compiled,
+}
+
+impl std::fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialSource::Env => write!(f, "environment"),
+            CredentialSource::File(path) => write!(f, "{}", path.display()),
+            CredentialSource::Compiled => write!(f, "built-in"),
         }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct SoundcloudCredentials {
-    pub client_id: String,
-    pub client_secret: String,
+/// Runtime credentials file, checked after the environment
+pub fn credentials_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("credentials.json"))
 }
 
-impl SoundcloudCredentials {
-    pub fn new() -> Self {
-        Self {
-            client_id: decrypt_string(ENCRYPTED_SOUNDCLOUD_CLIENT_ID),
-            client_secret: decrypt_string(ENCRYPTED_SOUNDCLOUD_CLIENT_SECRET),
+/// Environment variable prefix for a platform's credentials
+fn credentials_env_prefix(platform: Platform) -> &'static str {
+    match platform {
+        Platform::Mixcloud => "DJ_UPLOADER_MIXCLOUD",
+        Platform::Soundcloud => "DJ_UPLOADER_SOUNDCLOUD",
+    }
+}
+
+fn compiled_credentials(platform: Platform) -> Credentials {
+    let (client_id, client_secret) = match platform {
+        Platform::Mixcloud => (
+            ENCRYPTED_MIXCLOUD_CLIENT_ID,
+            ENCRYPTED_MIXCLOUD_CLIENT_SECRET,
+        ),
+        Platform::Soundcloud => (
+            ENCRYPTED_SOUNDCLOUD_CLIENT_ID,
+            ENCRYPTED_SOUNDCLOUD_CLIENT_SECRET,
+        ),
+    };
+    Credentials {
+        client_id: decrypt_string(client_id),
+        client_secret: decrypt_string(client_secret),
+    }
+}
+
+/// Whether real credentials for `platform` were built in, rather than the
+/// placeholders build.rs uses when config.json has none
+pub fn has_compiled_credentials(platform: Platform) -> bool {
+    !compiled_credentials(platform).is_placeholder()
+}
+
+/// Client credentials for a platform. The environment overrides
+/// credentials.json, which overrides the credentials built into the binary.
+pub fn resolve_credentials(platform: Platform) -> Result<Credentials> {
+    let (credentials, source) = resolve_credentials_with_source(platform)?;
+    log::debug!("Using {} credentials from {}", platform, source);
+    Ok(credentials)
+}
+
+/// [`resolve_credentials`], also saying where they came from
+pub fn resolve_credentials_with_source(
+    platform: Platform,
+) -> Result<(Credentials, CredentialSource)> {
+    let path = credentials_path()?;
+    let file = match fs::read_to_string(&path) {
+        Ok(contents) => Some(contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    resolve_from(
+        platform,
+        |name| env::var(name).ok(),
+        &path,
+        file.as_deref(),
+        compiled_credentials(platform),
+    )
+}
+
+/// Pick the first usable source; split out so each one can be tested
+fn resolve_from(
+    platform: Platform,
+    env_var: impl Fn(&str) -> Option<String>,
+    file_path: &std::path::Path,
+    file: Option<&str>,
+    compiled: Credentials,
+) -> Result<(Credentials, CredentialSource)> {
+    let prefix = credentials_env_prefix(platform);
+    let id_var = format!("{}_CLIENT_ID", prefix);
+    let secret_var = format!("{}_CLIENT_SECRET", prefix);
+    match (env_var(&id_var), env_var(&secret_var)) {
+        (Some(client_id), Some(client_secret)) => {
+            let credentials = Credentials {
+                client_id,
+                client_secret,
+            };
+            if !credentials.is_placeholder() {
+                return Ok((credentials, CredentialSource::Env));
+            }
+        }
+        (Some(_), None) => bail!("{} is set but {} is not", id_var, secret_var),
+        (None, Some(_)) => bail!("{} is set but {} is not", secret_var, id_var),
+        (None, None) => {}
+    }
+
+    let key = platform.to_string().to_lowercase();
+    if let Some(contents) = file {
+        let mut sections: BTreeMap<String, serde_json::Value> = serde_json::from_str(contents)
+            .with_context(|| format!("Failed to parse {}", file_path.display()))?;
+        if let Some(section) = sections.remove(&key) {
+            let credentials: Credentials = serde_json::from_value(section).with_context(|| {
+                format!("Invalid \"{}\" section in {}", key, file_path.display())
+            })?;
+            if !credentials.is_placeholder() {
+                return Ok((credentials, CredentialSource::File(file_path.to_path_buf())));
+            }
         }
     }
+
+    if !compiled.is_placeholder() {
+        return Ok((compiled, CredentialSource::Compiled));
+    }
+
+    bail!(
+        "No {} credentials found. Set {} and {}, add a \"{}\" section to {}, \
+         or rebuild with them in config.json",
+        platform,
+        id_var,
+        secret_var,
+        key,
+        file_path.display()
+    )
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +212,14 @@ pub struct TokenInfo {
     /// Seconds until token expires (if known)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_in: Option<i64>,
+    /// Username from the last profile lookup, so status can show it offline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_username: Option<String>,
+    /// Account tier from the same lookup (e.g. "Pro"), if the platform has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_at: Option<DateTime<Utc>>,
 }
 
 impl TokenInfo {
@@ -94,7 +233,28 @@ impl TokenInfo {
             refresh_token,
             created_at: Utc::now(),
             expires_in,
+            cached_username: None,
+            cached_tier: None,
+            cached_at: None,
+        }
+    }
+
+    /// Carry the cached profile over from the token this one replaces.
+    /// Only for refreshes; a new authorization may be a different account.
+    pub fn with_profile_cache(mut self, previous: &TokenInfo) -> Self {
+        self.cached_username = previous.cached_username.clone();
+        self.cached_tier = previous.cached_tier.clone();
+        self.cached_at = previous.cached_at;
+        self
+    }
+
+    /// Cached username, if it was looked up within the last `ttl_hours`
+    pub fn fresh_username(&self, ttl_hours: u64) -> Option<&str> {
+        let cached_at = self.cached_at?;
+        if Utc::now() - cached_at > Duration::hours(ttl_hours as i64) {
+            return None;
         }
+        self.cached_username.as_deref()
     }
 
     /// Check if token is expired or will expire soon (within 5 minutes)
@@ -111,6 +271,11 @@ impl TokenInfo {
         }
     }
 
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_in
+            .map(|expires_in| self.created_at + Duration::seconds(expires_in))
+    }
+
     pub fn time_until_expiry(&self) -> Option<Duration> {
         if let Some(expires_in) = self.expires_in {
             let expiry_time = self.created_at + Duration::seconds(expires_in);
@@ -128,12 +293,71 @@ impl TokenInfo {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Directory holding all dj-uploader state
+/// Uses XDG_CONFIG_HOME if set, otherwise ~/.config
+pub fn config_dir() -> Result<PathBuf> {
+    let config_dir = if let Ok(xdg_config) = env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(xdg_config)
+    } else {
+        dirs::home_dir()
+            .context("Failed to determine home directory")?
+            .join(".config")
+    };
+
+    Ok(config_dir.join("dj-uploader"))
+}
+
+/// Whether there's a desktop session to open windows on. Always true on
+/// macOS and Windows; elsewhere it needs an X11 or Wayland display.
+pub fn has_desktop() -> bool {
+    cfg!(any(target_os = "macos", windows))
+        || env::var_os("DISPLAY").is_some()
+        || env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Show a directory in the system file manager
+pub fn open_in_file_manager(dir: &Path) -> Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    // Not waited on: explorer exits with 1 even when it worked
+    std::process::Command::new(opener)
+        .arg(dir)
+        .spawn()
+        .with_context(|| format!("Failed to run {}", opener))?;
+    Ok(())
+}
+
+/// Name of the account stored in the top-level token fields
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Tokens for an additional named account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountTokens {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mixcloud: Option<TokenInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soundcloud: Option<TokenInfo>,
+}
+
+/// Tokens shared by every client in this process, backed by tokens.json.
+/// Clients read and write through it, so when one refreshes a token the
+/// others see it instead of later saving their stale copy over it.
+static SHARED_TOKENS: Mutex<Option<TokenStorage>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenStorage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mixcloud: Option<TokenInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub soundcloud: Option<TokenInfo>,
+    /// Additional named accounts, keyed by alias
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accounts: BTreeMap<String, AccountTokens>,
 }
 
 impl TokenStorage {
@@ -141,10 +365,7 @@ impl TokenStorage {
         let token_path = Self::token_path()?;
 
         if !token_path.exists() {
-            return Ok(Self {
-                mixcloud: None,
-                soundcloud: None,
-            });
+            return Ok(Self::default());
         }
 
         let contents = fs::read_to_string(&token_path).context("Failed to read token file")?;
@@ -171,25 +392,400 @@ impl TokenStorage {
     }
 
     pub fn token_path() -> Result<PathBuf> {
-        // Use XDG_CONFIG_HOME if set, otherwise ~/.config
-        let config_dir = if let Ok(xdg_config) = env::var("XDG_CONFIG_HOME") {
-            PathBuf::from(xdg_config)
-        } else {
-            dirs::home_dir()
-                .context("Failed to determine home directory")?
-                .join(".config")
+        Ok(config_dir()?.join("tokens.json"))
+    }
+
+    /// The process-wide tokens, loaded from disk on first use
+    pub fn shared() -> Result<TokenStorage> {
+        let mut shared = SHARED_TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+        if shared.is_none() {
+            *shared = Some(Self::load()?);
+        }
+        Ok(shared.clone().unwrap_or_default())
+    }
+
+    /// Change the process-wide tokens and write them to disk under one lock,
+    /// so concurrent updates from different clients can't undo each other.
+    /// The update applies to what's on disk now, keeping tokens another
+    /// process (a cron run, the GUI) saved since this one loaded them.
+    pub fn update_shared(update: impl FnOnce(&mut TokenStorage)) -> Result<()> {
+        let mut shared = SHARED_TOKENS.lock().unwrap_or_else(|e| e.into_inner());
+        let storage = shared.insert(Self::load()?);
+        update(storage);
+        storage.save()
+    }
+
+    /// Token for a platform, from the default account when `account` is None
+    pub fn token(&self, platform: Platform, account: Option<&str>) -> Option<&TokenInfo> {
+        let (mixcloud, soundcloud) = match account.filter(|a| *a != DEFAULT_ACCOUNT) {
+            None => (&self.mixcloud, &self.soundcloud),
+            Some(name) => {
+                let entry = self.accounts.get(name)?;
+                (&entry.mixcloud, &entry.soundcloud)
+            }
+        };
+
+        match platform {
+            Platform::Mixcloud => mixcloud.as_ref(),
+            Platform::Soundcloud => soundcloud.as_ref(),
+        }
+    }
+
+    pub fn set_token(&mut self, platform: Platform, account: Option<&str>, token_info: TokenInfo) {
+        let (mixcloud, soundcloud) = match account.filter(|a| *a != DEFAULT_ACCOUNT) {
+            None => (&mut self.mixcloud, &mut self.soundcloud),
+            Some(name) => {
+                let entry = self.accounts.entry(name.to_string()).or_default();
+                (&mut entry.mixcloud, &mut entry.soundcloud)
+            }
+        };
+
+        match platform {
+            Platform::Mixcloud => *mixcloud = Some(token_info),
+            Platform::Soundcloud => *soundcloud = Some(token_info),
+        }
+    }
+
+    /// Remember the username and tier for an account's token
+    pub fn cache_profile(
+        &mut self,
+        platform: Platform,
+        account: Option<&str>,
+        username: String,
+        tier: Option<String>,
+    ) {
+        let token = match account.filter(|a| *a != DEFAULT_ACCOUNT) {
+            None => match platform {
+                Platform::Mixcloud => self.mixcloud.as_mut(),
+                Platform::Soundcloud => self.soundcloud.as_mut(),
+            },
+            Some(name) => self
+                .accounts
+                .get_mut(name)
+                .and_then(|entry| match platform {
+                    Platform::Mixcloud => entry.mixcloud.as_mut(),
+                    Platform::Soundcloud => entry.soundcloud.as_mut(),
+                }),
+        };
+
+        if let Some(token) = token {
+            token.cached_username = Some(username);
+            token.cached_tier = tier;
+            token.cached_at = Some(Utc::now());
+        }
+    }
+
+    /// Aliases of every account authorized with a platform, default first
+    pub fn account_names(&self, platform: Platform) -> Vec<String> {
+        let mut names = Vec::new();
+        if self.token(platform, None).is_some() {
+            names.push(DEFAULT_ACCOUNT.to_string());
+        }
+        for name in self.accounts.keys() {
+            if self.token(platform, Some(name)).is_some() {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+/// Overrides for the multipart field names sent to a platform.
+/// Unset fields keep the built-in names; only needed to follow an API rename
+/// before a new release is out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldNameOverrides {
+    /// The audio file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    /// The track title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The cover image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Tags; for Mixcloud `{index}` is replaced with each tag's position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+    /// Scheduled publish date (Mixcloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish_date: Option<String>,
+    /// Unlisted flag (Mixcloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unlisted: Option<String>,
+    /// Public/private setting (SoundCloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sharing: Option<String>,
+    /// Recording ISRC (SoundCloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+    /// Language tag (SoundCloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Track type, sent as "remix" with --remix-of (SoundCloud only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_type: Option<String>,
+}
+
+impl FieldNameOverrides {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Overrides for a platform's title, description and tag limits and its
+/// title rules.
+/// Unset fields keep the built-in limits; only needed when a platform
+/// changes them before a new release is out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_max: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_max: Option<usize>,
+    /// Most tags per upload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_count_max: Option<usize>,
+    /// Longest tag, in characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_len_max: Option<usize>,
+    /// Emoji a title may have before it's reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_emoji_max: Option<usize>,
+    /// Allow links in titles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urls_in_title: Option<bool>,
+}
+
+impl LimitOverrides {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// User-tunable settings, stored next to the tokens in settings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Uploads larger than this many megabytes ask for confirmation first
+    pub large_upload_threshold_mb: u64,
+    /// Tags applied to batch uploads when none are given on the command line
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default_tags: Vec<String>,
+    /// Account alias last used for each platform in the GUI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_mixcloud_account: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_soundcloud_account: Option<String>,
+    /// Multipart field name overrides per platform
+    #[serde(skip_serializing_if = "FieldNameOverrides::is_empty")]
+    pub mixcloud_fields: FieldNameOverrides,
+    #[serde(skip_serializing_if = "FieldNameOverrides::is_empty")]
+    pub soundcloud_fields: FieldNameOverrides,
+    /// Title, description and tag limit overrides per platform
+    #[serde(skip_serializing_if = "LimitOverrides::is_empty")]
+    pub mixcloud_limits: LimitOverrides,
+    #[serde(skip_serializing_if = "LimitOverrides::is_empty")]
+    pub soundcloud_limits: LimitOverrides,
+    /// Webhook notified after every upload unless --notify-webhook is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_webhook: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_format: Option<WebhookFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_template: Option<String>,
+    /// How long a cached account username is trusted before looking it up again
+    pub username_cache_ttl_hours: u64,
+    /// Batch rate limit per platform; unset uses cautious per-platform defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_uploads_per_minute: Option<f64>,
+    /// Uploads a batch may start back to back before the rate limit applies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_burst: Option<u32>,
+    /// HTML page shown in the browser after authorizing, instead of the
+    /// built-in one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_success_page: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            large_upload_threshold_mb: 500,
+            default_tags: Vec::new(),
+            last_mixcloud_account: None,
+            last_soundcloud_account: None,
+            mixcloud_fields: FieldNameOverrides::default(),
+            soundcloud_fields: FieldNameOverrides::default(),
+            mixcloud_limits: LimitOverrides::default(),
+            soundcloud_limits: LimitOverrides::default(),
+            notify_webhook: None,
+            webhook_format: None,
+            webhook_template: None,
+            username_cache_ttl_hours: 24,
+            batch_uploads_per_minute: None,
+            batch_burst: None,
+            auth_success_page: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Webhook to notify, with command-line values taking precedence. The
+    /// message template is checked here so a typo fails before any upload.
+    pub fn webhook(
+        &self,
+        url: Option<String>,
+        format: Option<WebhookFormat>,
+        template: Option<String>,
+    ) -> Result<Option<Webhook>> {
+        let Some(url) = url.or_else(|| self.notify_webhook.clone()) else {
+            return Ok(None);
         };
+        let format = format
+            .or(self.webhook_format)
+            .unwrap_or(WebhookFormat::Json);
+        let template = template
+            .or_else(|| self.webhook_template.clone())
+            .unwrap_or_else(|| crate::webhook::DEFAULT_TEMPLATE.to_string());
+        if !matches!(format, WebhookFormat::Json) {
+            crate::template::validate(&template, crate::webhook::PLACEHOLDERS)
+                .context("Invalid webhook template")?;
+        }
+        Ok(Some(Webhook {
+            url,
+            format,
+            template,
+        }))
+    }
+
+    pub fn load() -> Result<Self> {
+        let settings_path = Self::settings_path()?;
+
+        if !settings_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents =
+            fs::read_to_string(&settings_path).context("Failed to read settings file")?;
+
+        let settings: Settings =
+            serde_json::from_str(&contents).context("Failed to parse settings file")?;
+
+        Ok(settings)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let settings_path = Self::settings_path()?;
+
+        if let Some(parent) = settings_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create settings directory")?;
+        }
+
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize settings")?;
+
+        fs::write(&settings_path, contents).context("Failed to write settings file")?;
+
+        Ok(())
+    }
+
+    pub fn settings_path() -> Result<PathBuf> {
+        Ok(config_dir()?.join("settings.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn credentials(id: &str) -> Credentials {
+        Credentials {
+            client_id: id.to_string(),
+            client_secret: format!("{}-secret", id),
+        }
+    }
 
-        Ok(config_dir.join("dj-uploader").join("tokens.json"))
+    const FILE: &str = r#"{"mixcloud": {"client_id": "file", "client_secret": "file-secret"}}"#;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "DJ_UPLOADER_MIXCLOUD_CLIENT_ID" => Some("env".to_string()),
+            "DJ_UPLOADER_MIXCLOUD_CLIENT_SECRET" => Some("env-secret".to_string()),
+            _ => None,
+        }
     }
 
-    pub fn set_mixcloud_tokens(&mut self, token_info: TokenInfo) {
-        self.mixcloud = Some(token_info);
+    #[test]
+    fn test_env_overrides_file_and_compiled() {
+        let path = Path::new("credentials.json");
+        let (resolved, source) = resolve_from(
+            Platform::Mixcloud,
+            env,
+            path,
+            Some(FILE),
+            credentials("built"),
+        )
+        .unwrap();
+        assert_eq!(resolved, credentials("env"));
+        assert_eq!(source, CredentialSource::Env);
     }
 
-    pub fn get_mixcloud_token(&self) -> Result<&TokenInfo> {
-        self.mixcloud
-            .as_ref()
-            .context("Not authorized with Mixcloud. Run 'dj-uploader auth mixcloud' first")
+    #[test]
+    fn test_file_overrides_compiled() {
+        let path = Path::new("credentials.json");
+        let (resolved, source) = resolve_from(
+            Platform::Mixcloud,
+            |_| None,
+            path,
+            Some(FILE),
+            credentials("built"),
+        )
+        .unwrap();
+        assert_eq!(resolved, credentials("file"));
+        assert_eq!(source, CredentialSource::File(path.to_path_buf()));
+
+        // A file without this platform's section falls through to the built-in ones
+        let (resolved, source) = resolve_from(
+            Platform::Soundcloud,
+            |_| None,
+            path,
+            Some(FILE),
+            credentials("built"),
+        )
+        .unwrap();
+        assert_eq!(resolved, credentials("built"));
+        assert_eq!(source, CredentialSource::Compiled);
+    }
+
+    #[test]
+    fn test_no_credentials_is_an_error() {
+        let path = Path::new("credentials.json");
+        let placeholder = Credentials {
+            client_id: "SOUNDCLOUD_CLIENT_ID_PLACEHOLDER".to_string(),
+            client_secret: "SOUNDCLOUD_CLIENT_SECRET_PLACEHOLDER".to_string(),
+        };
+        let error = resolve_from(Platform::Soundcloud, |_| None, path, None, placeholder)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("No SoundCloud credentials found"));
+        assert!(error.contains("DJ_UPLOADER_SOUNDCLOUD_CLIENT_ID"));
+
+        // Half-set environment variables are reported rather than skipped
+        let error = resolve_from(
+            Platform::Mixcloud,
+            |name| (name == "DJ_UPLOADER_MIXCLOUD_CLIENT_ID").then(|| "env".to_string()),
+            path,
+            None,
+            credentials("built"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("DJ_UPLOADER_MIXCLOUD_CLIENT_SECRET is not"));
     }
 }
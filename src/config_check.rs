@@ -0,0 +1,202 @@
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Placeholder values from config.json.example
+const PLACEHOLDER_PREFIX: &str = "YOUR_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Result of checking one field
+#[derive(Debug)]
+pub struct Finding {
+    pub level: Level,
+    pub field: String,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(level: Level, field: &str, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Check a credentials file field by field. Secret values are never included
+/// in the findings, only whether they look right.
+pub fn check_config(contents: &str) -> Result<Vec<Finding>> {
+    let config: Value = serde_json::from_str(contents).context("config.json is not valid JSON")?;
+    let Some(config) = config.as_object() else {
+        bail!("config.json must contain a JSON object");
+    };
+
+    let mut findings = Vec::new();
+
+    match config.get("mixcloud") {
+        Some(section) => check_section(&mut findings, "mixcloud", section, Level::Error),
+        None => findings.push(Finding::new(Level::Error, "mixcloud", "missing (required)")),
+    }
+
+    match config.get("soundcloud") {
+        Some(section) => check_section(&mut findings, "soundcloud", section, Level::Warning),
+        None => findings.push(Finding::new(
+            Level::Warning,
+            "soundcloud",
+            "not set, SoundCloud uploads will be unavailable",
+        )),
+    }
+
+    for key in config.keys() {
+        if key != "mixcloud" && key != "soundcloud" {
+            findings.push(Finding::new(
+                Level::Warning,
+                key,
+                "unknown section, ignored",
+            ));
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Check `client_id`/`client_secret` in one platform section; problems are
+/// reported at `severity`
+fn check_section(findings: &mut Vec<Finding>, name: &str, section: &Value, severity: Level) {
+    let Some(section) = section.as_object() else {
+        findings.push(Finding::new(severity, name, "must be an object"));
+        return;
+    };
+
+    for key in ["client_id", "client_secret"] {
+        let field = format!("{}.{}", name, key);
+        let finding = match section.get(key) {
+            None => Finding::new(severity, &field, "missing"),
+            Some(Value::String(value)) => check_value(severity, &field, value),
+            Some(_) => Finding::new(severity, &field, "must be a string"),
+        };
+        findings.push(finding);
+    }
+}
+
+fn check_value(severity: Level, field: &str, value: &str) -> Finding {
+    if value.trim().is_empty() {
+        Finding::new(severity, field, "empty")
+    } else if value.starts_with(PLACEHOLDER_PREFIX) {
+        Finding::new(
+            severity,
+            field,
+            "still the placeholder from config.json.example",
+        )
+    } else if value.chars().any(char::is_whitespace) {
+        Finding::new(
+            severity,
+            field,
+            "contains whitespace, check for a copy/paste error",
+        )
+    } else {
+        Finding::new(
+            Level::Ok,
+            field,
+            format!("present ({} characters)", value.chars().count()),
+        )
+    }
+}
+
+/// Check that the OAuth endpoints answer at all
+pub fn check_endpoints() -> Vec<Finding> {
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return vec![Finding::new(Level::Error, "network", e.to_string())],
+    };
+
+    [
+        (
+            "mixcloud oauth",
+            crate::platforms::mixcloud::OAUTH_AUTHORIZE_URL,
+        ),
+        (
+            "soundcloud oauth",
+            crate::platforms::soundcloud::OAUTH_AUTHORIZE_URL,
+        ),
+    ]
+    .into_iter()
+    .map(|(field, url)| match client.get(url).send() {
+        Ok(response) if !response.status().is_server_error() => Finding::new(
+            Level::Ok,
+            field,
+            format!("reachable ({})", response.status()),
+        ),
+        Ok(response) => Finding::new(
+            Level::Warning,
+            field,
+            format!("server error ({})", response.status()),
+        ),
+        Err(e) => Finding::new(Level::Warning, field, format!("unreachable: {}", e)),
+    })
+    .collect()
+}
+
+/// Run `check-config` and print the findings; fails if any field is unusable
+pub fn handle_check_config(path: &Path, endpoints: bool) -> Result<()> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut findings = check_config(&contents)?;
+    if endpoints {
+        findings.extend(check_endpoints());
+    }
+
+    println!("Checking {}:", path.display());
+    for finding in &findings {
+        let icon = match finding.level {
+            Level::Ok => "✓",
+            Level::Warning => "⚠",
+            Level::Error => "✗",
+        };
+        println!("  {} {}: {}", icon, finding.field, finding.message);
+    }
+
+    let errors = findings.iter().filter(|f| f.level == Level::Error).count();
+    if errors > 0 {
+        bail!("{} problem(s) found in {}", errors, path.display());
+    }
+    println!("\n✓ Config looks good");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_config() {
+        let findings = check_config(
+            r#"{
+                "mixcloud": {"client_id": "abc123def456", "client_secret": "YOUR_MIXCLOUD_CLIENT_SECRET"},
+                "extra": {}
+            }"#,
+        )
+        .unwrap();
+        let level = |field: &str| findings.iter().find(|f| f.field == field).unwrap().level;
+
+        assert_eq!(level("mixcloud.client_id"), Level::Ok);
+        assert_eq!(level("mixcloud.client_secret"), Level::Error);
+        assert_eq!(level("soundcloud"), Level::Warning);
+        assert_eq!(level("extra"), Level::Warning);
+        assert!(findings.iter().all(|f| !f.message.contains("abc123")));
+
+        assert!(check_config("{ not json").is_err());
+    }
+}
@@ -0,0 +1,658 @@
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont, point};
+use anyhow::{Context, Result, bail};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::{Rgb, RgbImage};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{
+    CoverFormat, CoverScheme, CropGravity, ExistingOutput, Platform, UnsupportedImage,
+};
+use crate::id3;
+
+/// Encoded image file contents
+pub type ImageBytes = Vec<u8>;
+
+/// Fonts tried in order when no `--cover-font` is given
+const SYSTEM_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans-Bold.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf",
+    "/System/Library/Fonts/Supplemental/Arial Bold.ttf",
+    "/Library/Fonts/Arial Bold.ttf",
+    "C:\\Windows\\Fonts\\arialbd.ttf",
+];
+
+/// Top color, bottom color and text color for a scheme
+fn scheme_colors(scheme: CoverScheme) -> ([u8; 3], [u8; 3], [u8; 3]) {
+    match scheme {
+        CoverScheme::Sunset => ([255, 126, 95], [106, 48, 147], [255, 255, 255]),
+        CoverScheme::Ocean => ([0, 180, 170], [10, 30, 80], [255, 255, 255]),
+        CoverScheme::Forest => ([60, 120, 70], [15, 40, 25], [235, 245, 225]),
+        CoverScheme::Mono => ([20, 20, 20], [20, 20, 20], [255, 255, 255]),
+    }
+}
+
+pub struct CoverOptions {
+    /// Width and height in pixels
+    pub size: u32,
+    pub scheme: CoverScheme,
+    /// TrueType/OpenType font for the title; system fonts are tried if None
+    pub font: Option<PathBuf>,
+    pub format: CoverFormat,
+    /// Peak level per column (0.0 to 1.0) to draw as a waveform, if any
+    pub waveform: Option<Vec<f32>>,
+    /// Draw the title on the cover
+    pub show_title: bool,
+}
+
+impl CoverOptions {
+    pub fn for_platform(
+        platform: Platform,
+        scheme: CoverScheme,
+        font: Option<PathBuf>,
+        format: CoverFormat,
+    ) -> Self {
+        Self {
+            size: recommended_cover_size(platform),
+            scheme,
+            font,
+            format,
+            waveform: None,
+            show_title: true,
+        }
+    }
+}
+
+/// Square artwork size each platform recommends
+pub fn recommended_cover_size(platform: Platform) -> u32 {
+    match platform {
+        Platform::Mixcloud => 1400,
+        Platform::Soundcloud => 800,
+    }
+}
+
+/// Render a square cover with the title over a gradient background
+fn render_cover(title: &str, opts: &CoverOptions) -> Result<RgbImage> {
+    let (top, bottom, text_color) = scheme_colors(opts.scheme);
+    let size = opts.size.max(1);
+
+    let mut img = RgbImage::from_fn(size, size, |_, y| {
+        let t = y as f32 / size as f32;
+        Rgb(std::array::from_fn(|i| {
+            (top[i] as f32 + (bottom[i] as f32 - top[i] as f32) * t).round() as u8
+        }))
+    });
+
+    if let Some(peaks) = &opts.waveform {
+        // Keep the middle free for the title, otherwise fill the cover
+        let (center, height) = if opts.show_title {
+            (size as f32 * 0.8, size as f32 * 0.25)
+        } else {
+            (size as f32 * 0.5, size as f32 * 0.6)
+        };
+        render_waveform(&mut img, peaks, text_color, center, height);
+    }
+    if opts.show_title {
+        let font = load_font(opts.font.as_deref())?;
+        draw_title(&mut img, &font, title, text_color)?;
+    }
+    Ok(img)
+}
+
+/// Draw peaks as a mirrored waveform around the horizontal line at `center`,
+/// at most `height` pixels tall, blended into the background
+fn render_waveform(img: &mut RgbImage, peaks: &[f32], color: [u8; 3], center: f32, height: f32) {
+    if peaks.is_empty() {
+        return;
+    }
+
+    let margin = (img.width() as f32 * 0.08) as u32;
+    let width = img.width().saturating_sub(2 * margin).max(1);
+    for x in 0..width {
+        let column = (x as usize * peaks.len() / width as usize).min(peaks.len() - 1);
+        let half = (peaks[column].clamp(0.0, 1.0) * height / 2.0).max(1.0);
+        let top = (center - half).max(0.0) as u32;
+        let bottom = ((center + half) as u32).min(img.height());
+        for y in top..bottom {
+            let pixel = img.get_pixel_mut(margin + x, y);
+            for (channel, &target) in pixel.0.iter_mut().zip(&color) {
+                *channel = (*channel as f32 * 0.2 + target as f32 * 0.8).round() as u8;
+            }
+        }
+    }
+}
+
+/// Settle `Auto`: flat graphics with few colors stay sharp and small as PNG,
+/// photos compress far better as JPEG
+fn resolve_format(img: &RgbImage, format: CoverFormat) -> CoverFormat {
+    match format {
+        CoverFormat::Auto => {
+            // Sample at most ~64k pixels on big images
+            let step = (img.pixels().len() / 65536).max(1);
+            let mut colors = std::collections::HashSet::new();
+            for pixel in img.pixels().step_by(step) {
+                colors.insert(pixel.0);
+                if colors.len() > 256 {
+                    return CoverFormat::Jpeg;
+                }
+            }
+            CoverFormat::Png
+        }
+        format => format,
+    }
+}
+
+/// File extension for a resolved format
+fn extension(format: CoverFormat) -> &'static str {
+    match format {
+        CoverFormat::Png => "png",
+        CoverFormat::Jpeg | CoverFormat::Auto => "jpg",
+    }
+}
+
+/// Encode as PNG, or as JPEG at `quality`
+fn encode_image(img: &RgbImage, format: CoverFormat, quality: u8) -> Result<ImageBytes> {
+    let mut bytes = ImageBytes::new();
+    match format {
+        CoverFormat::Png => img
+            .write_with_encoder(PngEncoder::new(&mut bytes))
+            .context("Failed to encode PNG image")?,
+        CoverFormat::Jpeg | CoverFormat::Auto => JpegEncoder::new_with_quality(&mut bytes, quality)
+            .encode_image(img)
+            .context("Failed to encode JPEG image")?,
+    }
+    Ok(bytes)
+}
+
+/// MIME type for an image file, from its extension
+pub fn image_mime(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "heic" => Some("image/heic"),
+        "heif" => Some("image/heif"),
+        "avif" => Some("image/avif"),
+        "webp" => Some("image/webp"),
+        "gif" => Some("image/gif"),
+        "bmp" => Some("image/bmp"),
+        "tif" | "tiff" => Some("image/tiff"),
+        _ => None,
+    }
+}
+
+/// Recognize image formats this build can't decode from their first bytes,
+/// so they get a clear message without attempting a full decode
+fn unsupported_image_format(header: &[u8]) -> Option<&'static str> {
+    // ISO-BMFF images: "ftyp" box at offset 4, followed by the brand
+    if header.len() >= 12 && &header[4..8] == b"ftyp" {
+        return match &header[8..12] {
+            b"heic" | b"heix" | b"hevc" | b"hevx" | b"mif1" | b"msf1" => Some("HEIC"),
+            b"avif" | b"avis" => Some("AVIF"),
+            _ => None,
+        };
+    }
+    if header.starts_with(b"II*\0") || header.starts_with(b"MM\0*") {
+        return Some("TIFF");
+    }
+    None
+}
+
+/// The format of an image file this build can't decode, if it is one
+fn sniff_unsupported_image(image_path: &Path) -> Result<Option<&'static str>> {
+    use std::io::Read;
+
+    let mut header = [0u8; 16];
+    let mut file = fs::File::open(image_path)
+        .with_context(|| format!("Failed to open image: {}", image_path.display()))?;
+    let len = file
+        .read(&mut header)
+        .with_context(|| format!("Failed to read image: {}", image_path.display()))?;
+    Ok(unsupported_image_format(&header[..len]))
+}
+
+/// Make sure an image path isn't the audio file and actually reads as an
+/// image. Catches swapped file/image columns before anything is uploaded.
+/// Formats that can't be decoded here (e.g. HEIC from an iPhone) fail with a
+/// request to convert, or pass unchecked with `UnsupportedImage::UploadRaw`.
+pub fn check_image(
+    audio_path: &Path,
+    image_path: &Path,
+    unsupported: UnsupportedImage,
+) -> Result<()> {
+    let same_file = match (fs::canonicalize(audio_path), fs::canonicalize(image_path)) {
+        (Ok(audio), Ok(image)) => audio == image,
+        _ => audio_path == image_path,
+    };
+    if same_file {
+        bail!(
+            "Image is the same file as the audio: {}. Check that the file and image weren't swapped",
+            image_path.display()
+        );
+    }
+
+    if let Some(format) = sniff_unsupported_image(image_path)? {
+        match unsupported {
+            UnsupportedImage::Error => bail!(
+                "{} is a {} image, which isn't supported. Please convert it to JPEG or PNG \
+                 (or pass --unsupported-image upload-raw to send it as is)",
+                image_path.display(),
+                format
+            ),
+            UnsupportedImage::UploadRaw => {
+                eprintln!(
+                    "⚠ Warning: {} is a {} image and can't be checked; uploading it unchanged ({})",
+                    image_path.display(),
+                    format,
+                    image_mime(image_path).unwrap_or("no content type")
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    image::ImageReader::open(image_path)
+        .with_context(|| format!("Failed to open image: {}", image_path.display()))?
+        .with_guessed_format()
+        .with_context(|| format!("Failed to read image: {}", image_path.display()))?
+        .into_dimensions()
+        .with_context(|| {
+            format!(
+                "{} is not a readable JPEG, PNG, GIF, WebP or BMP image. Is it an audio file?",
+                image_path.display()
+            )
+        })?;
+    Ok(())
+}
+
+/// Horizontal and vertical crop offsets (see [`square_crop`]) for a gravity
+pub fn gravity_offsets(gravity: CropGravity) -> (f32, f32) {
+    match gravity {
+        CropGravity::Center => (0.5, 0.5),
+        CropGravity::Top => (0.5, 0.0),
+        CropGravity::Bottom => (0.5, 1.0),
+        CropGravity::Left => (0.0, 0.5),
+        CropGravity::Right => (1.0, 0.5),
+    }
+}
+
+/// The largest square in a `width`x`height` image as (x, y, side).
+/// Offsets run from 0.0 (keep the left/top edge) through 0.5 (center) to 1.0
+/// (keep the right/bottom edge); only the one along the longer side matters.
+pub fn square_crop(width: u32, height: u32, x_offset: f32, y_offset: f32) -> (u32, u32, u32) {
+    let side = width.min(height);
+    let x = ((width - side) as f32 * x_offset.clamp(0.0, 1.0)).round() as u32;
+    let y = ((height - side) as f32 * y_offset.clamp(0.0, 1.0)).round() as u32;
+    (x, y, side)
+}
+
+/// Crop an image to a square at the given offsets and write it as
+/// `<stem>_square.jpg` (or `.png`). Square images, and formats that can't be
+/// decoded here, are returned as they are.
+pub fn crop_image(
+    image_path: &Path,
+    x_offset: f32,
+    y_offset: f32,
+    format: CoverFormat,
+    output_dir: &Path,
+) -> Result<PathBuf> {
+    // Left to --unsupported-image, which decides whether it can be uploaded
+    if let Some(format) = sniff_unsupported_image(image_path)? {
+        eprintln!(
+            "⚠ Warning: {} is a {} image and can't be cropped; using it as is",
+            image_path.display(),
+            format
+        );
+        return Ok(image_path.to_path_buf());
+    }
+
+    let img = image::ImageReader::open(image_path)
+        .with_context(|| format!("Failed to open image: {}", image_path.display()))?
+        .with_guessed_format()?
+        .decode()
+        .with_context(|| format!("Failed to decode image: {}", image_path.display()))?;
+    if img.width() == img.height() {
+        return Ok(image_path.to_path_buf());
+    }
+
+    let (x, y, side) = square_crop(img.width(), img.height(), x_offset, y_offset);
+    let rgb = img.crop_imm(x, y, side, side).to_rgb8();
+    let format = resolve_format(&rgb, format);
+    let bytes = encode_image(&rgb, format, 90).context("Failed to encode cropped image")?;
+
+    let stem = image_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("cover");
+    let output = output_dir.join(format!("{}_square.{}", stem, extension(format)));
+    fs::write(&output, &bytes)
+        .with_context(|| format!("Failed to write cropped image: {}", output.display()))?;
+    Ok(output)
+}
+
+/// Embed artwork into a copy of the audio file so downloads carry it too.
+/// MP3 gets an ID3v2 APIC frame, M4A an iTunes `covr` item; other formats
+/// are returned unchanged with a warning. Writes `<stem>_cover.<ext>` and
+/// returns the path of the file that should be uploaded.
+pub fn embed_cover(
+    audio_path: &Path,
+    image_bytes: &[u8],
+    output_dir: &Path,
+    existing: ExistingOutput,
+) -> Result<PathBuf> {
+    let is_png = image_bytes.starts_with(b"\x89PNG");
+    let mime = if is_png {
+        "image/png"
+    } else if image_bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else {
+        bail!("Only JPEG and PNG artwork can be embedded in audio files");
+    };
+
+    let data = fs::read(audio_path).context("Failed to read audio file")?;
+    let (extension, output) = match crate::audio::extension_from_header(&data) {
+        Some("mp3") => {
+            // Front cover: encoding, MIME type, picture type 3, empty description
+            let mut apic = vec![0x00];
+            apic.extend_from_slice(mime.as_bytes());
+            apic.extend_from_slice(&[0x00, 0x03, 0x00]);
+            apic.extend_from_slice(image_bytes);
+
+            let (version, mut frames, audio_start) = id3::split_tag(&data, &[b"APIC"]);
+            frames.extend(id3::frame(version, b"APIC", &apic));
+            (
+                "mp3",
+                id3::write_tag(version, &frames, &data[audio_start..]),
+            )
+        }
+        Some("m4a") => {
+            let extension = audio_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("m4a");
+            (
+                extension,
+                crate::mp4::embed_cover(&data, image_bytes, is_png)?,
+            )
+        }
+        _ => {
+            eprintln!(
+                "⚠ Warning: Cover embedding is only implemented for MP3 and M4A, uploading {} unchanged",
+                audio_path.display()
+            );
+            return Ok(audio_path.to_path_buf());
+        }
+    };
+
+    let stem = audio_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid file name")?;
+    let output_path = output_dir.join(format!("{}_cover.{}", stem, extension));
+    if !crate::output::should_write(&output_path, existing)? {
+        return Ok(output_path);
+    }
+
+    fs::write(&output_path, output).context("Failed to write audio file with cover")?;
+    Ok(output_path)
+}
+
+/// Downscale an image to fit `max_size` and re-encode it, for platforms that
+/// rejected the original. Writes `<stem>_reduced.jpg` (or `.png`).
+pub fn reduce_image(
+    image_path: &Path,
+    max_size: u32,
+    format: CoverFormat,
+    output_dir: &Path,
+) -> Result<PathBuf> {
+    let img = image::ImageReader::open(image_path)
+        .with_context(|| format!("Failed to open image: {}", image_path.display()))?
+        .with_guessed_format()?
+        .decode()
+        .with_context(|| format!("Failed to decode image: {}", image_path.display()))?;
+
+    let img = if img.width() > max_size || img.height() > max_size {
+        img.resize(max_size, max_size, image::imageops::FilterType::Lanczos3)
+    } else {
+        img
+    };
+    let rgb = img.to_rgb8();
+    let format = resolve_format(&rgb, format);
+    let bytes = encode_image(&rgb, format, 85).context("Failed to encode reduced image")?;
+
+    let stem = image_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("cover");
+    let output = output_dir.join(format!("{}_reduced.{}", stem, extension(format)));
+    fs::write(&output, &bytes)
+        .with_context(|| format!("Failed to write reduced image: {}", output.display()))?;
+
+    log::info!(
+        "Reduced artwork {} ({}x{}, {} KB) to {}",
+        image_path.display(),
+        rgb.width(),
+        rgb.height(),
+        bytes.len() / 1024,
+        output.display()
+    );
+    Ok(output)
+}
+
+/// Generate a cover and save it in `output_dir` as `<stem>_cover.jpg` (or `.png`)
+pub fn write_cover(
+    audio_path: &Path,
+    title: &str,
+    opts: &CoverOptions,
+    output_dir: &Path,
+    existing: ExistingOutput,
+) -> Result<PathBuf> {
+    let img = render_cover(title, opts)?;
+    let format = resolve_format(&img, opts.format);
+
+    let stem = audio_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("cover");
+    let output = output_dir.join(format!("{}_cover.{}", stem, extension(format)));
+    if !crate::output::should_write(&output, existing)? {
+        return Ok(output);
+    }
+
+    let bytes = encode_image(&img, format, 90).context("Failed to encode cover image")?;
+    fs::write(&output, bytes)
+        .with_context(|| format!("Failed to write cover: {}", output.display()))?;
+    Ok(output)
+}
+
+fn load_font(path: Option<&Path>) -> Result<FontVec> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => SYSTEM_FONTS
+            .iter()
+            .map(PathBuf::from)
+            .find(|p| p.exists())
+            .context("No usable font found; pass one with --cover-font")?,
+    };
+
+    let data =
+        fs::read(&path).with_context(|| format!("Failed to read font: {}", path.display()))?;
+    FontVec::try_from_vec(data).with_context(|| format!("Invalid font: {}", path.display()))
+}
+
+/// Word-wrap the title and draw it centered on the image
+fn draw_title(img: &mut RgbImage, font: &FontVec, title: &str, color: [u8; 3]) -> Result<()> {
+    let size = img.width() as f32;
+    let margin = size * 0.1;
+    let max_width = size - 2.0 * margin;
+
+    // Shrink the text until the wrapped title fits inside the margins
+    let mut px = size * 0.12;
+    let (scale, lines) = loop {
+        let scale = PxScale::from(px);
+        let lines = wrap_words(font, scale, title, max_width);
+        let height = lines.len() as f32 * font.as_scaled(scale).height();
+        let widest = lines
+            .iter()
+            .map(|line| line_width(font, scale, line))
+            .fold(0.0, f32::max);
+
+        if (height <= max_width && widest <= max_width) || px <= 12.0 {
+            break (scale, lines);
+        }
+        px *= 0.9;
+    };
+
+    if lines.is_empty() {
+        bail!("Title is empty, nothing to render");
+    }
+
+    let scaled = font.as_scaled(scale);
+    let line_height = scaled.height();
+    let mut baseline = (size - lines.len() as f32 * line_height) / 2.0 + scaled.ascent();
+
+    for line in &lines {
+        let mut x = (size - line_width(font, scale, line)) / 2.0;
+        let mut previous = None;
+        for c in line.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(prev) = previous {
+                x += scaled.kern(prev, id);
+            }
+            let glyph = id.with_scale_and_position(scale, point(x, baseline));
+            x += scaled.h_advance(id);
+            previous = Some(id);
+
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i32 + gx as i32;
+                let py = bounds.min.y as i32 + gy as i32;
+                if px < 0 || py < 0 || px as u32 >= img.width() || py as u32 >= img.height() {
+                    return;
+                }
+                let pixel = img.get_pixel_mut(px as u32, py as u32);
+                for (channel, &target) in pixel.0.iter_mut().zip(&color) {
+                    let blended = *channel as f32 * (1.0 - coverage) + target as f32 * coverage;
+                    *channel = blended.round() as u8;
+                }
+            });
+        }
+        baseline += line_height;
+    }
+
+    Ok(())
+}
+
+fn line_width(font: &FontVec, scale: PxScale, line: &str) -> f32 {
+    let scaled = font.as_scaled(scale);
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in line.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            width += scaled.kern(prev, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+fn wrap_words(font: &FontVec, scale: PxScale, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+
+        if !current.is_empty() && line_width(font, scale, &candidate) > max_width {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_format() {
+        let flat = RgbImage::from_pixel(64, 64, Rgb([20, 20, 20]));
+        assert!(matches!(
+            resolve_format(&flat, CoverFormat::Auto),
+            CoverFormat::Png
+        ));
+
+        let photo = RgbImage::from_fn(64, 64, |x, y| Rgb([x as u8 * 4, y as u8 * 4, 128]));
+        assert!(matches!(
+            resolve_format(&photo, CoverFormat::Auto),
+            CoverFormat::Jpeg
+        ));
+        assert!(matches!(
+            resolve_format(&flat, CoverFormat::Jpeg),
+            CoverFormat::Jpeg
+        ));
+    }
+
+    #[test]
+    fn test_square_crop() {
+        assert_eq!(square_crop(1600, 900, 0.5, 0.5), (350, 0, 900));
+        assert_eq!(square_crop(1600, 900, 0.0, 0.5), (0, 0, 900));
+        assert_eq!(square_crop(900, 1600, 0.5, 1.0), (0, 700, 900));
+        assert_eq!(square_crop(500, 500, 1.0, 1.0), (0, 0, 500));
+    }
+
+    #[test]
+    fn test_check_image_rejects_audio() {
+        let dir = crate::temp::TestDir::new("cover");
+        let audio = dir.join("mix.mp3");
+        // ID3 header followed by an MPEG frame sync
+        fs::write(&audio, b"ID3\x04\x00\x00\x00\x00\x00\x00\xff\xfb\x90\x64").unwrap();
+
+        let err = check_image(&audio, &audio, UnsupportedImage::Error).unwrap_err();
+        assert!(err.to_string().contains("same file as the audio"));
+
+        let swapped = dir.join("cover.mp3");
+        fs::copy(&audio, &swapped).unwrap();
+        let err = check_image(&audio, &swapped, UnsupportedImage::Error).unwrap_err();
+        assert!(format!("{:#}", err).contains("not a readable JPEG, PNG"));
+
+        // An iPhone photo is recognized from its header alone
+        let heic = dir.join("IMG_0001.HEIC");
+        fs::write(&heic, b"\0\0\0\x18ftypheic\0\0\0\0mif1heic").unwrap();
+        let err = check_image(&audio, &heic, UnsupportedImage::Error).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("HEIC image, which isn't supported")
+        );
+        assert!(check_image(&audio, &heic, UnsupportedImage::UploadRaw).is_ok());
+        let cropped = crop_image(&heic, 0.5, 0.5, CoverFormat::Auto, &dir).unwrap();
+        assert_eq!(cropped, heic);
+
+        // GIF, WebP and BMP decode; other files starting with "BM" don't pass as BMP
+        let gif = dir.join("cover.gif");
+        image::RgbImage::new(4, 2).save(&gif).unwrap();
+        assert!(check_image(&audio, &gif, UnsupportedImage::Error).is_ok());
+        let notes = dir.join("BMX notes.bmp");
+        fs::write(&notes, b"BMX track notes").unwrap();
+        let err = check_image(&audio, &notes, UnsupportedImage::UploadRaw).unwrap_err();
+        assert!(format!("{:#}", err).contains("not a readable"));
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::Platform;
+use crate::limits::Limits;
+
+/// Combine `--description` and `--description-file`.
+/// When both are given the inline text comes first, followed by a blank line
+/// and the file contents.
+pub fn load_description(text: Option<&str>, file: Option<&Path>) -> Result<Option<String>> {
+    let file_body = match file {
+        Some(path) => {
+            let body = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read description file: {}", path.display()))?;
+            Some(body.trim_end().to_string())
+        }
+        None => None,
+    };
+
+    Ok(match (text, file_body) {
+        (Some(text), Some(body)) => Some(format!("{}\n\n{}", text, body)),
+        (Some(text), None) => Some(text.to_string()),
+        (None, Some(body)) => Some(body),
+        (None, None) => None,
+    })
+}
+
+/// Add a "Remix of" credit line below the description. Neither platform's
+/// upload API has a field for the original work (SoundCloud's track type only
+/// says it's a remix), so the credit itself travels in the text.
+pub fn with_attribution(description: Option<String>, remix_of: Option<&str>) -> Option<String> {
+    let Some(original) = remix_of.map(str::trim).filter(|o| !o.is_empty()) else {
+        return description;
+    };
+    let credit = format!("Remix of: {}", original);
+    Some(match description {
+        Some(d) => format!("{}\n\n{}", d.trim_end(), credit),
+        None => credit,
+    })
+}
+
+/// Description for part `part` of `parts` of a split upload: the original
+/// text, which part this is, and links to the parts already uploaded
+pub fn part_description(
+    description: Option<&str>,
+    part: usize,
+    parts: usize,
+    previous_links: &[String],
+) -> String {
+    let mut lines = Vec::new();
+    if let Some(description) = description {
+        lines.push(description.trim_end().to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!("Part {} of {}", part, parts));
+    for (i, link) in previous_links.iter().enumerate() {
+        lines.push(format!("Part {}: {}", i + 1, link));
+    }
+    lines.join("\n")
+}
+
+/// Check a description against the platform limit, showing the current and
+/// maximum length. Warns when it's too long, or errors with `strict`.
+pub fn validate_description(
+    platform: Platform,
+    limits: &Limits,
+    description: &str,
+    strict: bool,
+) -> Result<()> {
+    let max = limits.description_max;
+    let len = description.chars().count();
+
+    if len <= max {
+        return Ok(());
+    }
+
+    if strict {
+        bail!(
+            "Description is {} characters, {} allows at most {}",
+            len,
+            platform,
+            max
+        );
+    }
+
+    eprintln!(
+        "⚠ Warning: Description is {} characters, {} allows at most {}; the rest will be cut off",
+        len, platform, max
+    );
+    Ok(())
+}
+
+/// Validate a description and truncate it to the platform limit
+pub fn fit_description(
+    platform: Platform,
+    limits: &Limits,
+    description: &str,
+    strict: bool,
+) -> Result<String> {
+    validate_description(platform, limits, description, strict)?;
+    Ok(description.chars().take(limits.description_max).collect())
+}
@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+
+use crate::cli::Platform;
+use crate::config::{self, CredentialSource, Settings};
+use crate::limits::Limits;
+
+/// Where a value in effect came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    Default,
+    Config,
+    Env,
+    Flag,
+    BuiltIn,
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::Default => write!(f, "default"),
+            Origin::Config => write!(f, "config"),
+            Origin::Env => write!(f, "env"),
+            Origin::Flag => write!(f, "flag"),
+            Origin::BuiltIn => write!(f, "built-in"),
+        }
+    }
+}
+
+/// Settings whose values can hold secrets, e.g. a Slack webhook URL
+const SECRET_SETTINGS: &[&str] = &["notify_webhook"];
+
+/// Print every setting in effect with where it came from. Secrets are
+/// redacted; client IDs and webhook URLs are shortened.
+pub fn print_config() -> Result<()> {
+    let config_dir = config::config_dir()?;
+    let dir_origin = if std::env::var_os("XDG_CONFIG_HOME").is_some() {
+        Origin::Env
+    } else {
+        Origin::Default
+    };
+    let settings_path = Settings::settings_path()?;
+    let credentials_path = config::credentials_path()?;
+
+    println!("Paths:");
+    print_row("config dir", &config_dir.display().to_string(), dir_origin);
+    print_row("settings", &file_state(&settings_path), dir_origin);
+    print_row("credentials", &file_state(&credentials_path), dir_origin);
+
+    println!("\nCredentials:");
+    for platform in [Platform::Mixcloud, Platform::Soundcloud] {
+        let key = platform.to_string().to_lowercase();
+        match config::resolve_credentials_with_source(platform) {
+            Ok((credentials, source)) => {
+                let origin = match source {
+                    CredentialSource::Env => Origin::Env,
+                    CredentialSource::File(_) => Origin::Config,
+                    CredentialSource::Compiled => Origin::BuiltIn,
+                };
+                print_row(
+                    &format!("{}.client_id", key),
+                    &shorten(&credentials.client_id),
+                    origin,
+                );
+                print_row(
+                    &format!("{}.client_secret", key),
+                    &redact(&credentials.client_secret),
+                    origin,
+                );
+            }
+            Err(e) => println!("  {:<28} ✗ {:#}", key, e),
+        }
+    }
+
+    // Keys present in settings.json came from the config, the rest are defaults
+    let file_keys: Vec<String> = match fs::read_to_string(&settings_path) {
+        Ok(contents) => serde_json::from_str::<Value>(&contents)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?
+            .as_object()
+            .map(|object| object.keys().cloned().collect())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    let settings = Settings::load()?;
+    println!("\nSettings:");
+    if let Value::Object(values) = serde_json::to_value(&settings)? {
+        for (key, value) in values {
+            let origin = if file_keys.contains(&key) {
+                Origin::Config
+            } else {
+                Origin::Default
+            };
+            let value = match value {
+                Value::String(text) if SECRET_SETTINGS.contains(&key.as_str()) => shorten(&text),
+                Value::String(text) => text,
+                other => other.to_string(),
+            };
+            print_row(&key, &value, origin);
+        }
+    }
+
+    println!("\nLimits:");
+    for (platform, overrides) in [
+        (Platform::Mixcloud, &settings.mixcloud_limits),
+        (Platform::Soundcloud, &settings.soundcloud_limits),
+    ] {
+        let key = platform.to_string().to_lowercase();
+        let limits = Limits::for_platform(platform, &settings);
+        for (name, value, overridden) in [
+            (
+                "title_max",
+                limits.title_max.to_string(),
+                overrides.title_max.is_some(),
+            ),
+            (
+                "description_max",
+                limits.description_max.to_string(),
+                overrides.description_max.is_some(),
+            ),
+            (
+                "tag_count_max",
+                limits.tag_count_max.to_string(),
+                overrides.tag_count_max.is_some(),
+            ),
+            (
+                "tag_len_max",
+                limits.tag_len_max.to_string(),
+                overrides.tag_len_max.is_some(),
+            ),
+            (
+                "title_emoji_max",
+                limits.title_emoji_max.to_string(),
+                overrides.title_emoji_max.is_some(),
+            ),
+            (
+                "urls_in_title",
+                limits.urls_in_title.to_string(),
+                overrides.urls_in_title.is_some(),
+            ),
+        ] {
+            let origin = if overridden {
+                Origin::Config
+            } else {
+                Origin::Default
+            };
+            print_row(&format!("{}.{}", key, name), &value, origin);
+        }
+    }
+
+    println!("\nNetwork:");
+    print_row(
+        "request timeout",
+        &format!("{}s", crate::platforms::REQUEST_TIMEOUT.as_secs()),
+        Origin::BuiltIn,
+    );
+    print_row(
+        "mixcloud callback port",
+        &crate::platforms::mixcloud::CALLBACK_PORT.to_string(),
+        Origin::BuiltIn,
+    );
+    print_row(
+        "soundcloud callback port",
+        &crate::platforms::soundcloud::CALLBACK_PORT.to_string(),
+        Origin::BuiltIn,
+    );
+
+    println!("\nFlags:");
+    let yes = crate::prompt::assume_yes();
+    print_row(
+        "yes",
+        &yes.to_string(),
+        if yes { Origin::Flag } else { Origin::Default },
+    );
+    Ok(())
+}
+
+fn print_row(key: &str, value: &str, origin: Origin) {
+    println!("  {:<28} {} ({})", key, value, origin);
+}
+
+/// The path, noting when the file doesn't exist
+fn file_state(path: &std::path::Path) -> String {
+    if path.exists() {
+        path.display().to_string()
+    } else {
+        format!("{} (not present)", path.display())
+    }
+}
+
+/// Only the length of a secret
+fn redact(secret: &str) -> String {
+    format!("<redacted, {} characters>", secret.chars().count())
+}
+
+/// Enough of a value to tell which one it is; short values are redacted
+fn shorten(value: &str) -> String {
+    let len = value.chars().count();
+    if len < 16 {
+        return redact(value);
+    }
+    let shown: String = value.chars().take(len.min(24) / 3).collect();
+    format!("{}… ({} characters)", shown, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(shorten("abcdefghij"), "<redacted, 10 characters>");
+        assert_eq!(shorten("0123456789abcdef"), "01234… (16 characters)");
+        assert_eq!(
+            shorten("https://hooks.slack.com/services/T000/B000/XXXX"),
+            "https://… (47 characters)"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::audio::{self, PREVIEW_CHUNK_SECS};
+use crate::cli::Platform;
+use crate::output::format_size;
+use crate::platforms::throughput::Throughput;
+
+/// Opening the file and seeking, once per preview chunk
+const CHUNK_SEEK_SECS: f64 = 0.3;
+/// MP3 encoding speed as a multiple of real time
+const MP3_ENCODE_SPEED: f64 = 40.0;
+/// Bitrate assumed for a file transcoded to MP3
+const TRANSCODE_KBPS: u64 = 320;
+
+/// Decoding speed as a multiple of real time. These are on the slow side,
+/// so the estimates err long.
+fn decode_speed(codec: Option<&str>) -> f64 {
+    match codec {
+        Some(codec) if codec.starts_with("pcm") => 400.0,
+        Some("flac" | "alac" | "wavpack") => 150.0,
+        _ => 80.0,
+    }
+}
+
+/// Seconds to generate previews of `durations` from a file in `codec`
+fn preview_secs(durations: &[u64], codec: Option<&str>) -> f64 {
+    let audio_secs: u64 = durations.iter().sum();
+    let chunks = audio_secs / PREVIEW_CHUNK_SECS;
+    audio_secs as f64 / decode_speed(codec) + chunks as f64 * CHUNK_SEEK_SECS
+}
+
+/// "~45s", "~3m 10s" or "~1h 05m"
+fn format_eta(secs: f64) -> String {
+    let secs = secs.max(1.0).round() as u64;
+    if secs >= 3600 {
+        format!("~{}h {:02}m", secs / 3600, secs / 60 % 60)
+    } else if secs >= 60 {
+        format!("~{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("~{}s", secs)
+    }
+}
+
+/// Print how long previews, normalizing, any transcode and the upload
+/// itself are likely to take for `file`. Upload times come from recent
+/// measured throughput, per platform when `platforms` are given.
+pub fn print_estimate(file: &Path, platforms: &[Platform]) -> Result<()> {
+    let size = std::fs::metadata(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?
+        .len();
+    let metadata = audio::probe_metadata(file)?;
+    let codec = metadata.codec.as_deref();
+
+    println!("Estimate for {} (approximate)", file.display());
+    print_row("File size", &format_size(size));
+
+    match audio::audio_info(file) {
+        Ok(info) => {
+            let duration = info.duration.as_secs_f64();
+            let mut details = Vec::new();
+            if let Some(rate) = info.sample_rate {
+                details.push(format!("{} Hz", rate));
+            }
+            if let Some(channels) = info.channels {
+                details.push(format!("{} channels", channels));
+            }
+            let label = crate::title::format_duration_label(duration);
+            if details.is_empty() {
+                print_row("Duration", &label);
+            } else {
+                print_row("Duration", &format!("{} ({})", label, details.join(", ")));
+            }
+            let durations = audio::DEFAULT_PREVIEW_DURATIONS;
+            let labels: Vec<String> = durations.iter().map(|d| format!("{}s", d)).collect();
+            print_row(
+                "Previews",
+                &format!(
+                    "{} ({} snippets with --generate-previews)",
+                    format_eta(preview_secs(&durations, codec)),
+                    labels.join(", ")
+                ),
+            );
+            // Two passes over the whole file: find the peak, then write
+            print_row(
+                "Normalizing",
+                &format!(
+                    "{} (--normalize-peak, writes a {} WAV)",
+                    format_eta(2.0 * duration / decode_speed(codec)),
+                    format_size(audio::estimate_wav_bytes(&metadata, duration, true))
+                ),
+            );
+            let mime = audio::audio_mime(file).ok();
+            for &platform in platforms {
+                if let Some(mime) = mime
+                    && !crate::platforms::accepted_audio(platform).contains(&mime)
+                {
+                    print_row(
+                        &format!("Transcode for {}", platform),
+                        &format!(
+                            "{} ({} may not take {}; a {} kbps MP3 would be {})",
+                            format_eta(
+                                duration / decode_speed(codec) + duration / MP3_ENCODE_SPEED
+                            ),
+                            platform,
+                            mime,
+                            TRANSCODE_KBPS,
+                            format_size((duration * TRANSCODE_KBPS as f64 * 1000.0 / 8.0) as u64)
+                        ),
+                    );
+                }
+            }
+        }
+        Err(e) => {
+            print_row("Duration", &format!("unknown ({:#})", e));
+            print_row("Previews", "unknown (duration not known)");
+            print_row("Normalizing", "unknown (duration not known)");
+        }
+    }
+
+    let throughput = Throughput::load().unwrap_or_default();
+    let overall = throughput.rate();
+    if platforms.is_empty() {
+        print_row("Upload", &upload_eta(size, overall, "recent uploads"));
+    }
+    for &platform in platforms {
+        let value = match throughput.platform_rate(platform) {
+            Some(rate) => upload_eta(size, Some(rate), &format!("recent {} uploads", platform)),
+            None => upload_eta(size, overall, "recent uploads to any platform"),
+        };
+        print_row(&format!("Upload to {}", platform), &value);
+    }
+    Ok(())
+}
+
+fn upload_eta(size: u64, rate: Option<f64>, source: &str) -> String {
+    match rate {
+        Some(rate) => format!(
+            "{} at {}/s ({})",
+            format_eta(size as f64 / rate),
+            format_size(rate as u64),
+            source
+        ),
+        None => "unknown (no upload measured yet)".to_string(),
+    }
+}
+
+fn print_row(label: &str, value: &str) {
+    println!("  {:<24} {}", label, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_secs() {
+        // 18 chunks of MP3
+        let secs = preview_secs(&[30, 60, 90], Some("mp3"));
+        assert!((secs - (180.0 / 80.0 + 18.0 * CHUNK_SEEK_SECS)).abs() < 1e-9);
+        assert!(preview_secs(&[30], Some("pcm_s16le")) < preview_secs(&[30], Some("flac")));
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(0.2), "~1s");
+        assert_eq!(format_eta(45.4), "~45s");
+        assert_eq!(format_eta(190.0), "~3m 10s");
+        assert_eq!(format_eta(3900.0), "~1h 05m");
+    }
+}
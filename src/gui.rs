@@ -1,26 +1,44 @@
 use anyhow::Result;
-use slint::SharedString;
+use slint::{Model, ModelRc, SharedString, VecModel};
+use std::cell::RefCell;
 use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
 use std::thread;
 
+use crate::audio::PreviewProgress;
+use crate::cli::{ExistingOutput, FadeCurve, Platform, TagDedupe};
+use crate::config::{Settings, TokenStorage};
+use crate::limits::Limits;
+use crate::platforms::AuthCancel;
+use crate::platforms::progress::{ProgressFn, UploadStats};
+
 slint::include_modules!();
 
 pub fn run_gui() -> Result<()> {
     let ui = MainWindow::new()?;
+    ui.set_mp3_available(cfg!(feature = "mp3"));
 
     // Check existing SoundCloud auth status on startup
     {
-        let storage = crate::config::TokenStorage::load().unwrap_or(crate::config::TokenStorage {
-            mixcloud: None,
-            soundcloud: None,
-        });
-        if let Some(ref token_info) = storage.soundcloud
+        let storage = TokenStorage::load().unwrap_or_default();
+        if let Some(token_info) = storage.token(Platform::Soundcloud, None)
             && !token_info.is_expired()
         {
             ui.set_soundcloud_connected(true);
         }
     }
 
+    load_accounts(&ui)?;
+
+    // Re-check the account whenever a dropdown selection changes
+    let ui_weak = ui.as_weak();
+    ui.on_account_changed(move || {
+        let ui = ui_weak.unwrap();
+        resolve_account(&ui, Platform::Mixcloud);
+        resolve_account(&ui, Platform::Soundcloud);
+    });
+
     // Handle file selection
     let ui_weak = ui.as_weak();
     ui.on_select_file(move || {
@@ -31,11 +49,46 @@ pub fn run_gui() -> Result<()> {
             .pick_file()
         {
             ui.set_file_path(SharedString::from(path.display().to_string()));
+
+            // Probe metadata off the UI thread, clearing anything stale first
+            ui.set_audio_info(SharedString::from("Reading file details..."));
+            ui.set_audio_warning(SharedString::from(""));
+
+            let ui_handle = ui.as_weak();
+            thread::spawn(move || {
+                let result = crate::audio::probe_metadata(&path);
+
+                slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_handle.upgrade() {
+                        // Ignore results for a file that is no longer selected
+                        if ui.get_file_path() != path.display().to_string().as_str() {
+                            return;
+                        }
+                        match result {
+                            Ok(metadata) => {
+                                ui.set_audio_info(SharedString::from(format_audio_info(&metadata)));
+                            }
+                            Err(e) => {
+                                ui.set_audio_info(SharedString::from(""));
+                                ui.set_audio_warning(SharedString::from(format!(
+                                    "⚠️ Could not read audio details: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                })
+                .ok();
+            });
         }
     });
 
+    // Decoded, downscaled copy of the artwork for redrawing the crop preview
+    let cover_thumbnail: Rc<RefCell<Option<image::DynamicImage>>> = Rc::default();
+
     // Handle image selection
     let ui_weak = ui.as_weak();
+    let thumbnail = cover_thumbnail.clone();
     ui.on_select_image(move || {
         let ui = ui_weak.unwrap();
 
@@ -44,6 +97,56 @@ pub fn run_gui() -> Result<()> {
             .pick_file()
         {
             ui.set_image_path(SharedString::from(path.display().to_string()));
+            ui.set_crop_offset(0.5);
+
+            let decoded = image::open(&path).ok();
+            let (width, height) = decoded
+                .as_ref()
+                .map_or((0, 0), |img| (img.width(), img.height()));
+            ui.set_cover_croppable(width != height);
+            ui.set_crop_vertical(height > width);
+            *thumbnail.borrow_mut() = decoded.map(|img| {
+                // Shorter side just big enough for the preview
+                let scale = CROP_PREVIEW_SIZE as f32 / width.min(height).max(1) as f32;
+                if scale < 1.0 {
+                    img.thumbnail(
+                        (width as f32 * scale).ceil() as u32,
+                        (height as f32 * scale).ceil() as u32,
+                    )
+                } else {
+                    img
+                }
+            });
+
+            let preview = thumbnail
+                .borrow()
+                .as_ref()
+                .map(|img| crop_preview(img, 0.5))
+                .map_or_else(|| slint::Image::load_from_path(&path), Ok);
+            match preview {
+                Ok(image) => {
+                    ui.set_cover_image(image);
+                    ui.set_has_cover(true);
+                    ui.set_cover_warning(SharedString::from(""));
+                }
+                Err(_) => {
+                    ui.set_cover_image(slint::Image::default());
+                    ui.set_has_cover(false);
+                    ui.set_cover_warning(SharedString::from(
+                        "⚠️ Could not read this image, check the file before uploading",
+                    ));
+                }
+            }
+        }
+    });
+
+    // Redraw the thumbnail as the crop position changes
+    let ui_weak = ui.as_weak();
+    let thumbnail = cover_thumbnail.clone();
+    ui.on_crop_offset_changed(move |offset| {
+        let ui = ui_weak.unwrap();
+        if let Some(img) = thumbnail.borrow().as_ref() {
+            ui.set_cover_image(crop_preview(img, offset));
         }
     });
 
@@ -57,16 +160,25 @@ pub fn run_gui() -> Result<()> {
         let title = ui.get_title_text().to_string();
         let description = ui.get_description_text().to_string();
         let image_path = ui.get_image_path().to_string();
+        let crop_offset = ui.get_crop_offset();
         let tags = ui.get_tags_text().to_string();
         let mixcloud_enabled = ui.get_mixcloud_enabled();
         let soundcloud_enabled = ui.get_soundcloud_enabled();
+        let mixcloud_account = selected_account(&ui, Platform::Mixcloud);
+        let soundcloud_account = selected_account(&ui, Platform::Soundcloud);
         let schedule_enabled = ui.get_schedule_enabled();
         let schedule_date = ui.get_schedule_date().to_string();
         let schedule_time = ui.get_schedule_time().to_string();
-        let generate_previews = ui.get_generate_previews();
+        let preview_format = ui.get_generate_previews().then(|| {
+            if cfg!(feature = "mp3") && ui.get_previews_mp3() {
+                crate::cli::PreviewFormat::Mp3
+            } else {
+                crate::cli::PreviewFormat::Wav
+            }
+        });
 
         // Validate
-        if file_path.is_empty() || title.is_empty() {
+        if file_path.is_empty() || title.trim().is_empty() {
             ui.set_status_message(SharedString::from("Error: File and title are required"));
             return;
         }
@@ -76,27 +188,85 @@ pub fn run_gui() -> Result<()> {
             return;
         }
 
+        if (mixcloud_enabled && !ui.get_mixcloud_account_valid())
+            || (soundcloud_enabled && !ui.get_soundcloud_account_valid())
+        {
+            ui.set_status_message(SharedString::from(
+                "Error: The selected account needs to be re-authorized",
+            ));
+            return;
+        }
+
         // Set uploading state
         ui.set_is_uploading(true);
         ui.set_status_message(SharedString::from("Uploading..."));
 
+        let mut platforms = Vec::new();
+        if mixcloud_enabled {
+            platforms.push(Platform::Mixcloud);
+        }
+        if soundcloud_enabled {
+            platforms.push(Platform::Soundcloud);
+        }
+
         // Spawn upload thread
         let ui_handle = ui.as_weak();
+        let progress_handle = ui.as_weak();
+        let preview_handle = ui.as_weak();
         thread::spawn(move || {
+            let on_progress = move |platform: Platform| -> ProgressFn {
+                let ui_handle = progress_handle.clone();
+                Arc::new(move |stats: &UploadStats| {
+                    let message = format!("Uploading to {}: {}", platform, stats.status_line());
+                    let ui_handle = ui_handle.clone();
+                    slint::invoke_from_event_loop(move || {
+                        if let Some(ui) = ui_handle.upgrade() {
+                            ui.set_status_message(SharedString::from(message));
+                        }
+                    })
+                    .ok();
+                })
+            };
+
+            let on_preview_progress = move |progress: &PreviewProgress| {
+                let message = format!(
+                    "Generating preview {}/{} ({}s): {:.0}%",
+                    progress.snippet,
+                    progress.snippets,
+                    progress.duration,
+                    progress.snippet_fraction * 100.0
+                );
+                let ui_handle = preview_handle.clone();
+                slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_handle.upgrade() {
+                        ui.set_status_message(SharedString::from(message));
+                    }
+                })
+                .ok();
+            };
+
             let result = perform_upload(
                 file_path,
-                title,
+                title.clone(),
                 description,
                 image_path,
+                crop_offset,
                 tags,
                 mixcloud_enabled,
                 soundcloud_enabled,
+                mixcloud_account,
+                soundcloud_account,
                 schedule_enabled,
                 schedule_date,
                 schedule_time,
-                generate_previews,
+                preview_format,
+                &on_preview_progress,
+                &on_progress,
             );
 
+            let error = result.as_ref().err().map(|e| e.to_string());
+            crate::notification::upload_finished(&title, &platforms, error.as_deref());
+
             // Update UI with result
             slint::invoke_from_event_loop(move || {
                 if let Some(ui) = ui_handle.upgrade() {
@@ -113,6 +283,12 @@ pub fn run_gui() -> Result<()> {
                             ui.set_description_text(SharedString::from(""));
                             ui.set_image_path(SharedString::from(""));
                             ui.set_tags_text(SharedString::from(""));
+                            ui.set_audio_info(SharedString::from(""));
+                            ui.set_audio_warning(SharedString::from(""));
+                            ui.set_cover_image(slint::Image::default());
+                            ui.set_has_cover(false);
+                            ui.set_cover_croppable(false);
+                            ui.set_cover_warning(SharedString::from(""));
                         }
                         Err(e) => {
                             ui.set_status_message(SharedString::from(format!("Error: {}", e)));
@@ -126,10 +302,17 @@ pub fn run_gui() -> Result<()> {
         });
     });
 
+    // Pending SoundCloud authorization and the status message to restore if
+    // it's cancelled
+    let pending_auth: Rc<RefCell<Option<(AuthCancel, SharedString)>>> = Rc::default();
+
     // Handle SoundCloud connect
     let ui_weak = ui.as_weak();
+    let pending = pending_auth.clone();
     ui.on_connect_soundcloud(move || {
         let ui = ui_weak.unwrap();
+        let cancel = AuthCancel::default();
+        *pending.borrow_mut() = Some((cancel.clone(), ui.get_status_message()));
         ui.set_soundcloud_connecting(true);
         ui.set_status_message(SharedString::from(
             "Opening browser for SoundCloud authorization...",
@@ -139,20 +322,36 @@ pub fn run_gui() -> Result<()> {
 
         let ui_handle = ui.as_weak();
         thread::spawn(move || {
-            let result = crate::platforms::soundcloud::SoundcloudClient::authorize();
+            let result = crate::platforms::soundcloud::SoundcloudClient::authorize(None, &cancel);
 
             slint::invoke_from_event_loop(move || {
+                // A cancelled attempt already reset the UI
+                if cancel.is_cancelled() {
+                    return;
+                }
                 if let Some(ui) = ui_handle.upgrade() {
                     ui.set_soundcloud_connecting(false);
                     match result {
                         Ok(()) => {
                             ui.set_soundcloud_connected(true);
                             ui.set_soundcloud_enabled(true);
-                            ui.set_status_message(SharedString::from(
-                                "SoundCloud connected successfully!",
-                            ));
-                            ui.set_is_success(true);
-                            ui.set_is_error(false);
+                            match load_accounts(&ui) {
+                                Ok(()) => {
+                                    ui.set_status_message(SharedString::from(
+                                        "SoundCloud connected successfully!",
+                                    ));
+                                    ui.set_is_success(true);
+                                    ui.set_is_error(false);
+                                }
+                                Err(e) => {
+                                    ui.set_status_message(SharedString::from(format!(
+                                        "SoundCloud connected, but the accounts couldn't be listed: {:#}",
+                                        e
+                                    )));
+                                    ui.set_is_success(false);
+                                    ui.set_is_error(true);
+                                }
+                            }
                         }
                         Err(e) => {
                             ui.set_status_message(SharedString::from(format!(
@@ -169,6 +368,17 @@ pub fn run_gui() -> Result<()> {
         });
     });
 
+    // Abandon a pending SoundCloud authorization and free the callback port
+    let ui_weak = ui.as_weak();
+    ui.on_cancel_soundcloud_auth(move || {
+        let ui = ui_weak.unwrap();
+        if let Some((cancel, previous_status)) = pending_auth.borrow_mut().take() {
+            cancel.cancel();
+            ui.set_status_message(previous_status);
+        }
+        ui.set_soundcloud_connecting(false);
+    });
+
     // Check for updates in background
     {
         let ui_handle = ui.as_weak();
@@ -202,44 +412,227 @@ pub fn run_gui() -> Result<()> {
     Ok(())
 }
 
+/// Fill the account dropdowns from token storage, preselecting the last-used
+/// account for each platform
+fn load_accounts(ui: &MainWindow) -> Result<()> {
+    let storage = TokenStorage::load().unwrap_or_default();
+    let settings = Settings::load()?;
+
+    for platform in [Platform::Mixcloud, Platform::Soundcloud] {
+        let names = storage.account_names(platform);
+        let last_used = match platform {
+            Platform::Mixcloud => settings.last_mixcloud_account.as_deref(),
+            Platform::Soundcloud => settings.last_soundcloud_account.as_deref(),
+        };
+        let index = last_used
+            .and_then(|last| names.iter().position(|name| name == last))
+            .unwrap_or(0) as i32;
+
+        let model: Vec<SharedString> = names
+            .iter()
+            .map(|name| SharedString::from(name.as_str()))
+            .collect();
+        let model = ModelRc::new(VecModel::from(model));
+
+        match platform {
+            Platform::Mixcloud => {
+                ui.set_mixcloud_accounts(model);
+                ui.set_mixcloud_account_index(index);
+            }
+            Platform::Soundcloud => {
+                ui.set_soundcloud_accounts(model);
+                ui.set_soundcloud_account_index(index);
+            }
+        }
+
+        resolve_account(ui, platform);
+    }
+    Ok(())
+}
+
+/// Alias currently picked in a platform's dropdown
+fn selected_account(ui: &MainWindow, platform: Platform) -> Option<String> {
+    let (accounts, index) = match platform {
+        Platform::Mixcloud => (ui.get_mixcloud_accounts(), ui.get_mixcloud_account_index()),
+        Platform::Soundcloud => (
+            ui.get_soundcloud_accounts(),
+            ui.get_soundcloud_account_index(),
+        ),
+    };
+
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| accounts.row_data(index))
+        .map(|name| name.to_string())
+}
+
+/// Check the selected account's token and look up its username in the background
+fn resolve_account(ui: &MainWindow, platform: Platform) {
+    let Some(account) = selected_account(ui, platform) else {
+        set_account_state(ui, platform, "", true);
+        return;
+    };
+
+    // A token is usable if it is still fresh or can be refreshed
+    let storage = TokenStorage::load().unwrap_or_default();
+    let valid = storage
+        .token(platform, Some(&account))
+        .is_some_and(|token| !token.is_expired() || token.refresh_token.is_some());
+
+    if !valid {
+        set_account_state(ui, platform, "⚠️ Token expired, reconnect", false);
+        return;
+    }
+
+    set_account_state(ui, platform, "Checking...", true);
+
+    let ui_handle = ui.as_weak();
+    thread::spawn(move || {
+        let username = crate::platforms::account_profile(platform, Some(&account))
+            .map(|profile| profile.username);
+
+        slint::invoke_from_event_loop(move || {
+            if let Some(ui) = ui_handle.upgrade() {
+                // Ignore lookups for an account that is no longer selected
+                if selected_account(&ui, platform).as_deref() != Some(account.as_str()) {
+                    return;
+                }
+                match username {
+                    Ok(username) => {
+                        set_account_state(&ui, platform, &format!("@{}", username), true)
+                    }
+                    Err(_) => set_account_state(&ui, platform, "⚠️ Account unavailable", false),
+                }
+            }
+        })
+        .ok();
+    });
+}
+
+/// Shorter side of the decoded artwork kept for the crop preview
+const CROP_PREVIEW_SIZE: u32 = 240;
+
+/// Square crop of the preview thumbnail at `offset` along the longer side
+fn crop_preview(img: &image::DynamicImage, offset: f32) -> slint::Image {
+    let (x, y, side) = crate::cover::square_crop(img.width(), img.height(), offset, offset);
+    let rgb = img.crop_imm(x, y, side, side).to_rgb8();
+    slint::Image::from_rgb8(slint::SharedPixelBuffer::clone_from_slice(
+        rgb.as_raw(),
+        side,
+        side,
+    ))
+}
+
+fn set_account_state(ui: &MainWindow, platform: Platform, user: &str, valid: bool) {
+    match platform {
+        Platform::Mixcloud => {
+            ui.set_mixcloud_account_user(SharedString::from(user));
+            ui.set_mixcloud_account_valid(valid);
+        }
+        Platform::Soundcloud => {
+            ui.set_soundcloud_account_user(SharedString::from(user));
+            ui.set_soundcloud_account_valid(valid);
+        }
+    }
+}
+
+/// Summarize probed audio details for the metadata panel
+fn format_audio_info(metadata: &crate::audio::AudioMetadata) -> String {
+    let mut details = Vec::new();
+
+    if let Some(duration) = metadata.duration_secs {
+        let total = duration.round() as u64;
+        let approx = metadata
+            .duration_source
+            .is_some_and(|source| source.is_estimate());
+        details.push(format!(
+            "{}{}:{:02}:{:02}",
+            if approx { "~" } else { "" },
+            total / 3600,
+            (total % 3600) / 60,
+            total % 60
+        ));
+    }
+    if let Some(bitrate) = metadata.bitrate_kbps {
+        details.push(format!("{} kbps", bitrate));
+    }
+    if let Some(sample_rate) = metadata.sample_rate {
+        details.push(format!("{:.1} kHz", sample_rate as f64 / 1000.0));
+    }
+    if let Some(channels) = metadata.channels {
+        details.push(format!("{} ch", channels));
+    }
+
+    let mut lines = vec![details.join(" · ")];
+    for (key, value) in metadata.tags.iter().take(6) {
+        lines.push(format!("{}: {}", key, value));
+    }
+
+    lines.join("\n")
+}
+
 #[allow(clippy::too_many_arguments)]
 fn perform_upload(
     file_path: String,
     title: String,
     description: String,
     image_path: String,
+    crop_offset: f32,
     tags: String,
     mixcloud: bool,
     soundcloud: bool,
+    mixcloud_account: Option<String>,
+    soundcloud_account: Option<String>,
     schedule_enabled: bool,
     schedule_date: String,
     schedule_time: String,
-    generate_previews: bool,
+    preview_format: Option<crate::cli::PreviewFormat>,
+    on_preview_progress: &dyn Fn(&PreviewProgress),
+    on_progress: &dyn Fn(Platform) -> ProgressFn,
 ) -> Result<String> {
-    use crate::platforms::{mixcloud, soundcloud as sc};
-    use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+    use crate::platforms::{self, UploadRequest};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
     let file = PathBuf::from(&file_path);
+    let settings = Settings::load()?;
 
     // Generate preview snippets if requested
-    if generate_previews {
-        match crate::audio::create_preview_snippets(&file) {
-            Ok(snippets) => {
-                println!("✓ Generated {} preview snippets:", snippets.len());
-                for snippet in &snippets {
-                    println!("  - {}", snippet.display());
-                }
-            }
-            Err(e) => {
-                eprintln!("⚠ Warning: Failed to generate previews: {}", e);
-                // Continue with upload even if preview generation fails
-            }
-        }
+    if let Some(format) = preview_format {
+        let options = crate::previews::PreviewOptions {
+            durations: crate::audio::DEFAULT_PREVIEW_DURATIONS.to_vec(),
+            format,
+            sample_format: crate::cli::WavSampleFormat::Int16,
+            output_dir: None,
+            placement: crate::audio::PreviewPlacement::default(),
+            fade_curve: FadeCurve::default(),
+            compress_silence: None,
+            existing: ExistingOutput::Overwrite,
+            min_free_space: 0,
+        };
+        crate::previews::report(crate::previews::generate(
+            &file,
+            &options,
+            on_preview_progress,
+        ));
     }
-    let image = if image_path.is_empty() {
-        None
+    // Non-square artwork is cropped where the preview showed it
+    let temp_dir = crate::temp::TempDir::new(None, false)?;
+    let images = if image_path.is_empty() {
+        Vec::new()
     } else {
-        Some(PathBuf::from(&image_path))
+        let original = PathBuf::from(&image_path);
+        let cropped = crate::cover::crop_image(
+            &original,
+            crop_offset,
+            crop_offset,
+            crate::cli::CoverFormat::Auto,
+            temp_dir.path(),
+        )
+        .unwrap_or_else(|e| {
+            log::warn!("Uploading artwork uncropped: {:#}", e);
+            original
+        });
+        vec![cropped]
     };
 
     let desc = if description.is_empty() {
@@ -251,12 +644,7 @@ fn perform_upload(
     let tag_list = if tags.is_empty() {
         None
     } else {
-        Some(
-            tags.split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect(),
-        )
+        Some(crate::tags::parse_tags(&tags))
     };
 
     // Parse and convert publish date to UTC if scheduled
@@ -274,15 +662,7 @@ fn perform_upload(
         let naive_datetime = NaiveDateTime::new(date, time);
 
         // Convert local time to UTC
-        let local_datetime = Local
-            .from_local_datetime(&naive_datetime)
-            .single()
-            .ok_or_else(|| anyhow::anyhow!("Ambiguous local time"))?;
-
-        let utc_datetime = local_datetime.with_timezone(&chrono::Utc);
-
-        // Format as required: YYYY-MM-DDTHH:MM:SSZ
-        Some(utc_datetime.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        Some(crate::schedule::local_to_utc_publish_date(&naive_datetime)?)
     } else {
         None
     };
@@ -291,24 +671,108 @@ fn perform_upload(
 
     // Upload to Mixcloud
     if mixcloud {
-        let mut client = mixcloud::MixcloudClient::new()?;
-        let response = client.upload(
-            &file,
-            &title,
-            desc,
-            image.as_deref(),
-            tag_list.clone(),
-            publish_date.as_deref(),
+        let mixcloud_limits = Limits::for_platform(Platform::Mixcloud, &settings);
+        let mixcloud_tags = tag_list
+            .as_deref()
+            .map(|t| crate::tags::validate_tags(Platform::Mixcloud, &mixcloud_limits, t, false))
+            .transpose()?
+            .map(|t| crate::tags::dedupe_tags(t, TagDedupe::Exact));
+        let mixcloud_title =
+            crate::title::validate_title(Platform::Mixcloud, &mixcloud_limits, &title)?;
+        let mixcloud_desc = desc
+            .map(|d| {
+                crate::description::fit_description(Platform::Mixcloud, &mixcloud_limits, d, false)
+            })
+            .transpose()?;
+        let outcome = platforms::upload(
+            Platform::Mixcloud,
+            mixcloud_account.as_deref(),
+            &UploadRequest {
+                file_path: &file,
+                title: &mixcloud_title,
+                description: mixcloud_desc.as_deref(),
+                image_paths: &images,
+                tags: mixcloud_tags,
+                publish_date: publish_date.as_deref(),
+                isrc: None,
+                language: None,
+                remix_of: None,
+                assume_pro: false,
+                unlisted: false,
+                deadline: None,
+                progress: Some(on_progress(Platform::Mixcloud)),
+            },
         )?;
-        results.push(format!("Mixcloud: {}", response.result.message));
+        results.push(outcome);
     }
 
     // Upload to SoundCloud
     if soundcloud {
-        let mut client = sc::SoundcloudClient::new()?;
-        let response = client.upload(&file, &title, desc, image.as_deref(), tag_list)?;
-        results.push(format!("SoundCloud: Track #{}", response.id));
+        let soundcloud_limits = Limits::for_platform(Platform::Soundcloud, &settings);
+        let soundcloud_tags = tag_list
+            .as_deref()
+            .map(|t| crate::tags::validate_tags(Platform::Soundcloud, &soundcloud_limits, t, false))
+            .transpose()?
+            .map(|t| crate::tags::dedupe_tags(t, TagDedupe::Exact));
+        let soundcloud_title =
+            crate::title::validate_title(Platform::Soundcloud, &soundcloud_limits, &title)?;
+        let soundcloud_desc = desc
+            .map(|d| {
+                crate::description::fit_description(
+                    Platform::Soundcloud,
+                    &soundcloud_limits,
+                    d,
+                    false,
+                )
+            })
+            .transpose()?;
+        let outcome = platforms::upload(
+            Platform::Soundcloud,
+            soundcloud_account.as_deref(),
+            &UploadRequest {
+                file_path: &file,
+                title: &soundcloud_title,
+                description: soundcloud_desc.as_deref(),
+                image_paths: &images,
+                tags: soundcloud_tags,
+                publish_date: None,
+                isrc: None,
+                language: None,
+                remix_of: None,
+                assume_pro: false,
+                unlisted: false,
+                deadline: None,
+                progress: Some(on_progress(Platform::Soundcloud)),
+            },
+        )?;
+        results.push(outcome);
+    }
+
+    // Remember the accounts for next time, without replacing a settings
+    // file that doesn't parse
+    let saved = Settings::load().and_then(|mut settings| {
+        if mixcloud {
+            settings.last_mixcloud_account = mixcloud_account;
+        }
+        if soundcloud {
+            settings.last_soundcloud_account = soundcloud_account;
+        }
+        settings.save()
+    });
+    if let Err(e) = saved {
+        eprintln!("⚠ Warning: Failed to save last-used accounts: {}", e);
     }
 
-    Ok(results.join(" | "))
+    let summary: Vec<String> = results
+        .iter()
+        .map(|outcome| {
+            let scheduled = if outcome.scheduled {
+                " (scheduled)"
+            } else {
+                ""
+            };
+            format!("{}: {}{}", outcome.platform, outcome.link(), scheduled)
+        })
+        .collect();
+    Ok(summary.join(" | "))
 }
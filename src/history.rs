@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Platform;
+use crate::config::config_dir;
+
+/// One finished upload, as stored in history.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Absolute path of the uploaded file
+    pub file: PathBuf,
+    pub platform: String,
+    pub title: String,
+    pub url: String,
+    pub uploaded_at: DateTime<Utc>,
+    /// Modification time of the file when it was uploaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+    /// SHA-256 of the file contents, recorded when --compare-hash was used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Upload history: one JSON object per line, appended after every
+/// successful upload so a crash never loses earlier entries
+pub fn history_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("history.jsonl"))
+}
+
+/// Remember a successful upload
+pub fn record(
+    file: &Path,
+    platform: Platform,
+    title: &str,
+    url: &str,
+    sha256: Option<String>,
+) -> Result<()> {
+    let entry = HistoryEntry {
+        file: std::path::absolute(file).unwrap_or_else(|_| file.to_path_buf()),
+        platform: platform.to_string(),
+        title: title.to_string(),
+        url: url.to_string(),
+        uploaded_at: Utc::now(),
+        modified: modified_at(file).ok(),
+        sha256,
+    };
+
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create history directory")?;
+    }
+    let mut line = serde_json::to_string(&entry).context("Failed to serialize history entry")?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every recorded upload, oldest first. Unreadable lines are skipped.
+pub fn load() -> Result<Vec<HistoryEntry>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::warn!("Skipping unreadable history line: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Whether `file` was already uploaded to `platform`
+pub fn contains(entries: &[HistoryEntry], file: &Path, platform: Platform) -> bool {
+    last_upload(entries, file, platform).is_some()
+}
+
+/// The most recent upload of `file` to `platform`
+pub fn last_upload<'a>(
+    entries: &'a [HistoryEntry],
+    file: &Path,
+    platform: Platform,
+) -> Option<&'a HistoryEntry> {
+    let file = std::path::absolute(file).unwrap_or_else(|_| file.to_path_buf());
+    let platform = platform.to_string();
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.file == file && entry.platform == platform)
+}
+
+/// Modification time of `file`
+pub fn modified_at(file: &Path) -> Result<DateTime<Utc>> {
+    let modified = fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .with_context(|| format!("Failed to read modification time of {}", file.display()))?;
+    Ok(modified.into())
+}
+
+/// Hex SHA-256 of the contents of `file`
+pub fn file_sha256(file: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    File::open(file)
+        .and_then(|mut source| std::io::copy(&mut source, &mut hasher))
+        .with_context(|| format!("Failed to hash {}", file.display()))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Why the file is the same as it was at `entry`'s upload, or None if it
+/// changed. Entries from before modification times were recorded fall back
+/// to the upload time.
+pub fn unchanged_since(
+    entry: &HistoryEntry,
+    modified: DateTime<Utc>,
+    sha256: Option<&str>,
+) -> Option<&'static str> {
+    if modified <= entry.modified.unwrap_or(entry.uploaded_at) {
+        Some("not modified since")
+    } else if sha256.is_some() && sha256 == entry.sha256.as_deref() {
+        Some("contents unchanged since")
+    } else {
+        None
+    }
+}
+
+/// Result of trimming the history file
+pub struct Pruned {
+    pub removed: usize,
+    pub bytes: u64,
+}
+
+/// Drop entries uploaded before `cutoff`, rewriting the file only if `apply`
+/// is set. Lines that can't be read are kept rather than lost.
+pub fn prune(cutoff: DateTime<Utc>, apply: bool) -> Result<Pruned> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Pruned {
+            removed: 0,
+            bytes: 0,
+        });
+    }
+
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let (kept, removed) = prune_lines(&contents, cutoff);
+    let bytes = (contents.len() - kept.len()) as u64;
+
+    if apply && removed > 0 {
+        fs::write(&path, kept).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(Pruned { removed, bytes })
+}
+
+/// The lines to keep and how many entries were dropped
+fn prune_lines(contents: &str, cutoff: DateTime<Utc>) -> (String, usize) {
+    let mut kept = String::with_capacity(contents.len());
+    let mut removed = 0;
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<HistoryEntry>(line) {
+            Ok(entry) if entry.uploaded_at < cutoff => removed += 1,
+            _ => {
+                kept.push_str(line);
+                kept.push('\n');
+            }
+        }
+    }
+    (kept, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_lines_keeps_recent_and_unreadable() {
+        let entry = |date: &str| {
+            serde_json::to_string(&HistoryEntry {
+                file: PathBuf::from("/mixes/set.mp3"),
+                platform: "Mixcloud".to_string(),
+                title: "Set".to_string(),
+                url: "https://example.com/set".to_string(),
+                uploaded_at: date.parse().unwrap(),
+                modified: None,
+                sha256: None,
+            })
+            .unwrap()
+        };
+        let old = entry("2024-01-01T00:00:00Z");
+        let recent = entry("2026-01-01T00:00:00Z");
+        let contents = format!("{}\nnot json\n{}\n", old, recent);
+
+        let (kept, removed) = prune_lines(&contents, "2025-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(removed, 1);
+        assert_eq!(kept, format!("not json\n{}\n", recent));
+    }
+
+    #[test]
+    fn test_unchanged_since() {
+        let at = |date: &str| date.parse::<DateTime<Utc>>().unwrap();
+        let entry = HistoryEntry {
+            file: PathBuf::from("/mixes/set.mp3"),
+            platform: "Mixcloud".to_string(),
+            title: "Set".to_string(),
+            url: "https://example.com/set".to_string(),
+            uploaded_at: at("2026-01-02T00:00:00Z"),
+            modified: Some(at("2026-01-01T00:00:00Z")),
+            sha256: Some("abc".to_string()),
+        };
+
+        let same = at("2026-01-01T00:00:00Z");
+        let touched = at("2026-01-03T00:00:00Z");
+        assert_eq!(
+            unchanged_since(&entry, same, None),
+            Some("not modified since")
+        );
+        assert_eq!(unchanged_since(&entry, touched, None), None);
+        assert_eq!(
+            unchanged_since(&entry, touched, Some("abc")),
+            Some("contents unchanged since")
+        );
+        assert_eq!(unchanged_since(&entry, touched, Some("def")), None);
+
+        // Older entries only know when the upload happened
+        let old = HistoryEntry {
+            modified: None,
+            ..entry
+        };
+        assert!(unchanged_since(&old, at("2026-01-01T12:00:00Z"), None).is_some());
+        assert!(unchanged_since(&old, touched, None).is_none());
+    }
+}
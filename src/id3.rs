@@ -0,0 +1,130 @@
+/// Split an existing ID3v2 tag off the front of an MP3.
+/// Returns the tag version, the existing frames minus any with an id in
+/// `replaced`, and the offset where the audio starts. Tags we can't safely
+/// copy are dropped.
+pub fn split_tag(data: &[u8], replaced: &[&[u8; 4]]) -> (u8, Vec<u8>, usize) {
+    if data.len() < 10 || &data[0..3] != b"ID3" {
+        return (3, Vec::new(), 0);
+    }
+
+    let version = data[3];
+    let flags = data[5];
+    let tag_size = read_synchsafe(&data[6..10]) as usize;
+    let audio_start = tag_len(data).min(data.len());
+
+    // Unsynchronised tags, extended headers and unknown versions aren't worth
+    // rewriting by hand, so start a fresh v2.3 tag instead
+    if !(version == 3 || version == 4) || flags & 0xC0 != 0 {
+        log::warn!("Replacing existing ID3 tag that could not be preserved");
+        return (3, Vec::new(), audio_start);
+    }
+
+    let body = &data[10..audio_start.min(10 + tag_size)];
+    let mut kept = Vec::new();
+    let mut pos = 0;
+
+    while pos + 10 <= body.len() && body[pos] != 0 {
+        let id = &body[pos..pos + 4];
+        let size = if version == 4 {
+            read_synchsafe(&body[pos + 4..pos + 8])
+        } else {
+            u32::from_be_bytes([body[pos + 4], body[pos + 5], body[pos + 6], body[pos + 7]])
+        } as usize;
+        let end = (pos + 10 + size).min(body.len());
+
+        if !replaced.iter().any(|r| id == r.as_slice()) {
+            kept.extend_from_slice(&body[pos..end]);
+        }
+        pos = end;
+    }
+
+    (version, kept, audio_start)
+}
+
+/// Bytes taken by an ID3v2 tag at the start of `header`, 0 without one
+pub fn tag_len(header: &[u8]) -> usize {
+    if header.len() < 10 || &header[0..3] != b"ID3" {
+        return 0;
+    }
+    let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + read_synchsafe(&header[6..10]) as usize + footer
+}
+
+/// A full MP3: an ID3v2 tag holding `frames`, followed by `audio`
+pub fn write_tag(version: u8, frames: &[u8], audio: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(10 + frames.len() + audio.len());
+    output.extend_from_slice(b"ID3");
+    output.extend_from_slice(&[version, 0, 0]);
+    output.extend_from_slice(&synchsafe(frames.len() as u32));
+    output.extend_from_slice(frames);
+    output.extend_from_slice(audio);
+    output
+}
+
+pub fn frame(version: u8, id: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let size = payload.len() as u32;
+    let mut frame = id.to_vec();
+    if version == 4 {
+        frame.extend_from_slice(&synchsafe(size));
+    } else {
+        frame.extend_from_slice(&size.to_be_bytes());
+    }
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Encode a text frame body as Latin-1 when possible, otherwise UTF-16 with BOM
+pub fn encode_text(text: &str) -> Vec<u8> {
+    if text.is_ascii() {
+        let mut body = vec![0x00];
+        body.extend_from_slice(text.as_bytes());
+        body
+    } else {
+        let mut body = vec![0x01, 0xFF, 0xFE];
+        for unit in text.encode_utf16() {
+            body.extend_from_slice(&unit.to_le_bytes());
+        }
+        body
+    }
+}
+
+fn synchsafe(value: u32) -> [u8; 4] {
+    [
+        ((value >> 21) & 0x7F) as u8,
+        ((value >> 14) & 0x7F) as u8,
+        ((value >> 7) & 0x7F) as u8,
+        (value & 0x7F) as u8,
+    ]
+}
+
+fn read_synchsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .take(4)
+        .fold(0u32, |acc, &b| (acc << 7) | (b & 0x7F) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synchsafe_roundtrip() {
+        for value in [0, 127, 128, 255, 1_000_000, 0x0FFF_FFFF] {
+            assert_eq!(read_synchsafe(&synchsafe(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_split_tag_drops_replaced_frames() {
+        let mut frames = frame(3, b"TIT2", &encode_text("Mix"));
+        frames.extend(frame(3, b"APIC", b"old"));
+        let data = write_tag(3, &frames, b"audio");
+
+        let (version, kept, audio_start) = split_tag(&data, &[b"APIC"]);
+        assert_eq!(version, 3);
+        assert_eq!(kept, frame(3, b"TIT2", &encode_text("Mix")));
+        assert_eq!(&data[audio_start..], b"audio");
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::{Result, bail};
+
+/// Check an ISRC and return it in the compact form platforms expect.
+/// Accepts `CC-XXX-YY-NNNNN` with or without hyphens/spaces, any case:
+/// a 2-letter country code, 3-character registrant, 2-digit year and
+/// 5-digit designation.
+pub fn normalize_isrc(code: &str) -> Result<String> {
+    let compact: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let chars: Vec<char> = compact.chars().collect();
+    let valid = chars.len() == 12
+        && chars[..2].iter().all(char::is_ascii_uppercase)
+        && chars[2..5].iter().all(char::is_ascii_alphanumeric)
+        && chars[5..].iter().all(char::is_ascii_digit);
+    if !valid {
+        bail!(
+            "Invalid ISRC \"{}\": expected CC-XXX-YY-NNNNN (country, registrant, year, \
+             number), e.g. GB-A1B-26-00042",
+            code
+        );
+    }
+
+    Ok(compact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_isrc() {
+        assert_eq!(normalize_isrc("GB-A1B-26-00042").unwrap(), "GBA1B2600042");
+        assert_eq!(normalize_isrc("usrc17607839").unwrap(), "USRC17607839");
+
+        assert!(normalize_isrc("GB-A1B-26-0004").is_err());
+        assert!(normalize_isrc("12-A1B-26-00042").is_err());
+        assert!(normalize_isrc("GB-A1B-2X-00042").is_err());
+    }
+}
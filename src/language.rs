@@ -0,0 +1,90 @@
+use anyhow::{Result, bail};
+
+/// Languages accepted by --language, as ISO 639-1 codes
+pub const SUPPORTED_LANGUAGES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("ca", "Catalan"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("hu", "Hungarian"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("no", "Norwegian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("vi", "Vietnamese"),
+    ("zh", "Chinese"),
+];
+
+/// Check a BCP 47 language tag and return it in canonical case.
+/// Accepts a language on its own or with a region, e.g. `es`, `pt-BR` or
+/// `en_gb`; the language must be one of [`SUPPORTED_LANGUAGES`].
+pub fn normalize_language(tag: &str) -> Result<String> {
+    let tag = tag.trim().replace('_', "-");
+    let (language, region) = match tag.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (tag.as_str(), None),
+    };
+    let language = language.to_ascii_lowercase();
+
+    if !SUPPORTED_LANGUAGES
+        .iter()
+        .any(|(code, _)| *code == language)
+    {
+        let codes: Vec<&str> = SUPPORTED_LANGUAGES.iter().map(|(code, _)| *code).collect();
+        bail!(
+            "Unsupported language \"{}\"; use one of {}",
+            tag,
+            codes.join(", ")
+        );
+    }
+
+    match region {
+        None => Ok(language),
+        Some(region)
+            if (region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+                || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            Ok(format!("{}-{}", language, region.to_ascii_uppercase()))
+        }
+        Some(region) => bail!(
+            "Invalid region \"{}\" in language \"{}\": expected two letters or \
+             three digits, e.g. pt-BR or es-419",
+            region,
+            tag
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize_language("EN").unwrap(), "en");
+        assert_eq!(normalize_language("pt_br").unwrap(), "pt-BR");
+        assert_eq!(normalize_language("es-419").unwrap(), "es-419");
+
+        assert!(normalize_language("xx").is_err());
+        assert!(normalize_language("english").is_err());
+        assert!(normalize_language("en-GBR").is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::cli::Platform;
+use crate::config::{LimitOverrides, Settings};
+use crate::sanitize::{Field, Warning};
+
+/// Length and count limits a platform puts on upload metadata.
+/// See [`LimitOverrides`] to change them when a platform does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Longest title accepted; longer ones are rejected
+    pub title_max: usize,
+    /// Longest description kept; anything past this is cut off
+    pub description_max: usize,
+    pub tag_count_max: usize,
+    pub tag_len_max: usize,
+    /// Emoji a title may have; more are reported, or cut with `--sanitize strip`
+    pub title_emoji_max: usize,
+    /// Whether links in a title are left alone
+    pub urls_in_title: bool,
+}
+
+impl Limits {
+    /// Built-in limits, ignoring settings.json. Neither platform publishes an
+    /// emoji limit, so `title_emoji_max` is a conservative default; it and
+    /// `urls_in_title` can be changed with [`LimitOverrides`].
+    pub fn defaults(platform: Platform) -> Self {
+        match platform {
+            Platform::Mixcloud => Self {
+                title_max: 100,
+                description_max: 1000,
+                tag_count_max: 5,
+                tag_len_max: 64,
+                title_emoji_max: 3,
+                urls_in_title: false,
+            },
+            Platform::Soundcloud => Self {
+                title_max: 100,
+                description_max: 4000,
+                tag_count_max: 30,
+                tag_len_max: 100,
+                title_emoji_max: 5,
+                urls_in_title: false,
+            },
+        }
+    }
+
+    /// Built-in limits for a platform with any overrides from `settings`
+    pub fn for_platform(platform: Platform, settings: &Settings) -> Self {
+        let overrides = match platform {
+            Platform::Mixcloud => &settings.mixcloud_limits,
+            Platform::Soundcloud => &settings.soundcloud_limits,
+        };
+        Self::defaults(platform).with_overrides(overrides)
+    }
+
+    fn with_overrides(mut self, overrides: &LimitOverrides) -> Self {
+        let pairs = [
+            (&mut self.title_max, overrides.title_max),
+            (&mut self.description_max, overrides.description_max),
+            (&mut self.tag_count_max, overrides.tag_count_max),
+            (&mut self.tag_len_max, overrides.tag_len_max),
+            (&mut self.title_emoji_max, overrides.title_emoji_max),
+        ];
+        for (limit, value) in pairs {
+            if let Some(value) = value {
+                *limit = value;
+            }
+        }
+        if let Some(allowed) = overrides.urls_in_title {
+            self.urls_in_title = allowed;
+        }
+        self
+    }
+}
+
+/// The text of an upload that the limits apply to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub title: String,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// Check `metadata` against `limits`. Everything over a limit is reported.
+/// A description is always cut to fit, since the platform would drop the
+/// rest anyway; titles and tags are only cut with `truncate`.
+pub fn enforce(limits: &Limits, metadata: Metadata, truncate: bool) -> (Metadata, Vec<Warning>) {
+    let mut warnings = Vec::new();
+    let over = |warnings: &mut Vec<Warning>, field: Field, message: String| {
+        warnings.push(Warning {
+            field,
+            message,
+            fixed: truncate,
+        })
+    };
+
+    let title_len = metadata.title.chars().count();
+    let title = if title_len > limits.title_max {
+        let cut = cut(&metadata.title, limits.title_max);
+        over(
+            &mut warnings,
+            Field::Title,
+            if truncate {
+                format!(
+                    "{} characters, the limit is {}; cut to \"{}\"",
+                    title_len, limits.title_max, cut
+                )
+            } else {
+                format!(
+                    "{} characters, the limit is {}",
+                    title_len, limits.title_max
+                )
+            },
+        );
+        if truncate { cut } else { metadata.title }
+    } else {
+        metadata.title
+    };
+
+    let description = metadata.description.map(|description| {
+        let len = description.chars().count();
+        if len <= limits.description_max {
+            return description;
+        }
+        warnings.push(Warning {
+            field: Field::Description,
+            message: format!(
+                "{} characters, the limit is {}; {} characters cut",
+                len,
+                limits.description_max,
+                len - limits.description_max
+            ),
+            fixed: true,
+        });
+        cut(&description, limits.description_max)
+    });
+
+    let tags = metadata.tags.map(|tags| {
+        let mut fitted = Vec::with_capacity(tags.len());
+        for tag in tags {
+            if tag.chars().count() <= limits.tag_len_max {
+                fitted.push(tag);
+                continue;
+            }
+            let cut_tag = cut(&tag, limits.tag_len_max);
+            over(
+                &mut warnings,
+                Field::Tags,
+                if truncate {
+                    format!(
+                        "\"{}\" is over {} characters; cut to \"{}\"",
+                        tag, limits.tag_len_max, cut_tag
+                    )
+                } else {
+                    format!("\"{}\" is over {} characters", tag, limits.tag_len_max)
+                },
+            );
+            fitted.push(if truncate { cut_tag } else { tag });
+        }
+
+        if fitted.len() > limits.tag_count_max {
+            let extra = &fitted[limits.tag_count_max..];
+            over(
+                &mut warnings,
+                Field::Tags,
+                format!(
+                    "{} tags, the limit is {}; {} {}",
+                    fitted.len(),
+                    limits.tag_count_max,
+                    if truncate { "dropped" } else { "over" },
+                    extra.join(", ")
+                ),
+            );
+            if truncate {
+                fitted.truncate(limits.tag_count_max);
+            }
+        }
+        fitted
+    });
+
+    (
+        Metadata {
+            title,
+            description,
+            tags,
+        },
+        warnings,
+    )
+}
+
+/// The first `max` characters of `text`, without trailing whitespace
+fn cut(text: &str, max: usize) -> String {
+    let cut: String = text.chars().take(max).collect();
+    cut.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits {
+            title_max: 10,
+            description_max: 5,
+            tag_count_max: 2,
+            tag_len_max: 4,
+            title_emoji_max: 1,
+            urls_in_title: false,
+        }
+    }
+
+    fn metadata() -> Metadata {
+        Metadata {
+            title: "Sunday Set Live".to_string(),
+            description: Some("ünïcødé".to_string()),
+            tags: Some(vec![
+                "house".to_string(),
+                "dnb".to_string(),
+                "jazz".to_string(),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_enforce_warns_without_changing() {
+        let (fitted, warnings) = enforce(&limits(), metadata(), false);
+        assert_eq!(fitted.title, metadata().title);
+        assert_eq!(fitted.tags, metadata().tags);
+        // Descriptions are cut either way
+        assert_eq!(fitted.description.as_deref(), Some("ünïcø"));
+        let fields: Vec<(Field, bool)> = warnings.iter().map(|w| (w.field, w.fixed)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Field::Title, false),
+                (Field::Description, true),
+                (Field::Tags, false),
+                (Field::Tags, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enforce_always_cuts_description() {
+        let metadata = Metadata {
+            title: "Set".to_string(),
+            description: Some("Three hours".to_string()),
+            tags: None,
+        };
+        let (fitted, warnings) = enforce(&limits(), metadata, false);
+        assert_eq!(fitted.description.as_deref(), Some("Three"));
+        assert_eq!(
+            warnings,
+            vec![Warning {
+                field: Field::Description,
+                message: "11 characters, the limit is 5; 6 characters cut".to_string(),
+                fixed: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_enforce_truncates() {
+        let (fitted, warnings) = enforce(&limits(), metadata(), true);
+        assert_eq!(fitted.title, "Sunday Set");
+        assert_eq!(fitted.description.as_deref(), Some("ünïcø"));
+        assert_eq!(
+            fitted.tags,
+            Some(vec!["hous".to_string(), "dnb".to_string()])
+        );
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings.iter().all(|w| w.fixed));
+        assert!(warnings[0].message.ends_with("cut to \"Sunday Set\""));
+        assert_eq!(warnings[3].message, "3 tags, the limit is 2; dropped jazz");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = LimitOverrides {
+            tag_count_max: Some(15),
+            ..LimitOverrides::default()
+        };
+        let limits = Limits::defaults(Platform::Mixcloud).with_overrides(&overrides);
+        assert_eq!(limits.tag_count_max, 15);
+        assert_eq!(limits.title_max, 100);
+    }
+}
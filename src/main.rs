@@ -1,11 +1,40 @@
 mod audio;
+mod batch;
+mod build_info;
+mod cleanup;
 mod cli;
 mod config;
+mod config_check;
+mod cover;
+mod description;
+mod effective_config;
+mod estimate;
 mod gui;
+mod history;
+mod id3;
+mod isrc;
+mod language;
+mod limits;
+mod mp4;
+mod notification;
+mod output;
 mod platforms;
+mod previews;
+mod prompt;
+mod rate_limit;
+mod report;
+mod sanitize;
+mod schedule;
+mod sidecar;
+mod tags;
+mod temp;
+mod template;
+mod title;
+mod tracklist;
 mod updater;
+mod webhook;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 
 fn main() -> Result<()> {
@@ -15,6 +44,16 @@ fn main() -> Result<()> {
     if args.gui {
         return gui::run_gui();
     }
+    if args.build_info {
+        build_info::print_build_info();
+        return Ok(());
+    }
+
+    prompt::set_assume_yes(args.yes);
+    if let Some(path) = &args.trace_to {
+        platforms::trace::start(path)?;
+        eprintln!("Tracing HTTP calls to {}", path.display());
+    }
 
     // Initialize logging for CLI mode
     env_logger::builder()
@@ -32,77 +71,924 @@ fn main() -> Result<()> {
     }
 
     match args.command {
-        Some(cli::Commands::Auth { platform }) => {
-            platforms::handle_auth(platform)?;
+        Some(cli::Commands::Auth {
+            platform,
+            account,
+            print_auth_url,
+            show_verifier,
+            force,
+        }) => {
+            if print_auth_url {
+                platforms::print_auth_url(platform, show_verifier)?;
+            } else {
+                platforms::handle_auth(platform, account.as_deref(), force)?;
+            }
         }
         Some(cli::Commands::Upload {
             platform,
             file,
             title,
+            no_sidecar,
+            sidecar: sidecar_file,
             description,
+            description_file,
+            remix_of,
+            isrc,
+            language,
             image,
+            append_duration,
+            append_date,
+            append_to,
+            append_format,
+            image_format,
+            crop_gravity,
+            embed_cover,
+            unsupported_image,
+            sanitize,
+            auto_truncate,
+            generate_cover,
+            cover_from_waveform,
+            no_cover_title,
+            cover_scheme,
+            cover_font,
             tags,
+            tag_dedupe,
             publish_date,
+            schedule_from_filename,
+            assume_pro,
+            unlisted,
             generate_previews,
+            previews: preview_mode,
+            preview_durations,
+            preview_strategy,
+            climax_at,
+            preview_timestamps,
+            preview_fade_curve,
+            preview_compress_silence,
+            preview_silence_threshold,
+            preview_max_gap,
+            preview_format,
+            preview_sample_format,
+            previews_after_upload,
+            verify_decode,
+            normalize_peak,
+            normalize_sample_format,
+            embed_tracklist,
+            split,
+            dry_run,
+            only_if_newer,
+            compare_hash,
+            account,
+            strict,
+            deadline,
+            temp_dir,
+            keep_temp,
+            preflight,
+            min_free_space,
+            replace_if_exists,
+            notify_webhook,
+            webhook_format,
+            webhook_template,
+            notify,
         }) => {
-            let tag_list = tags.map(|t| {
-                t.split(',')
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect()
-            });
+            let settings = config::Settings::load()?;
+            let webhook = settings.webhook(notify_webhook, webhook_format, webhook_template)?;
+            if only_if_newer && file.as_os_str() == "-" {
+                anyhow::bail!("--only-if-newer needs a file path, not audio from stdin");
+            }
 
-            // Parse and convert publish_date if provided
-            let publish_date_utc = if let Some(date_str) = publish_date {
-                use chrono::{Local, NaiveDateTime, TimeZone};
+            // A sidecar next to the audio fills in whatever the flags leave out
+            let sidecar = if no_sidecar || (file.as_os_str() == "-" && sidecar_file.is_none()) {
+                None
+            } else {
+                sidecar::load_sidecar(&file, sidecar_file.as_deref())?
+            }
+            .unwrap_or_default();
+            let title = title.or(sidecar.title.clone()).context(
+                "--title is required unless a sidecar file next to the audio supplies one",
+            )?;
+            let description = description.or(sidecar.description.clone());
+            let publish_date = publish_date.or(sidecar.publish_date.clone());
+            let tags = tags
+                .map(|t| tags::parse_tags(&t))
+                .or_else(|| sidecar.tags_with_genre());
 
-                let naive_datetime = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M")
-                    .map_err(|e| {
-                        anyhow::anyhow!(
-                            "Invalid publish_date format. Use 'YYYY-MM-DD HH:MM': {}",
-                            e
-                        )
-                    })?;
+            let temp_dir = temp::TempDir::new(temp_dir.as_deref(), keep_temp)?;
+            // Nothing exists yet in this run's own temp dir, so --replace-if-exists
+            // only matters for the previews written next to the mix
+            let temp_outputs = cli::ExistingOutput::Overwrite;
+
+            // `--file -` buffers piped audio into the temp dir first
+            let file = if file.as_os_str() == "-" {
+                let buffered = temp_dir.save_stdin(&title)?;
+                println!("✓ Read audio from stdin into {}", buffered.display());
+                buffered
+            } else {
+                file
+            };
 
-                let local_datetime = Local
-                    .from_local_datetime(&naive_datetime)
-                    .single()
-                    .ok_or_else(|| anyhow::anyhow!("Ambiguous local time"))?;
+            // Hashed up front so the same hash goes into the history
+            let sha256 = compare_hash
+                .then(|| history::file_sha256(&file))
+                .transpose()?;
+            if only_if_newer {
+                let history = history::load()?;
+                if let Some(entry) = history::last_upload(&history, &file, platform)
+                    && let Some(reason) = history::unchanged_since(
+                        entry,
+                        history::modified_at(&file)?,
+                        sha256.as_deref(),
+                    )
+                {
+                    println!(
+                        "✓ Skipping {}: {} its upload to {} on {} ({})",
+                        file.display(),
+                        reason,
+                        platform,
+                        entry
+                            .uploaded_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M"),
+                        entry.url
+                    );
+                    return Ok(());
+                }
+            }
 
-                let utc_datetime = local_datetime.with_timezone(&chrono::Utc);
-                Some(utc_datetime.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+            if verify_decode {
+                println!("Decoding {} to check it...", file.display());
+                let report = audio::verify_decode(&file)
+                    .with_context(|| format!("{} doesn't decode to the end", file.display()))?;
+                let duration = title::format_duration_label(report.decoded_secs);
+                if report.bad_packets > 0 {
+                    for error in &report.errors {
+                        eprintln!("  ✗ {}", error);
+                    }
+                    anyhow::bail!(
+                        "{} has {} damaged packet(s) in {} of audio; re-export it before uploading",
+                        file.display(),
+                        report.bad_packets,
+                        duration
+                    );
+                }
+                println!("✓ Decoded {} of audio without errors", duration);
+            }
+
+            // Checked before any previews or artwork are made for nothing
+            if split.is_some() && audio::probe_metadata(&file)?.codec.as_deref() != Some("mp3") {
+                anyhow::bail!(
+                    "--split only works on MP3s, which are cut without re-encoding; \
+                     other formats would be uploaded as WAV parts larger than the mix"
+                );
+            }
+
+            let tag_list = tags
+                .map(|t| {
+                    let limits = limits::Limits::for_platform(platform, &settings);
+                    tags::validate_tags(platform, &limits, &t, strict)
+                })
+                .transpose()?
+                .map(|t| tags::dedupe_tags(t, tag_dedupe));
+
+            let description =
+                description::load_description(description.as_deref(), description_file.as_deref())?;
+            let description = description::with_attribution(description, remix_of.as_deref());
+
+            // Parse publish_date if provided, otherwise optionally take it from the filename
+            let publish_datetime = if let Some(date_str) = publish_date {
+                let naive_datetime = chrono::NaiveDateTime::parse_from_str(
+                    &date_str,
+                    "%Y-%m-%d %H:%M",
+                )
+                .map_err(|e| {
+                    anyhow::anyhow!("Invalid publish_date format. Use 'YYYY-MM-DD HH:MM': {}", e)
+                })?;
+                Some(naive_datetime)
+            } else if schedule_from_filename {
+                let parsed = schedule::parse_filename_schedule(&file);
+                match parsed {
+                    Some(datetime) => {
+                        println!(
+                            "✓ Scheduling from filename: {}",
+                            datetime.format("%Y-%m-%d %H:%M")
+                        );
+                        if datetime < chrono::Local::now().naive_local() {
+                            eprintln!("⚠ Warning: Date from filename is in the past");
+                        }
+                    }
+                    None => eprintln!("⚠ Warning: No date found in filename, not scheduling"),
+                }
+                parsed
             } else {
                 None
             };
 
-            // Generate preview snippets if requested
-            if generate_previews {
-                match audio::create_preview_snippets(&file) {
-                    Ok(snippets) => {
-                        println!("✓ Generated {} preview snippets:", snippets.len());
-                        for snippet in &snippets {
-                            println!("  - {}", snippet.display());
+            // Convert the local publish time to UTC
+            let publish_date_utc = publish_datetime
+                .map(|datetime| schedule::local_to_utc_publish_date(&datetime))
+                .transpose()?;
+
+            // Append the mix length and/or date, e.g. "Sunday Set (2h15m)"
+            let (title, description) = if append_duration || append_date {
+                let duration = if append_duration {
+                    Some(
+                        audio::get_audio_duration(&file)
+                            .context("Failed to read duration for --append-duration")?,
+                    )
+                } else {
+                    None
+                };
+                let date = append_date.then(|| {
+                    publish_datetime
+                        .map(|datetime| datetime.date())
+                        .unwrap_or_else(|| chrono::Local::now().date_naive())
+                });
+                let format = append_format
+                    .as_deref()
+                    .unwrap_or_else(|| title::default_suffix_format(append_duration, append_date));
+                let suffix = title::render_suffix(format, duration, date)
+                    .context("Invalid --append-format")?;
+                match append_to {
+                    cli::AppendTarget::Title => {
+                        (format!("{} {}", title.trim_end(), suffix), description)
+                    }
+                    cli::AppendTarget::Description => {
+                        let description = match description {
+                            Some(d) => format!("{}\n\n{}", d.trim_end(), suffix),
+                            None => suffix,
+                        };
+                        (title, Some(description))
+                    }
+                }
+            } else {
+                (title, description)
+            };
+
+            // Previews come first unless they should only follow a successful upload
+            let preview_options = previews::select_durations(
+                &file,
+                generate_previews,
+                preview_mode,
+                preview_durations,
+                preview_timestamps.as_deref(),
+            )
+            .map(|durations| previews::PreviewOptions {
+                durations,
+                format: preview_format,
+                sample_format: preview_sample_format,
+                output_dir: None,
+                placement: audio::PreviewPlacement {
+                    strategy: preview_strategy,
+                    climax_at,
+                    timestamps: preview_timestamps,
+                },
+                fade_curve: preview_fade_curve,
+                compress_silence: preview_compress_silence.then_some(audio::SilenceCompression {
+                    threshold_db: preview_silence_threshold,
+                    max_gap_secs: preview_max_gap,
+                }),
+                existing: replace_if_exists,
+                min_free_space,
+            });
+
+            // Stop before anything gets written
+            if dry_run {
+                if split.is_some() {
+                    println!("Note: --split is not simulated, showing the whole file");
+                }
+                let processed = preview_options.is_some()
+                    || normalize_peak.is_some()
+                    || embed_tracklist.is_some()
+                    || (image.is_empty() && (generate_cover || cover_from_waveform))
+                    || crop_gravity.is_some()
+                    || embed_cover;
+                if processed {
+                    println!(
+                        "Note: previews, audio changes and cover artwork changes are not simulated, showing the original files"
+                    );
+                }
+                return platforms::dry_run(
+                    platform,
+                    account.as_deref(),
+                    &file,
+                    &title,
+                    description.as_deref(),
+                    &image,
+                    tag_list,
+                    publish_date_utc.as_deref(),
+                    isrc.as_deref(),
+                    language.as_deref(),
+                    remix_of.as_deref(),
+                    unlisted,
+                    strict,
+                    auto_truncate,
+                );
+            }
+
+            if !previews_after_upload && let Some(options) = &preview_options {
+                previews::report(previews::generate(&file, options, &print_preview_progress));
+            }
+
+            // Peak-normalize into a separate file and upload that instead
+            let upload_file = if let Some(target_dbfs) = normalize_peak {
+                let metadata = audio::probe_metadata(&file)?;
+                // The estimate is for 16-bit samples
+                let needed = audio::estimate_full_wav_bytes(&file, &metadata)
+                    .map(|bytes| bytes * normalize_sample_format.bytes_per_sample() / 2);
+                match needed {
+                    Some(needed) => {
+                        let source = std::fs::metadata(&file)?.len();
+                        if needed > source.saturating_mul(2) {
+                            eprintln!(
+                                "⚠ Warning: The normalized WAV will be about {}, against {} for {}",
+                                output::format_size(needed),
+                                output::format_size(source),
+                                file.display()
+                            );
+                        }
+                    }
+                    None => {
+                        eprintln!(
+                            "⚠ Warning: Unknown length, not checking free space for normalizing"
+                        )
+                    }
+                }
+                output::ensure_free_space(temp_dir.path(), needed.unwrap_or(0), min_free_space)?;
+                let normalized = audio::normalize_peak(
+                    &file,
+                    target_dbfs,
+                    normalize_sample_format,
+                    temp_dir.path(),
+                    temp_outputs,
+                )?;
+                println!(
+                    "✓ Normalized to {} dBFS: {}",
+                    target_dbfs,
+                    normalized.display()
+                );
+                normalized
+            } else {
+                file.clone()
+            };
+
+            // Write the tracklist into the file as chapters
+            let upload_file = if let Some(tracklist_path) = embed_tracklist {
+                let sections = tracklist::load_tracklist(&tracklist_path)?;
+                let tagged = tracklist::embed_tracklist(
+                    &upload_file,
+                    &sections,
+                    temp_dir.path(),
+                    temp_outputs,
+                )?;
+                if tagged != upload_file {
+                    println!(
+                        "✓ Embedded {} chapters: {}",
+                        sections.len(),
+                        tagged.display()
+                    );
+                }
+                tagged
+            } else {
+                upload_file
+            };
+
+            // Fall back to a generated cover; a failure just means no artwork
+            let image = if !image.is_empty() {
+                image
+            } else if generate_cover || cover_from_waveform {
+                let mut opts = cover::CoverOptions::for_platform(
+                    platform,
+                    cover_scheme,
+                    cover_font,
+                    image_format,
+                );
+                if cover_from_waveform {
+                    match audio::waveform_peaks(&file, opts.size as usize) {
+                        Ok(peaks) => {
+                            opts.waveform = Some(peaks);
+                            opts.show_title = !no_cover_title;
                         }
+                        Err(e) => eprintln!(
+                            "⚠ Warning: Failed to read the waveform, using a plain cover: {}",
+                            e
+                        ),
+                    }
+                }
+                match cover::write_cover(&file, &title, &opts, temp_dir.path(), temp_outputs) {
+                    Ok(path) => {
+                        println!("✓ Generated cover: {}", path.display());
+                        vec![path]
                     }
                     Err(e) => {
-                        eprintln!("⚠ Warning: Failed to generate previews: {}", e);
-                        // Continue with upload even if preview generation fails
+                        eprintln!(
+                            "⚠ Warning: Failed to generate cover, uploading without one: {}",
+                            e
+                        );
+                        Vec::new()
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            // Crop non-square artwork so the platform doesn't pick the square itself
+            let image = match crop_gravity {
+                Some(gravity) => {
+                    let (x_offset, y_offset) = cover::gravity_offsets(gravity);
+                    image
+                        .iter()
+                        .map(|path| {
+                            cover::crop_image(
+                                path,
+                                x_offset,
+                                y_offset,
+                                image_format,
+                                temp_dir.path(),
+                            )
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                }
+                None => image,
+            };
+
+            // Carry the artwork inside the uploaded file as well
+            let upload_file = match image.first() {
+                Some(image_path) if embed_cover => {
+                    let image_bytes = std::fs::read(image_path)
+                        .with_context(|| format!("Failed to read {}", image_path.display()))?;
+                    let covered = cover::embed_cover(
+                        &upload_file,
+                        &image_bytes,
+                        temp_dir.path(),
+                        temp_outputs,
+                    )?;
+                    if covered != upload_file {
+                        println!("✓ Embedded cover: {}", covered.display());
+                    }
+                    covered
+                }
+                _ => upload_file,
+            };
+
+            // Upload long mixes as several parts, each linking back to the earlier ones
+            if let Some(part_length) = split {
+                // Parts are copied frames, so they add up to the original
+                let needed = std::fs::metadata(&upload_file)?.len();
+                output::ensure_free_space(temp_dir.path(), needed, min_free_space)?;
+
+                let cuts = audio::find_split_points(&upload_file, part_length.as_secs_f64())?;
+                if cuts.is_empty() {
+                    println!("Mix is no longer than --split, uploading it in one piece");
+                } else {
+                    let parts =
+                        audio::split_audio(&upload_file, &cuts, temp_dir.path(), temp_outputs)?;
+                    println!("✓ Split into {} parts", parts.len());
+
+                    let mut links = Vec::new();
+                    for (i, part) in parts.iter().enumerate() {
+                        let part_title = format!("{} (Part {})", title, i + 1);
+                        let part_description = description::part_description(
+                            description.as_deref(),
+                            i + 1,
+                            parts.len(),
+                            &links,
+                        );
+                        println!("\nUploading part {} of {}...", i + 1, parts.len());
+                        let result = platforms::handle_upload(
+                            platform,
+                            part,
+                            &part_title,
+                            Some(&part_description),
+                            &image,
+                            tag_list.clone(),
+                            publish_date_utc.as_deref(),
+                            assume_pro,
+                            unlisted,
+                            account.as_deref(),
+                            strict,
+                            deadline,
+                            preflight,
+                            image_format,
+                            unsupported_image,
+                            temp_dir.path(),
+                            isrc.as_deref(),
+                            language.as_deref(),
+                            remix_of.as_deref(),
+                            sanitize,
+                            auto_truncate,
+                        );
+
+                        if let Some(webhook) = &webhook {
+                            let outcome = match &result {
+                                Ok(Some(outcome)) => Some(Ok(outcome.url.clone())),
+                                Ok(None) => None,
+                                Err(e) => Some(Err(format!("{:#}", e))),
+                            };
+                            if let Some(outcome) = outcome {
+                                webhook.notify(&webhook::UploadPayload::new(
+                                    platform,
+                                    &part_title,
+                                    outcome,
+                                ));
+                            }
+                        }
+
+                        match result {
+                            Ok(Some(outcome)) => links.push(outcome.link()),
+                            Ok(None) => anyhow::bail!("Upload of part {} was cancelled", i + 1),
+                            Err(e) => {
+                                if notify {
+                                    notification::upload_finished(
+                                        &title,
+                                        &[platform],
+                                        Some(&e.to_string()),
+                                    );
+                                }
+                                return Err(e.context(format!(
+                                    "Part {} of {} failed; {} uploaded before it",
+                                    i + 1,
+                                    parts.len(),
+                                    i
+                                )));
+                            }
+                        }
                     }
+
+                    println!("\n✓ Uploaded {} parts:", links.len());
+                    for (i, link) in links.iter().enumerate() {
+                        println!("  Part {}: {}", i + 1, link);
+                    }
+                    // The source file is what --only-if-newer looks up; part 1 leads to the rest
+                    if let Some(first) = links.first()
+                        && let Err(e) = history::record(&file, platform, &title, first, sha256)
+                    {
+                        log::warn!("Failed to record upload in history: {:#}", e);
+                    }
+                    if notify {
+                        notification::upload_finished(&title, &[platform], None);
+                    }
+                    if previews_after_upload && let Some(options) = &preview_options {
+                        previews::report(previews::generate(
+                            &file,
+                            options,
+                            &print_preview_progress,
+                        ));
+                    }
+                    return Ok(());
                 }
             }
 
-            platforms::handle_upload(
+            let result = platforms::handle_upload(
                 platform,
-                &file,
+                &upload_file,
                 &title,
                 description.as_deref(),
-                image.as_deref(),
+                &image,
                 tag_list,
                 publish_date_utc.as_deref(),
-            )?;
+                assume_pro,
+                unlisted,
+                account.as_deref(),
+                strict,
+                deadline,
+                preflight,
+                image_format,
+                unsupported_image,
+                temp_dir.path(),
+                isrc.as_deref(),
+                language.as_deref(),
+                remix_of.as_deref(),
+                sanitize,
+                auto_truncate,
+            );
+
+            if let Some(webhook) = &webhook {
+                let outcome = match &result {
+                    Ok(Some(outcome)) => Some(Ok(outcome.url.clone())),
+                    Ok(None) => None,
+                    Err(e) => Some(Err(format!("{:#}", e))),
+                };
+                if let Some(outcome) = outcome {
+                    webhook.notify(&webhook::UploadPayload::new(platform, &title, outcome));
+                }
+            }
+            if notify && !matches!(result, Ok(None)) {
+                let error = result.as_ref().err().map(|e| e.to_string());
+                notification::upload_finished(&title, &[platform], error.as_deref());
+            }
+            if let Ok(Some(outcome)) = &result
+                && let Err(e) = history::record(&file, platform, &title, &outcome.link(), sha256)
+            {
+                log::warn!("Failed to record upload in history: {:#}", e);
+            }
+            if previews_after_upload
+                && matches!(result, Ok(Some(_)))
+                && let Some(options) = &preview_options
+            {
+                previews::report(previews::generate(&file, options, &print_preview_progress));
+            }
+            result?;
         }
-        Some(cli::Commands::Status) => {
-            platforms::show_status()?;
+        Some(cli::Commands::Batch {
+            platform,
+            input_glob,
+            manifest,
+            resume,
+            retry_failed,
+            description,
+            title_template,
+            tags,
+            tag_dedupe,
+            concurrency,
+            fail_fast,
+            account,
+            notify_webhook,
+            webhook_format,
+            webhook_template,
+        }) => {
+            // A failure report is a manifest like the one --resume reads
+            let resume = resume.or(retry_failed);
+            // Reports go next to the CSV the batch came from, or with the
+            // upload history for a glob
+            let report_dir = match manifest.as_deref().or(resume.as_deref()) {
+                Some(csv) => csv
+                    .parent()
+                    .unwrap_or(std::path::Path::new(""))
+                    .to_path_buf(),
+                None => config::config_dir()?,
+            };
+            let mut items = match (manifest, input_glob, resume) {
+                (Some(manifest), _, _) => {
+                    let items = batch::load_manifest(&manifest, platform)?;
+                    if items.is_empty() {
+                        anyhow::bail!("Manifest {} has no rows", manifest.display());
+                    }
+                    items
+                }
+                (None, _, Some(resume)) => {
+                    let items = batch::load_manifest(&resume, platform)?;
+                    let (items, skipped) = batch::skip_uploaded(items)?;
+                    if skipped > 0 {
+                        println!("Skipping {} already uploaded", skipped);
+                    }
+                    if items.is_empty() {
+                        println!("Nothing left to upload in {}", resume.display());
+                        return Ok(());
+                    }
+                    items
+                }
+                (None, Some(input_glob), None) => {
+                    let files = batch::expand_glob(&input_glob)?;
+                    if files.is_empty() {
+                        anyhow::bail!("No files match {}", input_glob);
+                    }
+                    files
+                        .into_iter()
+                        .map(|file| batch::BatchItem {
+                            title: batch::title_from_filename(&file),
+                            file,
+                            platform,
+                        })
+                        .collect()
+                }
+                (None, None, None) => {
+                    anyhow::bail!("Pass --input-glob, --manifest, --resume or --retry-failed")
+                }
+            };
+            if let Some(template) = &title_template {
+                batch::apply_title_template(&mut items, template)?;
+            }
+
+            let settings = config::Settings::load()?;
+            let webhook = settings.webhook(notify_webhook, webhook_format, webhook_template)?;
+            let tag_list = match tags {
+                Some(t) => tags::parse_tags(&t),
+                None => settings.default_tags.clone(),
+            };
+            let limits = limits::Limits::for_platform(platform, &settings);
+            let tag_list = tags::validate_tags(platform, &limits, &tag_list, false)?;
+            let tag_list = tags::dedupe_tags(tag_list, tag_dedupe);
+
+            let options = batch::BatchOptions {
+                description_template: description,
+                tags: (!tag_list.is_empty()).then_some(tag_list),
+                account,
+                concurrency,
+                fail_fast,
+                settings,
+            };
+
+            batch::install_interrupt_handler()?;
+            let results = batch::run_batch(items, &options)?;
+            batch::print_summary(&results);
+
+            // Leave a manifest of what's left so the batch can be picked up again
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            let remaining_path = report_dir.join(format!("batch-remaining-{}.csv", stamp));
+            if batch::write_remaining(&results, &remaining_path)? > 0 {
+                println!(
+                    "\nRemaining uploads saved to {}\nResume with: dj-uploader batch {} --resume {}",
+                    remaining_path.display(),
+                    platform.to_string().to_lowercase(),
+                    remaining_path.display()
+                );
+            }
+            let failures_path = report_dir.join(format!("batch-failures-{}.csv", stamp));
+            if batch::write_failures(&results, &failures_path)? > 0 {
+                println!(
+                    "Failures and their reasons saved to {}\nRetry them with: dj-uploader batch {} --retry-failed {}",
+                    failures_path.display(),
+                    platform.to_string().to_lowercase(),
+                    failures_path.display()
+                );
+            }
+
+            if let Some(webhook) = &webhook {
+                for result in &results {
+                    let Some(outcome) = &result.outcome else {
+                        continue;
+                    };
+                    webhook.notify(&webhook::UploadPayload::new(
+                        result.item.platform,
+                        &result.item.title,
+                        outcome.clone().map(Some),
+                    ));
+                }
+            }
+
+            let failed = results
+                .iter()
+                .filter(|r| !matches!(r.outcome, Some(Ok(_))))
+                .count();
+            if failed > 0 {
+                anyhow::bail!("{} of {} uploads did not complete", failed, results.len());
+            }
+        }
+        Some(cli::Commands::DebugChunk {
+            file,
+            start,
+            len,
+            out,
+        }) => {
+            let (actual_start, seek_method) = audio::debug_chunk(&file, start, len, &out)?;
+            println!("✓ Wrote {}s chunk to {}", len, out.display());
+            println!("  Requested start: {:.3}s", start);
+            match actual_start {
+                Some(actual) => println!(
+                    "  Actual start:    {:.3}s ({:+.3}s)",
+                    actual,
+                    actual - start
+                ),
+                None => println!("  Actual start:    seek failed, decoded from 0.000s"),
+            }
+            println!("  Positioned by:   {}", seek_method);
+        }
+        Some(cli::Commands::CheckConfig {
+            path,
+            check_endpoints,
+        }) => {
+            config_check::handle_check_config(&path, check_endpoints)?;
+        }
+        Some(cli::Commands::Describe {
+            file,
+            json,
+            levels,
+            bpm,
+            exact_duration,
+        }) => {
+            let report = report::describe_file(&file, levels, bpm, exact_duration)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                report::print_report(&report);
+            }
+        }
+        Some(cli::Commands::Previews {
+            file,
+            previews: preview_mode,
+            preview_durations,
+            format,
+            output_dir,
+            preview_strategy,
+            climax_at,
+            preview_timestamps,
+            preview_fade_curve,
+            preview_compress_silence,
+            preview_silence_threshold,
+            preview_max_gap,
+            preview_sample_format,
+            min_free_space,
+            replace_if_exists,
+        }) => {
+            let durations = previews::select_durations(
+                &file,
+                true,
+                preview_mode,
+                preview_durations,
+                preview_timestamps.as_deref(),
+            )
+            .unwrap_or_else(|| audio::DEFAULT_PREVIEW_DURATIONS.to_vec());
+            let options = previews::PreviewOptions {
+                durations,
+                format,
+                sample_format: preview_sample_format,
+                output_dir,
+                placement: audio::PreviewPlacement {
+                    strategy: preview_strategy,
+                    climax_at,
+                    timestamps: preview_timestamps,
+                },
+                fade_curve: preview_fade_curve,
+                compress_silence: preview_compress_silence.then_some(audio::SilenceCompression {
+                    threshold_db: preview_silence_threshold,
+                    max_gap_secs: preview_max_gap,
+                }),
+                existing: replace_if_exists,
+                min_free_space,
+            };
+            // Standalone, a failure is the whole job rather than a warning
+            let snippets = previews::generate(&file, &options, &print_preview_progress)?;
+            println!("✓ Generated {} preview snippets:", snippets.len());
+            for snippet in &snippets {
+                println!("  - {}", snippet.display());
+            }
+        }
+        Some(cli::Commands::Cleanup {
+            older_than,
+            sessions,
+            history,
+            previews_in,
+            temp_dir,
+        }) => {
+            // With nothing selected, clean the local state but leave audio folders alone
+            let everything = !sessions && !history && previews_in.is_none();
+            cleanup::run_cleanup(&cleanup::CleanupOptions {
+                older_than_days: older_than,
+                sessions: sessions || everything,
+                history: history || everything,
+                previews_in,
+                temp_dir,
+                apply: prompt::assume_yes(),
+            })?;
+        }
+        Some(cli::Commands::Estimate { file, platform }) => {
+            estimate::print_estimate(&file, &platform)?;
+        }
+        Some(cli::Commands::ListFormats) => {
+            println!("Input containers:");
+            for (name, extensions) in audio::INPUT_CONTAINERS {
+                println!("  {:<20} {}", name, extensions);
+            }
+
+            println!("\nInput codecs:");
+            for (short_name, long_name) in audio::supported_codecs() {
+                println!("  {:<20} {}", short_name, long_name);
+            }
+
+            println!("\nOutput:");
+            if cfg!(feature = "mp3") {
+                println!(
+                    "  {:<20} 16-bit or float mono WAV, or {} kbps mono MP3",
+                    "Preview snippets",
+                    audio::MP3_PREVIEW_KBPS
+                );
+            } else {
+                println!("  {:<20} 16-bit or float mono WAV", "Preview snippets");
+            }
+            println!("  {:<20} 16-bit WAV, original channels", "--normalize-peak");
+            println!(
+                "  {:<20} MP3 input only, ID3v2 chapters",
+                "--embed-tracklist"
+            );
+        }
+        Some(cli::Commands::ResetBreaker { platform }) => {
+            platforms::breaker::reset(platform)?;
+            match platform {
+                Some(platform) => println!("✓ Uploads to {} are allowed again", platform),
+                None => println!("✓ Uploads to all platforms are allowed again"),
+            }
+        }
+        Some(cli::Commands::OpenConfigDir) => {
+            let dir = config::config_dir()?;
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            // The path alone is what scripts and headless machines need
+            println!("{}", dir.display());
+            if config::has_desktop()
+                && let Err(e) = config::open_in_file_manager(&dir)
+            {
+                eprintln!("⚠ Warning: Could not open a file manager: {:#}", e);
+            }
+        }
+        Some(cli::Commands::PrintConfig) => {
+            effective_config::print_config()?;
+        }
+        Some(cli::Commands::Status { json }) => {
+            if json {
+                platforms::show_status_json()?;
+            } else {
+                platforms::show_status()?;
+            }
         }
         None => {
             println!("DJ Uploader - Upload your music to Mixcloud and SoundCloud");
@@ -110,9 +996,43 @@ fn main() -> Result<()> {
             println!("  dj-uploader auth <platform>          Authorize with a platform");
             println!("  dj-uploader upload <platform> ...    Upload a mix");
             println!("  dj-uploader status                   Show configuration status");
+            println!("  dj-uploader describe <file>          Show file details and analysis");
+            println!("  dj-uploader previews <file>          Generate preview snippets only");
+            println!("  dj-uploader estimate <file>          Estimate processing and upload time");
+            println!("  dj-uploader list-formats             Show supported audio formats");
+            println!("  dj-uploader --build-info             Show how this binary was built");
+            println!("  dj-uploader cleanup                  Remove old temp files and history");
+            println!("  dj-uploader check-config [path]      Validate a credentials file");
+            println!("  dj-uploader print-config             Show the settings in effect");
+            println!(
+                "  dj-uploader open-config-dir          Open the folder with tokens and settings"
+            );
             println!("\nUse --help for more information");
         }
     }
 
     Ok(())
 }
+
+/// Redraw one stderr line with preview generation progress
+fn print_preview_progress(progress: &audio::PreviewProgress) {
+    use std::io::{IsTerminal, Write};
+
+    let mut stderr = std::io::stderr();
+    if !stderr.is_terminal() {
+        return;
+    }
+    let _ = write!(
+        stderr,
+        "\r\x1b[2K  Preview {}/{} ({}s): {:.0}% — overall {:.0}%",
+        progress.snippet,
+        progress.snippets,
+        progress.duration,
+        progress.snippet_fraction * 100.0,
+        progress.overall() * 100.0
+    );
+    if progress.snippet == progress.snippets && progress.snippet_fraction >= 1.0 {
+        let _ = writeln!(stderr);
+    }
+    let _ = stderr.flush();
+}
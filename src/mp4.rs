@@ -0,0 +1,230 @@
+use anyhow::{Context, Result, bail};
+use std::ops::Range;
+
+/// One box: its type and where its body sits in the parent slice
+struct Atom {
+    kind: [u8; 4],
+    body: Range<usize>,
+}
+
+impl Atom {
+    /// The whole box, header included
+    fn range(&self, header: usize) -> Range<usize> {
+        self.body.start - header..self.body.end
+    }
+}
+
+/// Parse the boxes laid out back to back in `data`.
+/// Returns each atom with the length of its header.
+fn parse_atoms(data: &[u8]) -> Result<Vec<(Atom, usize)>> {
+    let mut atoms = Vec::new();
+    let mut pos = 0;
+
+    while pos + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[pos..pos + 4].try_into()?) as u64;
+        let kind: [u8; 4] = data[pos + 4..pos + 8].try_into()?;
+        let (header, size) = match size {
+            // 64-bit size follows the type
+            1 => {
+                let large = data
+                    .get(pos + 8..pos + 16)
+                    .context("Truncated MP4 box header")?;
+                (16, u64::from_be_bytes(large.try_into()?))
+            }
+            // Runs to the end of the file
+            0 => (8, (data.len() - pos) as u64),
+            size => (8, size),
+        };
+
+        let end = pos as u64 + size;
+        if size < header as u64 || end > data.len() as u64 {
+            bail!(
+                "Malformed MP4 box '{}' at offset {}",
+                String::from_utf8_lossy(&kind),
+                pos
+            );
+        }
+        let end = end as usize;
+        atoms.push((
+            Atom {
+                kind,
+                body: pos + header..end,
+            },
+            header,
+        ));
+        pos = end;
+    }
+
+    Ok(atoms)
+}
+
+fn atom(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(body.len() + 8);
+    bytes.extend_from_slice(&(body.len() as u32 + 8).to_be_bytes());
+    bytes.extend_from_slice(kind);
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Copy the children of a container, replacing the one of type `kind` with
+/// `rebuild(its body)`, or appending `rebuild(None)` if there is none
+fn replace_child(
+    body: &[u8],
+    kind: &[u8; 4],
+    rebuild: impl FnOnce(Option<&[u8]>) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(body.len());
+    let mut rebuild = Some(rebuild);
+
+    for (child, header) in parse_atoms(body)? {
+        if &child.kind == kind
+            && let Some(rebuild) = rebuild.take()
+        {
+            output.extend(atom(kind, &rebuild(Some(&body[child.body.clone()]))?));
+        } else {
+            output.extend_from_slice(&body[child.range(header)]);
+        }
+    }
+    if let Some(rebuild) = rebuild {
+        output.extend(atom(kind, &rebuild(None)?));
+    }
+
+    Ok(output)
+}
+
+/// Add cover art to an MP4/M4A file as the iTunes `covr` item, replacing any
+/// existing cover. Chunk offsets are moved along when the metadata sits in
+/// front of the audio data.
+pub fn embed_cover(data: &[u8], image: &[u8], is_png: bool) -> Result<Vec<u8>> {
+    let atoms = parse_atoms(data)?;
+    let (moov, moov_header) = atoms
+        .iter()
+        .find(|(atom, _)| &atom.kind == b"moov")
+        .context("MP4 file has no 'moov' box")?;
+    let mdat_after_moov = atoms
+        .iter()
+        .any(|(atom, _)| &atom.kind == b"mdat" && atom.body.start > moov.body.start);
+
+    // covr > data: type 13 is JPEG, 14 is PNG, then a zero locale
+    let mut cover_data = (if is_png { 14u32 } else { 13u32 }).to_be_bytes().to_vec();
+    cover_data.extend_from_slice(&[0; 4]);
+    cover_data.extend_from_slice(image);
+    let covr = atom(b"data", &cover_data);
+
+    let ilst = |ilst: Option<&[u8]>| -> Result<Vec<u8>> {
+        let mut items = Vec::new();
+        if let Some(ilst) = ilst {
+            for (item, header) in parse_atoms(ilst)? {
+                if &item.kind != b"covr" {
+                    items.extend_from_slice(&ilst[item.range(header)]);
+                }
+            }
+        }
+        items.extend(atom(b"covr", &covr));
+        Ok(items)
+    };
+    let meta = |meta: Option<&[u8]>| -> Result<Vec<u8>> {
+        match meta {
+            // meta is a full box: version and flags come before the children
+            Some(meta) => {
+                let (version, children) =
+                    meta.split_at_checked(4).context("Truncated 'meta' box")?;
+                let mut body = version.to_vec();
+                body.extend(replace_child(children, b"ilst", ilst)?);
+                Ok(body)
+            }
+            None => {
+                let mut hdlr = vec![0; 8];
+                hdlr.extend_from_slice(b"mdirappl");
+                hdlr.extend_from_slice(&[0; 9]);
+                let mut body = vec![0; 4];
+                body.extend(atom(b"hdlr", &hdlr));
+                body.extend(atom(b"ilst", &ilst(None)?));
+                Ok(body)
+            }
+        }
+    };
+    let udta = |udta: Option<&[u8]>| replace_child(udta.unwrap_or_default(), b"meta", meta);
+
+    let mut new_moov = replace_child(&data[moov.body.clone()], b"udta", udta)?;
+    let delta = new_moov.len() as i64 - moov.body.len() as i64;
+    if mdat_after_moov && delta != 0 {
+        shift_chunk_offsets(&mut new_moov, delta)?;
+    }
+
+    let moov_range = moov.range(*moov_header);
+    let mut output = Vec::with_capacity(data.len() + covr.len() + 64);
+    output.extend_from_slice(&data[..moov_range.start]);
+    output.extend(atom(b"moov", &new_moov));
+    output.extend_from_slice(&data[moov_range.end..]);
+    Ok(output)
+}
+
+/// Move every chunk offset (`stco`/`co64`) in a moov body by `delta` bytes
+fn shift_chunk_offsets(container: &mut [u8], delta: i64) -> Result<()> {
+    for (child, _) in parse_atoms(container)? {
+        let body = &mut container[child.body];
+        match &child.kind {
+            b"trak" | b"mdia" | b"minf" | b"stbl" => shift_chunk_offsets(body, delta)?,
+            b"stco" | b"co64" => {
+                let width = if &child.kind == b"stco" { 4 } else { 8 };
+                let count = body.get(4..8).context("Truncated chunk offset table")?;
+                let count = u32::from_be_bytes(count.try_into()?) as usize;
+                let entries = body
+                    .get_mut(8..8 + count * width)
+                    .context("Truncated chunk offset table")?;
+                for entry in entries.chunks_exact_mut(width) {
+                    if width == 4 {
+                        let offset = u32::from_be_bytes(entry.try_into()?) as i64 + delta;
+                        let offset = u32::try_from(offset)
+                            .context("Chunk offset out of range after adding the cover")?;
+                        entry.copy_from_slice(&offset.to_be_bytes());
+                    } else {
+                        let offset = u64::from_be_bytes(entry.try_into()?) as i64 + delta;
+                        entry.copy_from_slice(&(offset as u64).to_be_bytes());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_cover_shifts_chunk_offsets() {
+        // ftyp, then moov with one chunk offset pointing into mdat
+        let ftyp = atom(b"ftyp", b"M4A \0\0\0\0");
+        // Six box headers, the offset table and the mdat header come before the audio
+        let mdat_offset = (ftyp.len() + 6 * 8 + 12 + 8) as u32;
+        let mut stco = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stco.extend_from_slice(&mdat_offset.to_be_bytes());
+        let stbl = atom(b"stbl", &atom(b"stco", &stco));
+        let moov = atom(
+            b"moov",
+            &atom(b"trak", &atom(b"mdia", &atom(b"minf", &stbl))),
+        );
+        let mut data = [ftyp.clone(), moov].concat();
+        assert_eq!(data.len() + 8, mdat_offset as usize);
+        data.extend(atom(b"mdat", b"audio"));
+
+        let image = b"\xFF\xD8\xFFjpeg";
+        let output = embed_cover(&data, image, false).unwrap();
+        let delta = (output.len() - data.len()) as u32;
+
+        // The audio moved and the chunk offset still points at it
+        let offset_pos = output.windows(4).position(|w| w == b"stco").unwrap() + 12;
+        let offset = u32::from_be_bytes(output[offset_pos..offset_pos + 4].try_into().unwrap());
+        assert_eq!(offset, mdat_offset + delta);
+        assert_eq!(&output[offset as usize..offset as usize + 5], b"audio");
+
+        // Embedding again replaces the cover instead of adding a second one
+        let again = embed_cover(&output, image, false).unwrap();
+        assert_eq!(again.len(), output.len());
+        assert_eq!(again.windows(4).filter(|w| w == b"covr").count(), 1);
+    }
+}
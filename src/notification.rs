@@ -0,0 +1,29 @@
+use log::debug;
+use notify_rust::Notification;
+
+use crate::cli::Platform;
+
+/// Show a desktop notification for a finished upload.
+/// Systems without a notification service are skipped silently.
+pub fn upload_finished(title: &str, platforms: &[Platform], error: Option<&str>) {
+    let names: Vec<String> = platforms.iter().map(|p| p.to_string()).collect();
+    let (summary, body) = match error {
+        None => (
+            format!("✓ Uploaded '{}' to {}", title, names.join(" and ")),
+            String::new(),
+        ),
+        Some(error) => (
+            "✗ Upload failed".to_string(),
+            format!("{}: {}", title, error),
+        ),
+    };
+
+    let result = Notification::new()
+        .appname("DJ Uploader")
+        .summary(&summary)
+        .body(&body)
+        .show();
+    if let Err(e) = result {
+        debug!("Desktop notification not shown: {}", e);
+    }
+}
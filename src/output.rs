@@ -0,0 +1,55 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+use crate::cli::ExistingOutput;
+
+/// Whether to write `path` given what to do with existing outputs.
+/// Ok(false) means the existing file should be used as-is.
+pub fn should_write(path: &Path, policy: ExistingOutput) -> Result<bool> {
+    if !path.exists() {
+        return Ok(true);
+    }
+
+    match policy {
+        ExistingOutput::Overwrite => Ok(true),
+        ExistingOutput::Skip => {
+            println!("✓ Keeping existing {}", path.display());
+            Ok(false)
+        }
+        ExistingOutput::Error => bail!(
+            "Output already exists: {}. Use --replace-if-exists overwrite or skip",
+            path.display()
+        ),
+    }
+}
+
+/// Bytes available to this user on the volume holding `dir`
+pub fn free_space(dir: &Path) -> Result<u64> {
+    fs2::available_space(dir)
+        .with_context(|| format!("Failed to check free space in {}", dir.display()))
+}
+
+/// Fail before writing anything if `needed` bytes plus `margin_mb` wouldn't fit
+pub fn ensure_free_space(dir: &Path, needed: u64, margin_mb: u64) -> Result<()> {
+    let available = free_space(dir)?;
+    let required = needed + margin_mb * 1024 * 1024;
+    if available < required {
+        bail!(
+            "Insufficient disk space in {}: need ~{}, have {}",
+            dir.display(),
+            format_size(required),
+            format_size(available)
+        );
+    }
+    Ok(())
+}
+
+/// Size in MB, or GB from 1024 MB up
+pub fn format_size(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
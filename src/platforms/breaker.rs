@@ -0,0 +1,191 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cli::Platform;
+use crate::config::config_dir;
+
+/// Consecutive auth failures before uploads to a platform are refused
+const TRIP_AFTER: u32 = 3;
+/// How long uploads are refused once the breaker trips
+const COOL_DOWN_HOURS: i64 = 6;
+
+/// Auth failures for one platform
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlatformState {
+    pub consecutive_failures: u32,
+    /// Uploads are refused until then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_until: Option<DateTime<Utc>>,
+}
+
+impl PlatformState {
+    fn record(&mut self, auth_failed: bool, now: DateTime<Utc>) {
+        if !auth_failed {
+            *self = Self::default();
+            return;
+        }
+        self.consecutive_failures += 1;
+        // Still failing after a cool-down trips it again straight away
+        if self.consecutive_failures >= TRIP_AFTER {
+            self.open_until = Some(now + Duration::hours(COOL_DOWN_HOURS));
+        }
+    }
+
+    fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+}
+
+/// Circuit breaker state per platform, kept in breaker.json so it survives
+/// between runs from cron
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Breaker {
+    pub platforms: BTreeMap<String, PlatformState>,
+}
+
+impl Breaker {
+    pub fn path() -> Result<PathBuf> {
+        Ok(config_dir()?.join("breaker.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Refuse to talk to `platform` while its breaker is open
+pub fn check(platform: Platform) -> Result<()> {
+    // A damaged breaker.json mustn't block every upload; `record` rewrites it
+    let breaker = match Breaker::load() {
+        Ok(breaker) => breaker,
+        Err(e) => {
+            eprintln!("⚠ Warning: {:#}, ignoring the auth failure count", e);
+            return Ok(());
+        }
+    };
+    if let Some(state) = breaker.platforms.get(&platform.to_string())
+        && state.is_open(Utc::now())
+        && let Some(until) = state.open_until
+    {
+        bail!(
+            "Too many {} auth failures ({} in a row) — re-authorize and reset with \
+             `dj-uploader reset-breaker`, or wait until {}",
+            platform,
+            state.consecutive_failures,
+            until.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+/// Count an upload response towards the breaker: 401 and 403 are auth
+/// failures, a success resets it, anything else says nothing about auth
+pub fn observe_upload(platform: Platform, status: StatusCode) {
+    if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+        record(platform, true);
+    } else if status.is_success() {
+        record(platform, false);
+    }
+}
+
+/// Count a token refresh response; a refresh token that's rejected outright
+/// (400, 401 or 403) is an auth failure, while a 429 only means slow down
+pub fn observe_refresh(platform: Platform, status: StatusCode) {
+    if matches!(
+        status,
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+    ) {
+        record(platform, true);
+    }
+}
+
+fn record(platform: Platform, auth_failed: bool) {
+    let mut breaker = Breaker::load().unwrap_or_default();
+    let key = platform.to_string();
+    if !auth_failed && !breaker.platforms.contains_key(&key) {
+        return;
+    }
+
+    let state = breaker.platforms.entry(key).or_default();
+    state.record(auth_failed, Utc::now());
+    if state.consecutive_failures >= TRIP_AFTER {
+        eprintln!(
+            "⚠ Warning: {} auth failed {} times in a row, pausing uploads for {}h",
+            platform, state.consecutive_failures, COOL_DOWN_HOURS
+        );
+    }
+    if *state == PlatformState::default() {
+        breaker.platforms.remove(&platform.to_string());
+    }
+
+    // Losing a count is harmless, so none of this fails the upload
+    if let Err(e) = breaker.save() {
+        debug!("Failed to save auth failure count: {:#}", e);
+    }
+}
+
+/// Close the breaker for one platform, or all of them. Works on a damaged
+/// breaker.json too, since that's when a reset is most needed.
+pub fn reset(platform: Option<Platform>) -> Result<()> {
+    let Some(platform) = platform else {
+        let path = Breaker::path()?;
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        };
+    };
+    // The other platforms' counts are lost with a file that doesn't parse
+    let mut breaker = Breaker::load().unwrap_or_default();
+    breaker.platforms.remove(&platform.to_string());
+    breaker.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trips_after_consecutive_failures() {
+        let now = Utc::now();
+        let mut state = PlatformState::default();
+        for _ in 1..TRIP_AFTER {
+            state.record(true, now);
+            assert!(!state.is_open(now));
+        }
+        state.record(true, now);
+        assert!(state.is_open(now));
+        assert!(!state.is_open(now + Duration::hours(COOL_DOWN_HOURS)));
+
+        // One more failure after the cool-down reopens it
+        let later = now + Duration::hours(COOL_DOWN_HOURS + 1);
+        state.record(true, later);
+        assert!(state.is_open(later));
+
+        state.record(false, later);
+        assert_eq!(state, PlatformState::default());
+    }
+}
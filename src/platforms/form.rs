@@ -0,0 +1,322 @@
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::progress::{ProgressFn, ProgressReader};
+use crate::cli::Platform;
+use crate::config::{FieldNameOverrides, Settings};
+
+/// Multipart field names a platform expects.
+/// Defaults match the current APIs; see [`FieldNameOverrides`] to change them.
+#[derive(Debug, Clone)]
+pub struct FieldNames {
+    pub audio: String,
+    pub title: String,
+    pub description: String,
+    pub image: String,
+    /// For Mixcloud, `{index}` is replaced with each tag's position
+    pub tags: String,
+    /// Mixcloud only
+    pub publish_date: String,
+    /// Mixcloud only
+    pub unlisted: String,
+    /// SoundCloud only
+    pub sharing: String,
+    /// SoundCloud only
+    pub isrc: String,
+    /// SoundCloud only
+    pub language: String,
+    /// SoundCloud only
+    pub track_type: String,
+}
+
+impl FieldNames {
+    fn defaults(platform: Platform) -> Self {
+        match platform {
+            Platform::Mixcloud => Self {
+                audio: "mp3".to_string(),
+                title: "name".to_string(),
+                description: "description".to_string(),
+                image: "picture".to_string(),
+                tags: "tags-{index}-tag".to_string(),
+                publish_date: "publish_date".to_string(),
+                unlisted: "unlisted".to_string(),
+                sharing: String::new(),
+                isrc: String::new(),
+                language: String::new(),
+                track_type: String::new(),
+            },
+            Platform::Soundcloud => Self {
+                audio: "track[asset_data]".to_string(),
+                title: "track[title]".to_string(),
+                description: "track[description]".to_string(),
+                image: "track[artwork_data]".to_string(),
+                tags: "track[tag_list]".to_string(),
+                publish_date: String::new(),
+                unlisted: String::new(),
+                sharing: "track[sharing]".to_string(),
+                isrc: "track[isrc]".to_string(),
+                language: "track[language]".to_string(),
+                track_type: "track[track_type]".to_string(),
+            },
+        }
+    }
+
+    /// Built-in names for a platform with any overrides from settings.json
+    pub fn for_platform(platform: Platform) -> Result<Self> {
+        let settings = Settings::load()?;
+        let overrides = match platform {
+            Platform::Mixcloud => &settings.mixcloud_fields,
+            Platform::Soundcloud => &settings.soundcloud_fields,
+        };
+        Ok(Self::defaults(platform).with_overrides(overrides))
+    }
+
+    fn with_overrides(mut self, overrides: &FieldNameOverrides) -> Self {
+        let pairs = [
+            (&mut self.audio, &overrides.audio),
+            (&mut self.title, &overrides.title),
+            (&mut self.description, &overrides.description),
+            (&mut self.image, &overrides.image),
+            (&mut self.tags, &overrides.tags),
+            (&mut self.publish_date, &overrides.publish_date),
+            (&mut self.unlisted, &overrides.unlisted),
+            (&mut self.sharing, &overrides.sharing),
+            (&mut self.isrc, &overrides.isrc),
+            (&mut self.language, &overrides.language),
+            (&mut self.track_type, &overrides.track_type),
+        ];
+        for (name, value) in pairs {
+            if let Some(value) = value {
+                *name = value.clone();
+            }
+        }
+        self
+    }
+}
+
+/// One multipart field, independent of the HTTP client that sends it
+#[derive(Clone)]
+pub enum FormField {
+    Text {
+        name: String,
+        value: String,
+    },
+    File {
+        name: String,
+        /// Shared, so cloning the form for a retry doesn't copy the audio
+        bytes: Bytes,
+        file_name: String,
+        mime: Option<&'static str>,
+    },
+}
+
+/// Length of the boundary reqwest generates: four 16-digit hex groups
+const BOUNDARY_LEN: u64 = 67;
+
+/// Longest text value shown in full by [`UploadForm::describe`]
+const DESCRIBE_TEXT_LEN: usize = 60;
+
+impl FormField {
+    /// Bytes this field takes up in a multipart body, headers included
+    fn encoded_len(&self) -> u64 {
+        // "--<boundary>\r\n", headers, "\r\n", data, "\r\n"
+        let framing = 2 + BOUNDARY_LEN + 2 + 2 + 2;
+        match self {
+            FormField::Text { name, value } => {
+                let disposition = format!("Content-Disposition: form-data; name=\"{}\"\r\n", name);
+                framing + disposition.len() as u64 + value.len() as u64
+            }
+            FormField::File {
+                name,
+                bytes,
+                file_name,
+                mime,
+            } => {
+                let disposition = format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                    name, file_name
+                );
+                let content_type = mime.map_or(0, |m| format!("Content-Type: {}\r\n", m).len());
+                framing + (disposition.len() + content_type) as u64 + bytes.len() as u64
+            }
+        }
+    }
+}
+
+/// Upload form fields plus the sizes needed to verify the upload afterwards
+#[derive(Clone)]
+pub struct UploadForm {
+    pub fields: Vec<FormField>,
+    /// Size of the audio file when the upload started
+    pub size_before: u64,
+    /// Bytes of audio actually put in the form
+    pub bytes_sent: u64,
+    /// Reports how much of the audio has been sent (blocking uploads only)
+    pub progress: Option<ProgressFn>,
+}
+
+impl UploadForm {
+    /// Start a form with the audio file as its first field
+    pub fn with_audio(field_name: &str, file_path: &Path) -> Result<Self> {
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .context("Invalid file name")?
+            .to_string();
+
+        let size_before = fs::metadata(file_path)
+            .context("Failed to read audio file")?
+            .len();
+        let bytes = Bytes::from(fs::read(file_path).context("Failed to read audio file")?);
+        let bytes_sent = bytes.len() as u64;
+
+        // Unreadable files keep the old label; the platform decides what to do with them
+        let mime = crate::audio::audio_mime(file_path).unwrap_or("audio/mpeg");
+
+        Ok(Self {
+            fields: vec![FormField::File {
+                name: field_name.to_string(),
+                bytes,
+                file_name,
+                mime: Some(mime),
+            }],
+            size_before,
+            bytes_sent,
+            progress: None,
+        })
+    }
+
+    pub fn text(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.fields.push(FormField::Text {
+            name: name.into(),
+            value: value.into(),
+        });
+    }
+
+    /// Add an image file, named `default_name` if the path has no file name
+    pub fn image(&mut self, name: &str, image_path: &Path, default_name: &str) -> Result<()> {
+        let bytes = Bytes::from(fs::read(image_path).context("Failed to read image file")?);
+        let file_name = image_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(default_name)
+            .to_string();
+
+        self.fields.push(FormField::File {
+            name: name.to_string(),
+            bytes,
+            file_name,
+            mime: crate::cover::image_mime(image_path),
+        });
+        Ok(())
+    }
+
+    /// Estimated size of the multipart request body
+    pub fn body_len(&self) -> u64 {
+        // Plus the closing "--<boundary>--\r\n"
+        self.fields.iter().map(FormField::encoded_len).sum::<u64>() + BOUNDARY_LEN + 6
+    }
+
+    /// Every field as it would be sent, followed by the estimated body size.
+    /// Long text values are shortened; file contents are summarized.
+    pub fn describe(&self) -> String {
+        let mut lines = Vec::new();
+        for field in &self.fields {
+            lines.push(match field {
+                FormField::Text { name, value } => {
+                    let len = value.chars().count();
+                    if len > DESCRIBE_TEXT_LEN {
+                        let start: String = value.chars().take(DESCRIBE_TEXT_LEN).collect();
+                        format!("  {} = {:?}… ({} characters)", name, start, len)
+                    } else {
+                        format!("  {} = {:?}", name, value)
+                    }
+                }
+                FormField::File {
+                    name,
+                    bytes,
+                    file_name,
+                    mime,
+                } => format!(
+                    "  {} = file {:?}, {}, {} bytes",
+                    name,
+                    file_name,
+                    mime.unwrap_or("no content type"),
+                    bytes.len()
+                ),
+            });
+        }
+
+        let total = self.body_len();
+        lines.push(format!(
+            "{} fields, request body ≈ {} ({} bytes)",
+            self.fields.len(),
+            crate::output::format_size(total),
+            total
+        ));
+        lines.join("\n")
+    }
+
+    pub fn into_blocking(self) -> reqwest::Result<reqwest::blocking::multipart::Form> {
+        use reqwest::blocking::multipart::{Form, Part};
+
+        let mut form = Form::new();
+        for field in self.fields {
+            form = match field {
+                FormField::Text { name, value } => form.text(name, value),
+                FormField::File {
+                    name,
+                    bytes,
+                    file_name,
+                    mime,
+                } => {
+                    let is_audio = mime.is_some_and(|m| m.starts_with("audio/"));
+                    let len = bytes.len() as u64;
+                    let reader = io::Cursor::new(bytes);
+                    let mut part = match &self.progress {
+                        Some(progress) if is_audio => Part::reader_with_length(
+                            ProgressReader::new(reader, len, progress.clone()),
+                            len,
+                        ),
+                        _ => Part::reader_with_length(reader, len),
+                    }
+                    .file_name(file_name);
+                    if let Some(mime) = mime {
+                        part = part.mime_str(mime)?;
+                    }
+                    form.part(name, part)
+                }
+            };
+        }
+        Ok(form)
+    }
+
+    #[cfg(feature = "async")]
+    pub fn into_async(self) -> Result<reqwest::multipart::Form> {
+        use reqwest::multipart::{Form, Part};
+
+        let mut form = Form::new();
+        for field in self.fields {
+            form = match field {
+                FormField::Text { name, value } => form.text(name, value),
+                FormField::File {
+                    name,
+                    bytes,
+                    file_name,
+                    mime,
+                } => {
+                    let len = bytes.len() as u64;
+                    let mut part = Part::stream_with_length(bytes, len).file_name(file_name);
+                    if let Some(mime) = mime {
+                        part = part.mime_str(mime)?;
+                    }
+                    form.part(name, part)
+                }
+            };
+        }
+        Ok(form)
+    }
+}
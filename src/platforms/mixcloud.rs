@@ -1,21 +1,72 @@
 use anyhow::{Context, Result, bail};
 use log::{debug, info, warn};
-use reqwest::blocking::{Client, multipart};
+use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::io::{BufRead, BufReader, Write};
-use std::net::TcpListener;
-use std::path::Path;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use url::Url;
 
-use crate::config::{MixcloudCredentials, TokenInfo, TokenStorage};
+use crate::cli::Platform;
+use crate::config::{Credentials, TokenInfo, TokenStorage, resolve_credentials};
+use crate::platforms::form::{FieldNames, UploadForm};
+use crate::platforms::progress::ProgressFn;
+use crate::platforms::{AuthCancel, AuthClient, Deadline, UploadOutcome, UploadRequest};
 
-const OAUTH_AUTHORIZE_URL: &str = "https://www.mixcloud.com/oauth/authorize";
+pub const OAUTH_AUTHORIZE_URL: &str = "https://www.mixcloud.com/oauth/authorize";
 const OAUTH_TOKEN_URL: &str = "https://www.mixcloud.com/oauth/access_token";
 const UPLOAD_URL: &str = "https://api.mixcloud.com/upload/";
+const ME_URL: &str = "https://api.mixcloud.com/me/";
+/// Local port the OAuth redirect comes back to
+pub const CALLBACK_PORT: u16 = 8888;
 const REDIRECT_URI: &str = "http://localhost:8888/callback";
 
+/// Shown in the browser once authorization succeeds, unless
+/// `auth_success_page` in settings.json points at a page of your own
+const SUCCESS_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>Authorization Successful</title>
+    <style>
+        body {
+            font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
+            display: flex;
+            justify-content: center;
+            align-items: center;
+            height: 100vh;
+            margin: 0;
+            background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+            color: white;
+        }
+        .container {
+            text-align: center;
+            padding: 2rem;
+            background: rgba(255, 255, 255, 0.1);
+            border-radius: 10px;
+            backdrop-filter: blur(10px);
+        }
+        h1 { margin: 0 0 1rem 0; }
+        p { margin: 0; opacity: 0.9; }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>✓ Authorization Successful!</h1>
+        <p>You can close this window and return to the terminal.</p>
+        <p style="margin-top: 1rem; font-size: 0.9em;">This window will close automatically...</p>
+    </div>
+    <script>
+        // Auto-close after 2 seconds
+        setTimeout(function() {
+            window.close();
+        }, 2000);
+    </script>
+</body>
+</html>
+"#;
+
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
     access_token: String,
@@ -28,6 +79,12 @@ struct TokenResponse {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadResponse {
     pub result: UploadResult,
+    /// Set when the bytes sent didn't match the file size
+    #[serde(skip)]
+    pub size_warning: Option<String>,
+    /// The response exactly as the API returned it
+    #[serde(skip)]
+    pub raw: serde_json::Value,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,40 +94,66 @@ pub struct UploadResult {
     pub key: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MixcloudUser {
+    pub username: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_pro: bool,
+}
+
 pub struct MixcloudClient {
     client: Client,
-    credentials: MixcloudCredentials,
-    token_storage: TokenStorage,
+    credentials: Credentials,
+    /// Named account to use, or None for the default account
+    account: Option<String>,
+    fields: FieldNames,
+    /// Overall time budget for the current upload
+    deadline: Option<Deadline>,
+    progress: Option<ProgressFn>,
 }
 
 impl MixcloudClient {
-    pub fn new() -> Result<Self> {
+    /// Create a client bound to a named account, or the default one for None
+    pub fn for_account(account: Option<&str>) -> Result<Self> {
         let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(300))
+            .timeout(super::REQUEST_TIMEOUT)
             .build()
             .context("Failed to create HTTP client")?;
 
-        let credentials = MixcloudCredentials::new();
-        let token_storage = TokenStorage::load()?;
+        let credentials = resolve_credentials(Platform::Mixcloud)?;
 
         Ok(Self {
             client,
             credentials,
-            token_storage,
+            account: account.map(str::to_string),
+            fields: FieldNames::for_platform(Platform::Mixcloud)?,
+            deadline: None,
+            progress: None,
         })
     }
 
-    pub fn authorize() -> Result<()> {
-        info!("Starting Mixcloud OAuth2 authorization...");
+    /// URL the user visits to grant access
+    pub fn authorization_url() -> Result<Url> {
+        let credentials = resolve_credentials(Platform::Mixcloud)?;
 
-        let credentials = MixcloudCredentials::new();
-
-        // Build authorization URL
         let mut auth_url = Url::parse(OAUTH_AUTHORIZE_URL)?;
         auth_url
             .query_pairs_mut()
             .append_pair("client_id", &credentials.client_id)
             .append_pair("redirect_uri", REDIRECT_URI);
+        Ok(auth_url)
+    }
+
+    /// Run the OAuth flow; `cancel` can abort it from another thread
+    pub fn authorize(account: Option<&str>, cancel: &AuthCancel) -> Result<()> {
+        info!("Starting Mixcloud OAuth2 authorization...");
+
+        let credentials = resolve_credentials(Platform::Mixcloud)?;
+        // Check a custom page before the browser opens, not after
+        let success_page = super::auth_success_page(SUCCESS_PAGE)?;
+        let auth_url = Self::authorization_url()?;
 
         println!("\nOpening browser for authorization...");
         println!("If the browser doesn't open, visit this URL:\n");
@@ -82,12 +165,11 @@ impl MixcloudClient {
         }
 
         // Start local server to receive callback
-        let listener = TcpListener::bind("127.0.0.1:8888")
-            .context("Failed to start callback server. Is port 8888 already in use?")?;
+        let listener = super::bind_callback(CALLBACK_PORT)?;
 
         println!("Waiting for authorization...");
 
-        let (mut stream, _) = listener.accept()?;
+        let mut stream = cancel.accept(&listener)?;
         let buf_reader = BufReader::new(&stream);
         let request_line = buf_reader
             .lines()
@@ -98,57 +180,8 @@ impl MixcloudClient {
         // Parse the authorization code from the request
         let code = Self::extract_code_from_request(&request_line)?;
 
-        // Send success response to browser with auto-close script
-        let html = r#"
-<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <title>Authorization Successful</title>
-    <style>
-        body {
-            font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
-            display: flex;
-            justify-content: center;
-            align-items: center;
-            height: 100vh;
-            margin: 0;
-            background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
-            color: white;
-        }
-        .container {
-            text-align: center;
-            padding: 2rem;
-            background: rgba(255, 255, 255, 0.1);
-            border-radius: 10px;
-            backdrop-filter: blur(10px);
-        }
-        h1 { margin: 0 0 1rem 0; }
-        p { margin: 0; opacity: 0.9; }
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>✓ Authorization Successful!</h1>
-        <p>You can close this window and return to the terminal.</p>
-        <p style="margin-top: 1rem; font-size: 0.9em;">This window will close automatically...</p>
-    </div>
-    <script>
-        // Auto-close after 2 seconds
-        setTimeout(function() {
-            window.close();
-        }, 2000);
-    </script>
-</body>
-</html>
-"#;
-
-        let response = format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
-            html.len(),
-            html
-        );
-        stream.write_all(response.as_bytes())?;
+        // Send success response to browser
+        super::respond_html(&mut stream, &success_page)?;
 
         info!("Received authorization code, exchanging for access token...");
 
@@ -160,20 +193,24 @@ impl MixcloudClient {
         params.insert("redirect_uri", REDIRECT_URI.to_string());
         params.insert("code", code);
 
+        super::log_request(
+            "POST",
+            OAUTH_TOKEN_URL,
+            super::RequestAuth::ClientCredentials,
+        );
         let response = client
             .post(OAUTH_TOKEN_URL)
             .form(&params)
             .send()
-            .context("Failed to exchange authorization code")?;
+            .map_err(|e| super::network_error("Failed to exchange authorization code", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = super::response_text(response).unwrap_or_default();
             bail!("Token exchange failed with status {}: {}", status, body);
         }
 
-        let token_response: TokenResponse =
-            response.json().context("Failed to parse token response")?;
+        let token_response: TokenResponse = super::parse_json(response, "token")?;
 
         // Save tokens to storage
         let token_info = TokenInfo::new(
@@ -182,12 +219,9 @@ impl MixcloudClient {
             token_response.expires_in,
         );
 
-        let mut storage = TokenStorage::load().unwrap_or(TokenStorage {
-            mixcloud: None,
-            soundcloud: None,
-        });
-        storage.set_mixcloud_tokens(token_info);
-        storage.save()?;
+        TokenStorage::update_shared(|storage| {
+            storage.set_token(Platform::Mixcloud, account, token_info)
+        })?;
 
         // Bring the app back to the foreground
         super::activate_app();
@@ -230,8 +264,15 @@ impl MixcloudClient {
         Ok(code)
     }
 
+    fn token(&self) -> Result<TokenInfo> {
+        TokenStorage::shared()?
+            .token(Platform::Mixcloud, self.account.as_deref())
+            .cloned()
+            .context("Not authorized with Mixcloud. Run 'dj-uploader auth mixcloud' first")
+    }
+
     fn refresh_token_if_needed(&mut self) -> Result<()> {
-        let token_info = self.token_storage.get_mixcloud_token()?;
+        let token_info = self.token()?;
 
         if token_info.is_expired() {
             warn!("Access token is expired or expiring soon, refreshing...");
@@ -246,16 +287,28 @@ impl MixcloudClient {
             params.insert("grant_type", "refresh_token".to_string());
             params.insert("refresh_token", refresh_token.clone());
 
-            let response = self
-                .client
-                .post(OAUTH_TOKEN_URL)
-                .form(&params)
-                .send()
-                .context("Failed to refresh token")?;
+            super::log_request(
+                "POST",
+                OAUTH_TOKEN_URL,
+                super::RequestAuth::ClientCredentials,
+            );
+            let response = super::send_with_retry(
+                self.deadline,
+                "Failed to refresh token",
+                true,
+                |timeout| {
+                    self.client
+                        .post(OAUTH_TOKEN_URL)
+                        .timeout(timeout)
+                        .form(&params)
+                        .send()
+                },
+            )?;
 
             if !response.status().is_success() {
                 let status = response.status();
-                let body = response.text().unwrap_or_default();
+                super::breaker::observe_refresh(Platform::Mixcloud, status);
+                let body = super::response_text(response).unwrap_or_default();
                 bail!(
                     "Token refresh failed with status {}: {}. Please re-authorize.",
                     status,
@@ -263,19 +316,19 @@ impl MixcloudClient {
                 );
             }
 
-            let token_response: TokenResponse = response
-                .json()
-                .context("Failed to parse token refresh response")?;
+            let token_response: TokenResponse = super::parse_json(response, "token refresh")?;
 
             // Update token storage
             let new_token_info = TokenInfo::new(
                 token_response.access_token,
                 token_response.refresh_token.or(Some(refresh_token.clone())),
                 token_response.expires_in,
-            );
+            )
+            .with_profile_cache(&token_info);
 
-            self.token_storage.set_mixcloud_tokens(new_token_info);
-            self.token_storage.save()?;
+            TokenStorage::update_shared(|storage| {
+                storage.set_token(Platform::Mixcloud, self.account.as_deref(), new_token_info)
+            })?;
 
             info!("Token refreshed successfully");
         }
@@ -283,121 +336,394 @@ impl MixcloudClient {
         Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn upload(
+    /// Fetch the authorized user's profile
+    pub fn me(&mut self) -> Result<MixcloudUser> {
+        self.refresh_token_if_needed()?;
+
+        let token_info = self.token()?;
+
+        super::log_request("GET", ME_URL, super::RequestAuth::QueryToken);
+        let response = self
+            .client
+            .get(ME_URL)
+            .query(&[("access_token", &token_info.access_token)])
+            .send()
+            .map_err(|e| super::network_error("Failed to fetch Mixcloud profile", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = super::response_text(response).unwrap_or_default();
+            bail!("Profile request failed with status {}: {}", status, body);
+        }
+
+        super::parse_json(response, "Mixcloud profile")
+    }
+
+    /// Fail fast when scheduling on an account that can't schedule
+    fn ensure_pro_for_scheduling(&mut self) -> Result<()> {
+        match self.me() {
+            Ok(user) if !user.is_pro => bail!(
+                "Scheduling requires Mixcloud Pro ({} is a free account). \
+                 Remove --publish-date, or pass --assume-pro to skip this check",
+                user.username
+            ),
+            Ok(_) => Ok(()),
+            Err(e) => {
+                warn!("Could not verify Mixcloud Pro status: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Make sure we're authorized and allowed to upload before building the form
+    fn prepare_upload(
         &mut self,
         file_path: &Path,
-        title: &str,
-        description: Option<&str>,
-        image_path: Option<&Path>,
-        tags: Option<Vec<String>>,
         publish_date: Option<&str>,
-    ) -> Result<UploadResponse> {
+        assume_pro: bool,
+    ) -> Result<()> {
+        super::breaker::check(Platform::Mixcloud)?;
+
         // Check if we have a token, if not, authorize first
-        if self.token_storage.mixcloud.is_none() {
+        if self.token().is_err() {
             println!("\nNo authorization found. Starting OAuth2 flow...\n");
-            Self::authorize()?;
-            // Reload token storage after authorization
-            self.token_storage = TokenStorage::load()?;
+            Self::authorize(self.account.as_deref(), &AuthCancel::default())?;
         }
 
         // Refresh token if needed
         self.refresh_token_if_needed()?;
 
-        let token_info = self.token_storage.get_mixcloud_token()?;
-
-        info!("Uploading {} to Mixcloud...", file_path.display());
-
         if !file_path.exists() {
             bail!("File not found: {}", file_path.display());
         }
 
-        // Build multipart form
-        let mut form = multipart::Form::new();
-
-        // Add audio file
-        let file_name = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .context("Invalid file name")?
-            .to_string();
-
-        let file_bytes = fs::read(file_path).context("Failed to read audio file")?;
+        if publish_date.is_some() && !assume_pro {
+            self.ensure_pro_for_scheduling()?;
+        }
 
-        let file_part = multipart::Part::bytes(file_bytes)
-            .file_name(file_name.clone())
-            .mime_str("audio/mpeg")?;
+        Ok(())
+    }
 
-        form = form.part("mp3", file_part);
+    #[allow(clippy::too_many_arguments)]
+    fn upload_form(
+        &self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        publish_date: Option<&str>,
+        unlisted: bool,
+    ) -> Result<UploadForm> {
+        let fields = &self.fields;
+        let mut form = UploadForm::with_audio(&fields.audio, file_path)?;
+        form.progress = self.progress.clone();
 
         // Add metadata
-        form = form.text("name", title.to_string());
+        form.text(&fields.title, title);
 
         if let Some(desc) = description {
-            form = form.text("description", desc.to_string());
+            form.text(&fields.description, desc);
         }
 
-        // Add cover image if provided
-        if let Some(img_path) = image_path
-            && img_path.exists()
+        // Add as many images as the platform accepts, primary first
+        for img_path in image_paths
+            .iter()
+            .take(super::max_images(Platform::Mixcloud))
         {
-            let img_bytes = fs::read(img_path).context("Failed to read image file")?;
-
-            let img_name = img_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("cover.jpg")
-                .to_string();
-
-            let img_part = multipart::Part::bytes(img_bytes).file_name(img_name);
-
-            form = form.part("picture", img_part);
+            if img_path.exists() {
+                form.image(&fields.image, img_path, "cover.jpg")?;
+            }
         }
 
         // Add tags if provided (Mixcloud expects tags-0-tag, tags-1-tag, etc.)
         if let Some(tag_list) = tags {
             for (index, tag) in tag_list.iter().enumerate() {
-                let field_name = format!("tags-{}-tag", index);
-                form = form.text(field_name, tag.to_string());
+                form.text(
+                    fields.tags.replace("{index}", &index.to_string()),
+                    tag.as_str(),
+                );
             }
         }
 
         // Add publish_date if provided (Pro accounts only)
         if let Some(date) = publish_date {
-            form = form.text("publish_date", date.to_string());
+            form.text(&fields.publish_date, date);
             debug!("Scheduling publish for: {}", date);
         }
 
-        debug!("Sending upload request...");
+        // Keep the upload off the public profile; only the link works
+        if unlisted {
+            form.text(&fields.unlisted, "1");
+        }
 
-        // Send upload request with OAuth token
-        let response = self
-            .client
-            .post(UPLOAD_URL)
-            .query(&[("access_token", &token_info.access_token)])
-            .multipart(form)
-            .send()
-            .context("Failed to upload file")?;
+        Ok(form)
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().unwrap_or_default();
+    /// Turn the upload response into a result and check the bytes sent
+    fn finish_upload(
+        file_path: &Path,
+        form_sizes: (u64, u64),
+        status: reqwest::StatusCode,
+        body: String,
+        publish_date: Option<&str>,
+    ) -> Result<UploadResponse> {
+        if !status.is_success() {
+            if publish_date.is_some() && body.contains("publish_date") {
+                bail!(
+                    "Mixcloud rejected the publish date (scheduling requires Mixcloud Pro): {}",
+                    body
+                );
+            }
             bail!("Upload failed with status {}: {}", status, body);
         }
 
-        // Get response text first for debugging
-        let response_text = response.text().context("Failed to read response body")?;
-
         // Always print the response so we can see what Mixcloud returns
         println!("\nMixcloud API Response:");
-        println!("{}", response_text);
+        println!("{}", body);
         println!();
 
-        let upload_response: UploadResponse =
-            serde_json::from_str(&response_text).context("Failed to parse upload response")?;
+        let mut upload_response: UploadResponse =
+            serde_json::from_str(&body).context("Failed to parse upload response")?;
+        upload_response.raw = serde_json::from_str(&body).unwrap_or_default();
+
+        let (size_before, bytes_sent) = form_sizes;
+        upload_response.size_warning = super::check_upload_size(file_path, size_before, bytes_sent);
+        if let Some(warning) = &upload_response.size_warning {
+            warn!("{}", warning);
+        }
 
         info!("Upload successful!");
 
         Ok(upload_response)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn upload(
+        &mut self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        publish_date: Option<&str>,
+        assume_pro: bool,
+        unlisted: bool,
+    ) -> Result<UploadResponse> {
+        self.prepare_upload(file_path, publish_date, assume_pro)?;
+
+        let token_info = self.token()?;
+
+        info!("Uploading {} to Mixcloud...", file_path.display());
+
+        let form = self.upload_form(
+            file_path,
+            title,
+            description,
+            image_paths,
+            tags,
+            publish_date,
+            unlisted,
+        )?;
+        let form_sizes = (form.size_before, form.bytes_sent);
+        let body_len = form.body_len();
+
+        debug!("Sending upload request...");
+
+        // Send upload request with OAuth token
+        super::log_request("POST", UPLOAD_URL, super::RequestAuth::QueryToken);
+        let response =
+            super::send_with_retry(self.deadline, "Failed to upload file", false, |timeout| {
+                let request = self
+                    .client
+                    .post(UPLOAD_URL)
+                    .timeout(timeout)
+                    .query(&[("access_token", &token_info.access_token)])
+                    .multipart(form.clone().into_blocking()?);
+                let started = Instant::now();
+                let result = request.send();
+                super::throughput::record_attempt(
+                    Platform::Mixcloud,
+                    body_len,
+                    started.elapsed(),
+                    result.is_ok(),
+                );
+                result
+            })?;
+
+        let status = response.status();
+        super::breaker::observe_upload(Platform::Mixcloud, status);
+        let headers = response.headers().clone();
+        let body = if status.is_success() {
+            super::response_text(response).context("Failed to read response body")?
+        } else {
+            super::response_text(response).unwrap_or_default()
+        };
+        super::check_rate_limit(status, &headers)?;
+        let content_type = super::content_type(&headers);
+        if status.is_success() {
+            super::ensure_json(content_type.as_deref(), &body, "upload")?;
+        }
+
+        Self::finish_upload(file_path, form_sizes, status, body, publish_date)
+    }
+
+    /// Non-blocking variant of [`upload`](Self::upload).
+    /// Authorization and token refresh still run on the blocking client, so
+    /// this must be called from a multi-threaded tokio runtime.
+    #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upload_async(
+        &mut self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        publish_date: Option<&str>,
+        assume_pro: bool,
+        unlisted: bool,
+    ) -> Result<UploadResponse> {
+        tokio::task::block_in_place(|| self.prepare_upload(file_path, publish_date, assume_pro))?;
+
+        let access_token = self.token()?.access_token;
+
+        info!("Uploading {} to Mixcloud...", file_path.display());
+
+        let form = self.upload_form(
+            file_path,
+            title,
+            description,
+            image_paths,
+            tags,
+            publish_date,
+            unlisted,
+        )?;
+        let form_sizes = (form.size_before, form.bytes_sent);
+        let body_len = form.body_len();
+
+        let client = reqwest::Client::builder()
+            .timeout(super::REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        super::log_request("POST", UPLOAD_URL, super::RequestAuth::QueryToken);
+        let started = Instant::now();
+        let result = client
+            .post(UPLOAD_URL)
+            .timeout(super::request_timeout(self.deadline)?)
+            .query(&[("access_token", &access_token)])
+            .multipart(form.into_async()?)
+            .send()
+            .await;
+        super::throughput::record_attempt(
+            Platform::Mixcloud,
+            body_len,
+            started.elapsed(),
+            result.is_ok(),
+        );
+        let response = result.map_err(|e| super::network_error("Failed to upload file", e))?;
+
+        let status = response.status();
+        super::breaker::observe_upload(Platform::Mixcloud, status);
+        let headers = response.headers().clone();
+        let body = if status.is_success() {
+            super::response_text_async(response)
+                .await
+                .context("Failed to read response body")?
+        } else {
+            super::response_text_async(response)
+                .await
+                .unwrap_or_default()
+        };
+        super::check_rate_limit(status, &headers)?;
+        let content_type = super::content_type(&headers);
+        if status.is_success() {
+            super::ensure_json(content_type.as_deref(), &body, "upload")?;
+        }
+
+        Self::finish_upload(file_path, form_sizes, status, body, publish_date)
+    }
+}
+
+impl AuthClient for MixcloudClient {
+    fn describe_upload(&self, request: &UploadRequest) -> Result<String> {
+        let form = self.upload_form(
+            request.file_path,
+            request.title,
+            request.description,
+            request.image_paths,
+            request.tags.clone(),
+            request.publish_date,
+            request.unlisted,
+        )?;
+        Ok(format!(
+            "POST {}?access_token=<redacted>\n{}",
+            UPLOAD_URL,
+            form.describe()
+        ))
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Mixcloud
+    }
+
+    fn authorize(&mut self) -> Result<()> {
+        Self::authorize(self.account.as_deref(), &AuthCancel::default())
+    }
+
+    fn refresh(&mut self) -> Result<()> {
+        self.refresh_token_if_needed()
+    }
+
+    fn me(&mut self) -> Result<String> {
+        Ok(MixcloudClient::me(self)?.username)
+    }
+
+    fn upload(&mut self, request: &UploadRequest) -> Result<UploadOutcome> {
+        self.deadline = request.deadline;
+        self.progress = request.progress.clone();
+        let response = MixcloudClient::upload(
+            self,
+            request.file_path,
+            request.title,
+            request.description,
+            request.image_paths,
+            request.tags.clone(),
+            request.publish_date,
+            request.assume_pro,
+            request.unlisted,
+        )?;
+
+        let url = format!("https://www.mixcloud.com{}", response.result.key);
+        let mut details = vec![
+            ("Message", response.result.message),
+            ("Key", response.result.key.clone()),
+            ("URL", url.clone()),
+        ];
+        details.push(if request.unlisted {
+            (
+                "Visibility",
+                "Unlisted (only people with the link can listen)".to_string(),
+            )
+        } else {
+            ("Visibility", "Public".to_string())
+        });
+        if request.publish_date.is_some() {
+            details.push((
+                "Scheduled",
+                "Yes (check Mixcloud for publish time)".to_string(),
+            ));
+        }
+
+        Ok(UploadOutcome {
+            platform: Platform::Mixcloud,
+            id: response.result.key,
+            url: Some(url),
+            scheduled: request.publish_date.is_some(),
+            details,
+            size_warning: response.size_warning,
+            raw: response.raw,
+        })
+    }
 }
@@ -1,12 +1,246 @@
+pub mod breaker;
+pub mod form;
 pub mod mixcloud;
+pub mod progress;
 pub mod soundcloud;
+pub mod throughput;
+pub mod trace;
 
-use anyhow::Result;
-use log::debug;
-use std::path::Path;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs;
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::cli::Platform;
-use crate::config::TokenStorage;
+use crate::config::{Settings, TokenInfo, TokenStorage};
+use crate::limits::{Limits, Metadata};
+
+/// Timeout for requests made by the platform clients
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Wrap a failed request with a message that tells an unreachable host
+/// apart from a timeout or a request that failed mid-flight
+pub fn network_error(action: &str, err: reqwest::Error) -> anyhow::Error {
+    let host = err
+        .url()
+        .and_then(|url| url.host_str())
+        .unwrap_or("the server")
+        .to_string();
+
+    let reason = if err.is_timeout() {
+        format!(
+            "request timed out after {}s — the file may be large or the connection slow",
+            REQUEST_TIMEOUT.as_secs()
+        )
+    } else if err.is_connect() {
+        format!("cannot reach {} — check your internet connection", host)
+    } else if err.is_body() {
+        format!("connection to {} dropped while sending data", host)
+    } else if err.is_request() {
+        format!("request to {} failed before a response arrived", host)
+    } else {
+        format!("request to {} failed", host)
+    };
+
+    anyhow::Error::new(err).context(format!("{}: {}", action, reason))
+}
+
+/// The platform answered 429 Too Many Requests
+#[derive(Debug)]
+pub struct RateLimited {
+    /// How long the platform asked us to wait, from Retry-After
+    pub retry_after: Option<Duration>,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.retry_after {
+            Some(wait) => write!(f, "Rate limited, retry after {}s", wait.as_secs()),
+            None => write!(f, "Rate limited"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Fail with [`RateLimited`] on a 429 so callers can back off instead of
+/// treating it like any other failed upload
+pub fn check_rate_limit(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Result<()> {
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Ok(());
+    }
+
+    // Retry-After is either a number of seconds or an HTTP date
+    let retry_after = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .map(Duration::from_secs)
+                .or_else(|| {
+                    let at = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+                    (at.with_timezone(&Utc) - Utc::now()).to_std().ok()
+                })
+        });
+    Err(RateLimited { retry_after }.into())
+}
+
+/// Content-Type of a response, if it sent one
+pub fn content_type(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Fail clearly when a body that should be JSON is something else, usually an
+/// HTML login or error page served after a redirect
+pub fn ensure_json(content_type: Option<&str>, body: &str, what: &str) -> Result<()> {
+    let declared_other = content_type.is_some_and(|ct| !ct.contains("json"));
+    let looks_like_markup = body.trim_start().starts_with('<');
+    if declared_other || looks_like_markup {
+        let preview: String = body.trim().chars().take(200).collect();
+        bail!(
+            "Unexpected non-JSON {} response ({}), likely an auth or endpoint problem: {}",
+            what,
+            content_type.unwrap_or("no content type"),
+            preview
+        );
+    }
+    Ok(())
+}
+
+/// Read a successful response as JSON, see [`ensure_json`]
+pub fn parse_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::blocking::Response,
+    what: &str,
+) -> Result<T> {
+    let content_type = content_type(response.headers());
+    let body =
+        response_text(response).with_context(|| format!("Failed to read {} response", what))?;
+    ensure_json(content_type.as_deref(), &body, what)?;
+    serde_json::from_str(&body).with_context(|| format!("Failed to parse {} response", what))
+}
+
+/// Attempts made for a request that fails to connect or times out
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Overall time budget for an upload, covering token refresh, retries and
+/// the upload itself
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    at: Instant,
+    total: Duration,
+}
+
+impl Deadline {
+    pub fn after(total: Duration) -> Self {
+        Self {
+            at: Instant::now() + total,
+            total,
+        }
+    }
+
+    /// Time left, or an error once the deadline has passed
+    pub fn remaining(&self) -> Result<Duration> {
+        let remaining = self.at.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            bail!("Gave up: deadline of {}s exceeded", self.total.as_secs());
+        }
+        Ok(remaining)
+    }
+}
+
+/// Timeout for the next request: the usual limit, capped by the deadline
+pub fn request_timeout(deadline: Option<Deadline>) -> Result<Duration> {
+    match deadline {
+        Some(deadline) => Ok(deadline.remaining()?.min(REQUEST_TIMEOUT)),
+        None => Ok(REQUEST_TIMEOUT),
+    }
+}
+
+/// Send a request built by `send`, retrying connection failures with
+/// exponential backoff. `send` gets the timeout to use for each attempt.
+/// Timeouts are only retried with `retry_timeouts`: the server may have
+/// received the whole request before timing out, and sending an upload again
+/// could publish it twice. With a deadline, a retry is only attempted if the
+/// backoff fits in the remaining budget.
+pub fn send_with_retry(
+    deadline: Option<Deadline>,
+    action: &str,
+    retry_timeouts: bool,
+    mut send: impl FnMut(Duration) -> reqwest::Result<reqwest::blocking::Response>,
+) -> Result<reqwest::blocking::Response> {
+    let mut attempt = 1;
+    loop {
+        let timeout = request_timeout(deadline)?;
+        match send(timeout) {
+            Ok(response) => return Ok(response),
+            Err(e)
+                if attempt < MAX_ATTEMPTS
+                    && (e.is_connect() || (retry_timeouts && e.is_timeout())) =>
+            {
+                let backoff = Duration::from_secs(2u64.pow(attempt));
+                if let Some(deadline) = deadline
+                    && !deadline.remaining().is_ok_and(|left| left > backoff)
+                {
+                    return Err(network_error(action, e).context(format!(
+                        "Gave up: deadline of {}s exceeded",
+                        deadline.total.as_secs()
+                    )));
+                }
+
+                warn!(
+                    "{} (attempt {} of {}), retrying in {}s: {}",
+                    action,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    backoff.as_secs(),
+                    e
+                );
+                std::thread::sleep(backoff);
+                attempt += 1;
+            }
+            Err(e) => return Err(network_error(action, e)),
+        }
+    }
+}
+
+/// Compare the bytes read for the upload with the file size when the upload
+/// started and after it finished. Returns a warning when they differ, e.g.
+/// because the file was overwritten or still being written.
+pub fn check_upload_size(path: &Path, size_before: u64, bytes_sent: u64) -> Option<String> {
+    let size_after = std::fs::metadata(path).map(|m| m.len()).ok();
+
+    if bytes_sent != size_before {
+        Some(format!(
+            "Sent {} bytes but the file was {} bytes when the upload started; the track may be truncated",
+            bytes_sent, size_before
+        ))
+    } else if size_after != Some(size_before) {
+        Some(format!(
+            "File changed during upload ({} bytes before, {} after); the track may not match the file on disk",
+            size_before,
+            size_after.map_or_else(|| "unreadable".to_string(), |s| format!("{} bytes", s))
+        ))
+    } else {
+        None
+    }
+}
 
 /// Bring the app back to the foreground after an OAuth callback.
 /// On macOS, this activates the app using AppleScript.
@@ -41,65 +275,762 @@ end tell"#,
     }
 }
 
-pub fn handle_auth(platform: Platform) -> Result<()> {
+/// How many images a platform accepts per upload.
+///
+/// - Mixcloud: one cover picture (`picture`)
+/// - SoundCloud: one artwork image (`track[artwork_data]`)
+///
+/// Extra images are dropped with a warning; raise the limit here when a
+/// platform starts accepting a gallery.
+pub fn max_images(platform: Platform) -> usize {
     match platform {
-        Platform::Mixcloud => {
-            mixcloud::MixcloudClient::authorize()?;
-        }
-        Platform::Soundcloud => {
-            soundcloud::SoundcloudClient::authorize()?;
-        }
+        Platform::Mixcloud | Platform::Soundcloud => 1,
     }
-    Ok(())
 }
 
-pub fn handle_upload(
+/// Audio MIME types a platform is known to take as-is.
+///
+/// - Mixcloud: MP3 and lossless sources; AAC/M4A has to be transcoded to MP3
+/// - SoundCloud: all of the common formats, including AAC/M4A
+///
+/// Anything else is still sent, with a warning, since the lists are not
+/// exhaustive.
+pub fn accepted_audio(platform: Platform) -> &'static [&'static str] {
+    match platform {
+        Platform::Mixcloud => &["audio/mpeg", "audio/wav", "audio/aiff", "audio/flac"],
+        Platform::Soundcloud => &[
+            "audio/mpeg",
+            "audio/mp4",
+            "audio/aac",
+            "audio/wav",
+            "audio/aiff",
+            "audio/flac",
+            "audio/ogg",
+        ],
+    }
+}
+
+/// Everything needed for one upload, shared by all platforms.
+/// Fields a platform doesn't support are ignored by its client.
+pub struct UploadRequest<'a> {
+    pub file_path: &'a Path,
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    /// Cover images, primary first
+    pub image_paths: &'a [PathBuf],
+    pub tags: Option<Vec<String>>,
+    pub publish_date: Option<&'a str>,
+    /// Recording ISRC, already validated (SoundCloud only)
+    pub isrc: Option<&'a str>,
+    /// BCP 47 language tag, already validated (SoundCloud only)
+    pub language: Option<&'a str>,
+    /// The original work of a remix; marks the track as a remix (SoundCloud only)
+    pub remix_of: Option<&'a str>,
+    pub assume_pro: bool,
+    pub unlisted: bool,
+    pub deadline: Option<Deadline>,
+    pub progress: Option<progress::ProgressFn>,
+}
+
+/// Platform-independent summary of a finished upload
+pub struct UploadOutcome {
+    pub platform: Platform,
+    /// Platform identifier: the Mixcloud key or the SoundCloud track ID
+    pub id: String,
+    pub url: Option<String>,
+    /// Whether publishing was scheduled for later
+    pub scheduled: bool,
+    /// Extra label/value lines for the success summary
+    pub details: Vec<(&'static str, String)>,
+    /// Set when the bytes sent didn't match the file size
+    pub size_warning: Option<String>,
+    /// The platform's response as returned, for anything not mapped above
+    pub raw: serde_json::Value,
+}
+
+impl UploadOutcome {
+    /// The public URL, or the track ID when the platform didn't return one
+    pub fn link(&self) -> String {
+        self.url
+            .clone()
+            .unwrap_or_else(|| format!("Track #{}", self.id))
+    }
+}
+
+/// Common interface of the platform clients
+pub trait AuthClient {
+    fn platform(&self) -> Platform;
+
+    /// Run the OAuth flow and store the token for this client's account
+    fn authorize(&mut self) -> Result<()>;
+
+    /// Refresh the access token if it's expired or about to expire
+    fn refresh(&mut self) -> Result<()>;
+
+    /// Username of the authorized account
+    fn me(&mut self) -> Result<String>;
+
+    fn upload(&mut self, request: &UploadRequest) -> Result<UploadOutcome>;
+
+    /// The request an upload would send, with the token redacted
+    fn describe_upload(&self, request: &UploadRequest) -> Result<String>;
+}
+
+/// Client for a platform, bound to a named account or the default one
+pub fn client_for(platform: Platform, account: Option<&str>) -> Result<Box<dyn AuthClient>> {
+    Ok(match platform {
+        Platform::Mixcloud => Box::new(mixcloud::MixcloudClient::for_account(account)?),
+        Platform::Soundcloud => Box::new(soundcloud::SoundcloudClient::for_account(account)?),
+    })
+}
+
+/// Upload with the given account and return the normalized result.
+/// No prompts or printing; see [`handle_upload`] for the interactive version.
+pub fn upload(
+    platform: Platform,
+    account: Option<&str>,
+    request: &UploadRequest,
+) -> Result<UploadOutcome> {
+    client_for(platform, account)?.upload(request)
+}
+
+/// Print the exact form an upload would send, without authorizing or
+/// touching the network. Title and description are fitted as for a real upload.
+#[allow(clippy::too_many_arguments)]
+pub fn dry_run(
     platform: Platform,
+    account: Option<&str>,
     file_path: &Path,
     title: &str,
     description: Option<&str>,
-    image_path: Option<&Path>,
+    image_paths: &[PathBuf],
     tags: Option<Vec<String>>,
     publish_date: Option<&str>,
+    isrc: Option<&str>,
+    language: Option<&str>,
+    remix_of: Option<&str>,
+    unlisted: bool,
+    strict: bool,
+    auto_truncate: bool,
 ) -> Result<()> {
-    match platform {
+    let limits = Limits::for_platform(platform, &Settings::load()?);
+    let metadata = fit_metadata(
+        platform,
+        &limits,
+        title.to_string(),
+        description.map(str::to_string),
+        tags,
+        strict,
+        auto_truncate,
+    )?;
+
+    let request = UploadRequest {
+        file_path,
+        title: &metadata.title,
+        description: metadata.description.as_deref(),
+        image_paths,
+        tags: metadata.tags,
+        publish_date,
+        isrc,
+        language,
+        remix_of,
+        assume_pro: false,
+        unlisted,
+        deadline: None,
+        progress: None,
+    };
+    let described = client_for(platform, account)?.describe_upload(&request)?;
+
+    println!("\nDry run, nothing will be uploaded:\n");
+    println!("{}", described);
+    Ok(())
+}
+
+/// Authorize an account. When it already has a working token this asks
+/// before starting the browser flow again (and skips it when not on a
+/// terminal), unless `force` is set.
+pub fn handle_auth(platform: Platform, account: Option<&str>, force: bool) -> Result<()> {
+    let mut client = client_for(platform, account)?;
+
+    if !force && TokenStorage::load()?.token(platform, account).is_some() {
+        // Refreshes if needed, so a revoked token shows up here
+        match client.me() {
+            Ok(username) => {
+                println!("Already authorized with {} as @{}", platform, username);
+                if !crate::prompt::confirm("Re-authorize anyway?", false)? {
+                    println!("Keeping the existing token (use --force to re-authorize)");
+                    return Ok(());
+                }
+            }
+            Err(e) => {
+                debug!("Stored token check failed: {:#}", e);
+                println!(
+                    "The stored {} token no longer works, authorizing again",
+                    platform
+                );
+            }
+        }
+    }
+
+    client.authorize()?;
+
+    // Fill the username cache right away; status works without it
+    if let Ok(profile) = account_profile(platform, account) {
+        println!("Signed in as @{}", profile.username);
+    }
+    Ok(())
+}
+
+/// Who an authorized account belongs to
+#[derive(Debug, Clone)]
+pub struct AccountProfile {
+    pub username: String,
+    pub tier: Option<String>,
+}
+
+/// Username and tier of an account: cached with its token when fresh,
+/// otherwise looked up from the platform and cached for next time
+pub fn account_profile(platform: Platform, account: Option<&str>) -> Result<AccountProfile> {
+    let ttl_hours = Settings::load()?.username_cache_ttl_hours;
+    let storage = TokenStorage::load()?;
+    if let Some(token) = storage.token(platform, account)
+        && let Some(username) = token.fresh_username(ttl_hours)
+    {
+        return Ok(AccountProfile {
+            username: username.to_string(),
+            tier: token.cached_tier.clone(),
+        });
+    }
+
+    let profile = match platform {
         Platform::Mixcloud => {
-            let mut client = mixcloud::MixcloudClient::new()?;
-            let response = client.upload(
-                file_path,
-                title,
-                description,
-                image_path,
-                tags,
-                publish_date,
-            )?;
-
-            println!("\n✓ Upload successful!");
-            println!("  Message: {}", response.result.message);
-            println!("  Key: {}", response.result.key);
-            println!("  URL: https://www.mixcloud.com{}", response.result.key);
-            if publish_date.is_some() {
-                println!("  Scheduled: Yes (check Mixcloud for publish time)");
+            let user = mixcloud::MixcloudClient::for_account(account)?.me()?;
+            AccountProfile {
+                username: user.username,
+                tier: Some(if user.is_pro { "Pro" } else { "Free" }.to_string()),
             }
         }
         Platform::Soundcloud => {
-            let mut client = soundcloud::SoundcloudClient::new()?;
-            let response = client.upload(file_path, title, description, image_path, tags)?;
+            let user = soundcloud::SoundcloudClient::for_account(account)?.me()?;
+            AccountProfile {
+                username: user.username,
+                tier: user.plan,
+            }
+        }
+    };
+
+    // Cache through the shared store so a token refreshed by the lookup is kept
+    TokenStorage::update_shared(|storage| {
+        storage.cache_profile(
+            platform,
+            account,
+            profile.username.clone(),
+            profile.tier.clone(),
+        )
+    })?;
+
+    Ok(profile)
+}
+
+/// Lets another thread abort an authorization that is waiting for the
+/// browser callback, releasing the callback port
+#[derive(Clone, Default)]
+pub struct AuthCancel {
+    cancelled: Arc<AtomicBool>,
+    /// Port the callback server is listening on, 0 before it starts
+    port: Arc<AtomicU16>,
+}
+
+impl AuthCancel {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // Wake up the blocked accept() with a throwaway connection
+        let port = self.port.load(Ordering::SeqCst);
+        if port != 0 {
+            let _ = TcpStream::connect(("127.0.0.1", port));
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait for the OAuth callback connection, or fail if cancelled meanwhile
+    pub fn accept(&self, listener: &TcpListener) -> Result<TcpStream> {
+        self.port
+            .store(listener.local_addr()?.port(), Ordering::SeqCst);
+        if self.is_cancelled() {
+            bail!("Authorization cancelled");
+        }
+
+        let (stream, _) = listener.accept()?;
+        if self.is_cancelled() {
+            bail!("Authorization cancelled");
+        }
+        Ok(stream)
+    }
+}
+
+/// Attempts at binding the OAuth callback port before giving up
+const CALLBACK_BIND_ATTEMPTS: u32 = 5;
+const CALLBACK_BIND_DELAY: Duration = Duration::from_millis(500);
+
+/// Page for the browser once authorization succeeds: the file set as
+/// `auth_success_page` in settings.json, or `built_in` when there is none.
+/// A custom page has to be a readable .html file.
+pub fn auth_success_page(built_in: &'static str) -> Result<Cow<'static, str>> {
+    let Some(path) = Settings::load()?.auth_success_page else {
+        return Ok(Cow::Borrowed(built_in));
+    };
+    let is_html = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension.to_ascii_lowercase().as_str(), "html" | "htm"));
+    let page = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read auth_success_page {}", path.display()))?;
+    let start: String = page.trim_start().chars().take(14).collect();
+    let start = start.to_ascii_lowercase();
+    if !is_html || !(start.starts_with("<!doctype html") || start.starts_with("<html")) {
+        bail!(
+            "auth_success_page {} is not an HTML page: use a .html file that \
+             starts with <!DOCTYPE html> or <html>",
+            path.display()
+        );
+    }
+    Ok(Cow::Owned(page))
+}
+
+/// Answer the browser's OAuth callback request with `html`
+pub fn respond_html(stream: &mut TcpStream, html: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+        html.len(),
+        html
+    );
+    stream.write_all(response.as_bytes())?;
+    Ok(())
+}
 
-            println!("\n✓ Upload successful!");
-            println!("  ID: {}", response.id);
-            println!("  Title: {}", response.title);
-            if let Some(url) = response.permalink_url {
-                println!("  URL: {}", url);
+/// Listen for the OAuth callback on localhost. SO_REUSEADDR lets a quick
+/// re-run take over a port still in TIME_WAIT, and a few short retries cover
+/// a previous run that hasn't quite let go of it.
+pub fn bind_callback(port: u16) -> Result<TcpListener> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let mut attempt = 1;
+    loop {
+        match bind_reusable(addr) {
+            Ok(listener) => return Ok(listener),
+            Err(e)
+                if e.kind() == std::io::ErrorKind::AddrInUse
+                    && attempt < CALLBACK_BIND_ATTEMPTS =>
+            {
+                debug!(
+                    "Callback port {} busy (attempt {}), retrying",
+                    port, attempt
+                );
+                std::thread::sleep(CALLBACK_BIND_DELAY);
+                attempt += 1;
             }
-            if let Some(desc) = response.description {
-                println!("  Description: {}", desc);
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Failed to start callback server. Is port {} already in use?",
+                        port
+                    )
+                });
             }
         }
     }
+}
+
+fn bind_reusable(addr: SocketAddr) -> std::io::Result<TcpListener> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::IPV4, Type::STREAM, Some(Protocol::TCP))?;
+    // On Windows this would allow two listeners on one port, so only Unix
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
+/// Cheap endpoint used to check that a platform's API is up
+fn api_status_url(platform: Platform) -> &'static str {
+    match platform {
+        Platform::Mixcloud => "https://api.mixcloud.com/",
+        Platform::Soundcloud => "https://api.soundcloud.com/",
+    }
+}
+
+/// How a request authenticates, for logging; never holds the secret itself
+#[derive(Debug, Clone, Copy)]
+pub enum RequestAuth {
+    None,
+    /// Client ID and secret in the form body
+    ClientCredentials,
+    /// `access_token` query parameter (Mixcloud)
+    QueryToken,
+    /// `Authorization: OAuth` header (SoundCloud)
+    OAuthHeader,
+}
+
+impl std::fmt::Display for RequestAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestAuth::None => write!(f, "none"),
+            RequestAuth::ClientCredentials => write!(f, "client_id + client_secret=<redacted>"),
+            RequestAuth::QueryToken => write!(f, "access_token=<redacted>"),
+            RequestAuth::OAuthHeader => write!(f, "OAuth <redacted>"),
+        }
+    }
+}
+
+/// Log the method, final URL and auth mechanism of an API request, so proxy
+/// setups can be checked with RUST_LOG=debug. Only ever pass the base URL:
+/// tokens go in query parameters or headers added afterwards.
+pub fn log_request(method: &str, url: &str, auth: RequestAuth) {
+    debug!("{} {} (auth: {})", method, url, auth);
+    trace::request(method, url, &auth.to_string());
+}
+
+/// Read a response body, recording the response for --trace-to
+pub fn response_text(response: reqwest::blocking::Response) -> reqwest::Result<String> {
+    let url = response.url().to_string();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text()?;
+    trace::response(&url, status, &headers, &body);
+    Ok(body)
+}
+
+/// [`response_text`] for the async client
+#[cfg(feature = "async")]
+pub async fn response_text_async(response: reqwest::Response) -> reqwest::Result<String> {
+    let url = response.url().to_string();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await?;
+    trace::response(&url, status, &headers, &body);
+    Ok(body)
+}
+
+/// Check that a platform's API is answering before starting a long upload.
+/// Any response below 500 counts as up: auth problems are reported later by
+/// the upload itself.
+pub fn preflight(platform: Platform) -> Result<()> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    log_request("GET", api_status_url(platform), RequestAuth::None);
+    let response = client
+        .get(api_status_url(platform))
+        .send()
+        .with_context(|| format!("{} API appears to be unavailable", platform))?;
+
+    let status = response.status();
+    // Only read for --trace-to; the status is all that matters here
+    let _ = response_text(response);
+    if status.is_server_error() {
+        bail!(
+            "{} API appears to be unavailable ({}). Try again later",
+            platform,
+            status.as_u16()
+        );
+    }
+    debug!("{} API is up ({})", platform, status);
     Ok(())
 }
 
+/// Whether an upload error is the platform refusing the artwork (too large,
+/// wrong format) rather than anything else
+fn is_artwork_rejection(err: &anyhow::Error) -> bool {
+    let message = format!("{:#}", err).to_lowercase();
+    let about_image = ["picture", "artwork", "image"]
+        .iter()
+        .any(|word| message.contains(word));
+    let rejected = [
+        "too large",
+        "too big",
+        "file size",
+        "dimension",
+        "format",
+        "unsupported",
+        "invalid",
+    ]
+    .iter()
+    .any(|word| message.contains(word));
+    about_image && rejected
+}
+
+/// Print the authorization URL without starting the OAuth flow
+pub fn print_auth_url(platform: Platform, show_verifier: bool) -> Result<()> {
+    match platform {
+        Platform::Mixcloud => {
+            println!("{}", mixcloud::MixcloudClient::authorization_url()?);
+            if show_verifier {
+                eprintln!("Mixcloud doesn't use PKCE, there is no code verifier");
+            }
+        }
+        Platform::Soundcloud => {
+            let request = soundcloud::SoundcloudClient::authorization_request()?;
+            println!("{}", request.url);
+            println!("State: {}", request.state);
+            if show_verifier {
+                println!("Code verifier: {}", request.code_verifier);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check title, description and tags against the platform limits and print
+/// what was over. Long descriptions are cut; titles and tags only with
+/// `auto_truncate`. A title that is still too long is an error, as is a long
+/// description with `strict`.
+fn fit_metadata(
+    platform: Platform,
+    limits: &Limits,
+    title: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    strict: bool,
+    auto_truncate: bool,
+) -> Result<Metadata> {
+    if strict && let Some(description) = &description {
+        crate::description::validate_description(platform, limits, description, true)?;
+    }
+    let metadata = Metadata {
+        title,
+        description,
+        tags,
+    };
+    let (mut metadata, warnings) = crate::limits::enforce(limits, metadata, auto_truncate);
+    for warning in &warnings {
+        let note = if warning.fixed {
+            "fixed"
+        } else {
+            "use --auto-truncate to cut it"
+        };
+        eprintln!(
+            "⚠ Warning: {}: {} ({})",
+            warning.field, warning.message, note
+        );
+    }
+
+    metadata.title = crate::title::validate_title(platform, limits, &metadata.title)?;
+    Ok(metadata)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_upload(
+    platform: Platform,
+    file_path: &Path,
+    title: &str,
+    description: Option<&str>,
+    image_paths: &[PathBuf],
+    tags: Option<Vec<String>>,
+    publish_date: Option<&str>,
+    assume_pro: bool,
+    unlisted: bool,
+    account: Option<&str>,
+    strict: bool,
+    deadline: Option<Duration>,
+    preflight_check: bool,
+    image_format: crate::cli::CoverFormat,
+    unsupported_image: crate::cli::UnsupportedImage,
+    temp_dir: &Path,
+    isrc: Option<&str>,
+    language: Option<&str>,
+    remix_of: Option<&str>,
+    sanitize: crate::cli::SanitizeMode,
+    auto_truncate: bool,
+) -> Result<Option<UploadOutcome>> {
+    use crate::sanitize::{Field, sanitize_for};
+
+    let settings = Settings::load()?;
+    let limits = Limits::for_platform(platform, &settings);
+    let (title, mut warnings) = sanitize_for(platform, &limits, Field::Title, title, sanitize);
+    let description = description.map(|d| {
+        let (description, more) = sanitize_for(platform, &limits, Field::Description, d, sanitize);
+        warnings.extend(more);
+        description
+    });
+    for warning in &warnings {
+        let note = if warning.fixed {
+            "fixed"
+        } else {
+            "use --sanitize strip to remove"
+        };
+        eprintln!(
+            "⚠ Warning: {}: {} ({})",
+            warning.field, warning.message, note
+        );
+    }
+
+    let metadata = fit_metadata(
+        platform,
+        &limits,
+        title,
+        description,
+        tags,
+        strict,
+        auto_truncate,
+    )?;
+    let title = &metadata.title;
+    let description = metadata.description.as_deref();
+    let tags = metadata.tags;
+
+    let file_size = std::fs::metadata(file_path)
+        .with_context(|| format!("File not found: {}", file_path.display()))?
+        .len();
+
+    for image_path in image_paths {
+        crate::cover::check_image(file_path, image_path, unsupported_image)?;
+    }
+
+    if !confirm_large_upload(platform, file_path, file_size, &settings)? {
+        println!("Upload cancelled");
+        return Ok(None);
+    }
+
+    // Large uploads always check first; discovering an outage afterwards is expensive
+    if preflight_check || file_size > settings.large_upload_threshold_mb * 1024 * 1024 {
+        preflight(platform)?;
+    }
+
+    let deadline = deadline.map(Deadline::after);
+
+    let mut client = client_for(platform, account)?;
+    if unlisted && client.platform() == Platform::Soundcloud {
+        eprintln!("⚠ Warning: --unlisted only applies to Mixcloud, ignoring");
+    }
+    if isrc.is_some() && client.platform() == Platform::Mixcloud {
+        eprintln!("⚠ Warning: --isrc only applies to SoundCloud, ignoring");
+    }
+    if language.is_some() && client.platform() == Platform::Mixcloud {
+        eprintln!("⚠ Warning: Mixcloud has no language field, ignoring --language");
+    }
+    if remix_of.is_some() && client.platform() == Platform::Mixcloud {
+        eprintln!(
+            "⚠ Warning: Mixcloud has no remix field, --remix-of is only credited in the description"
+        );
+    }
+    match crate::audio::audio_mime(file_path) {
+        Ok(mime) if !accepted_audio(platform).contains(&mime) => {
+            let hint = if matches!(mime, "audio/mp4" | "audio/aac") {
+                "; transcode to MP3 if the upload is rejected"
+            } else {
+                ""
+            };
+            eprintln!(
+                "⚠ Warning: {} may not accept {} audio{}",
+                platform, mime, hint
+            );
+        }
+        Ok(_) => {}
+        Err(e) => debug!("Could not detect audio codec: {:#}", e),
+    }
+    let max_images = max_images(platform);
+    if image_paths.len() > max_images {
+        eprintln!(
+            "⚠ Warning: {} accepts {} image(s) per upload, ignoring the other {}",
+            platform,
+            max_images,
+            image_paths.len() - max_images
+        );
+    }
+
+    let reduced;
+    let mut request = UploadRequest {
+        file_path,
+        title,
+        description,
+        image_paths,
+        tags,
+        publish_date,
+        isrc,
+        language,
+        remix_of,
+        assume_pro,
+        unlisted,
+        deadline,
+        progress: Some(progress::terminal_progress()),
+    };
+
+    // Retry once with reduced artwork if the platform rejected the image
+    let outcome = match client.upload(&request) {
+        Err(e) if !image_paths.is_empty() && is_artwork_rejection(&e) => {
+            warn!("Artwork rejected: {:#}", e);
+            eprintln!(
+                "⚠ {} rejected the artwork, retrying with a reduced image",
+                platform
+            );
+            reduced = image_paths
+                .iter()
+                .map(|path| {
+                    crate::cover::reduce_image(
+                        path,
+                        crate::cover::recommended_cover_size(platform),
+                        image_format,
+                        temp_dir,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()
+                .context("Failed to reduce artwork after it was rejected")?;
+            request.image_paths = &reduced;
+            client.upload(&request)?
+        }
+        result => result?,
+    };
+
+    debug!("{} response: {}", outcome.platform, outcome.raw);
+    println!("\n✓ Upload successful!");
+    for (label, value) in &outcome.details {
+        println!("  {}: {}", label, value);
+    }
+    if let Some(warning) = &outcome.size_warning {
+        println!("  ⚠ {}", warning);
+    }
+
+    Ok(Some(outcome))
+}
+
+/// Ask before starting an upload above the configured size threshold.
+/// Returns true when the upload should go ahead. Non-interactive sessions
+/// and --yes never prompt.
+fn confirm_large_upload(
+    platform: Platform,
+    file_path: &Path,
+    file_size: u64,
+    settings: &Settings,
+) -> Result<bool> {
+    let threshold = settings.large_upload_threshold_mb * 1024 * 1024;
+    if file_size <= threshold || crate::prompt::assume_yes() || !crate::prompt::interactive() {
+        return Ok(true);
+    }
+
+    println!("\nLarge upload:");
+    println!("  File: {}", file_path.display());
+    println!("  Size: {:.1} MB", file_size as f64 / (1024.0 * 1024.0));
+    println!("  Platform: {}", platform);
+    match throughput::recent_rate() {
+        Some(rate) => {
+            let minutes = (file_size as f64 / rate / 60.0).ceil();
+            println!(
+                "  Estimated time: ~{} minutes (at {:.1} MB/s, recent uploads)",
+                minutes,
+                rate / (1024.0 * 1024.0)
+            );
+        }
+        None => println!("  Estimated time: Unknown (no previous upload)"),
+    }
+
+    crate::prompt::confirm("\nContinue?", false)
+}
+
 pub fn show_status() -> Result<()> {
     let token_storage = TokenStorage::load()?;
 
@@ -109,6 +1040,12 @@ pub fn show_status() -> Result<()> {
     match &token_storage.mixcloud {
         Some(token_info) => {
             println!("Mixcloud: ✓ Authorized");
+            if let Some(username) = &token_info.cached_username {
+                match &token_info.cached_tier {
+                    Some(tier) => println!("  Account: @{} ({})", username, tier),
+                    None => println!("  Account: @{}", username),
+                }
+            }
             println!(
                 "  Token created: {}",
                 token_info.created_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -153,6 +1090,12 @@ pub fn show_status() -> Result<()> {
     match &token_storage.soundcloud {
         Some(token_info) => {
             println!("SoundCloud: ✓ Authorized");
+            if let Some(username) = &token_info.cached_username {
+                match &token_info.cached_tier {
+                    Some(tier) => println!("  Account: @{} ({})", username, tier),
+                    None => println!("  Account: @{}", username),
+                }
+            }
             println!(
                 "  Token created: {}",
                 token_info.created_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -189,6 +1132,138 @@ pub fn show_status() -> Result<()> {
     }
 
     println!("\nToken storage: {}", TokenStorage::token_path()?.display());
+    match throughput::recent_rate() {
+        Some(rate) => println!("Recent upload speed: {:.1} MB/s", rate / (1024.0 * 1024.0)),
+        None => println!("Recent upload speed: not measured yet"),
+    }
+
+    Ok(())
+}
+
+/// Token state for one platform, without any secrets
+#[derive(Debug, Serialize)]
+pub struct PlatformStatus {
+    pub authorized: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub seconds_until_expiry: Option<i64>,
+    pub expired: bool,
+    pub has_refresh_token: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+}
+
+impl PlatformStatus {
+    fn from_token(token_info: Option<&TokenInfo>) -> Self {
+        match token_info {
+            Some(token_info) => Self {
+                authorized: true,
+                created_at: Some(token_info.created_at),
+                expires_at: token_info.expires_at(),
+                seconds_until_expiry: token_info
+                    .time_until_expiry()
+                    .map(|remaining| remaining.num_seconds()),
+                expired: token_info.is_expired(),
+                has_refresh_token: token_info.refresh_token.is_some(),
+                username: None,
+                tier: None,
+            },
+            None => Self {
+                authorized: false,
+                created_at: None,
+                expires_at: None,
+                seconds_until_expiry: None,
+                expired: false,
+                has_refresh_token: false,
+                username: None,
+                tier: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub mixcloud: PlatformStatus,
+    pub soundcloud: PlatformStatus,
+    pub token_storage: String,
+    /// Rolling throughput of recent upload attempts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_bytes_per_sec: Option<f64>,
+}
+
+pub fn show_status_json() -> Result<()> {
+    let token_storage = TokenStorage::load()?;
+
+    let mut mixcloud = PlatformStatus::from_token(token_storage.mixcloud.as_ref());
+    let mut soundcloud = PlatformStatus::from_token(token_storage.soundcloud.as_ref());
+    for (platform, status) in [
+        (Platform::Mixcloud, &mut mixcloud),
+        (Platform::Soundcloud, &mut soundcloud),
+    ] {
+        if status.authorized {
+            // Best effort: a stale token or no network just leaves it out
+            if let Ok(profile) = account_profile(platform, None) {
+                status.username = Some(profile.username);
+                status.tier = profile.tier;
+            }
+        }
+    }
+
+    let report = StatusReport {
+        mixcloud,
+        soundcloud,
+        token_storage: TokenStorage::token_path()?.display().to_string(),
+        upload_bytes_per_sec: throughput::recent_rate(),
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_artwork_rejection() {
+        let rejected = [
+            r#"Upload failed with status 400 Bad Request: {"error": {"type": "PictureError", "message": "The picture is too large"}}"#,
+            r#"Upload failed with status 422 Unprocessable Entity: {"errors": [{"error_message": "artwork_data has an invalid format"}]}"#,
+            "Upload failed with status 413 Payload Too Large: Image file size exceeds 2MB",
+        ];
+        for body in rejected {
+            assert!(is_artwork_rejection(&anyhow::anyhow!(body)), "{}", body);
+        }
+
+        let other = [
+            r#"Upload failed with status 401 Unauthorized: {"error": "invalid_token"}"#,
+            "Upload failed with status 400 Bad Request: Unsupported audio format",
+            "Upload failed with status 413 Payload Too Large: file too large",
+        ];
+        for body in other {
+            assert!(!is_artwork_rejection(&anyhow::anyhow!(body)), "{}", body);
+        }
+    }
+
+    #[test]
+    fn test_ensure_json() {
+        assert!(ensure_json(Some("application/json; charset=utf-8"), "{}", "upload").is_ok());
+        assert!(ensure_json(None, "{\"id\": 1}", "upload").is_ok());
+
+        let err = ensure_json(
+            Some("text/html"),
+            "<!DOCTYPE html><title>Log in</title>",
+            "upload",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("non-JSON upload response (text/html)")
+        );
+        assert!(ensure_json(None, "  <html>", "token").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Called with the current stats while the audio is being sent
+pub type ProgressFn = Arc<dyn Fn(&UploadStats) + Send + Sync>;
+
+/// No rate or ETA is shown until the upload has run this long
+const RAMP_UP: Duration = Duration::from_secs(3);
+/// Window for the smoothed rate
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+/// Minimum time between progress callbacks
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Bytes sent over time, for rates and the time remaining
+pub struct UploadStats {
+    total: u64,
+    sent: u64,
+    started: Instant,
+    /// (time, bytes sent) samples inside the rate window, oldest first
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl UploadStats {
+    pub fn new(total: u64) -> Self {
+        Self::starting_at(total, Instant::now())
+    }
+
+    fn starting_at(total: u64, started: Instant) -> Self {
+        Self {
+            total,
+            sent: 0,
+            started,
+            samples: VecDeque::from([(started, 0)]),
+        }
+    }
+
+    fn record_at(&mut self, sent: u64, now: Instant) {
+        self.sent = sent;
+        self.samples.push_back((now, sent));
+        // Keep one sample older than the window so it always spans RATE_WINDOW
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= RATE_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        (self.sent as f64 / self.total as f64).min(1.0)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.sent >= self.total
+    }
+
+    /// Average rate over the last few seconds, None while ramping up
+    pub fn smoothed_rate(&self) -> Option<f64> {
+        let (t0, b0) = *self.samples.front()?;
+        let (t1, b1) = *self.samples.back()?;
+        if t1.duration_since(self.started) < RAMP_UP {
+            return None;
+        }
+        rate(b1 - b0, t1.duration_since(t0))
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        let rate = self.smoothed_rate()?;
+        let left = self.total.saturating_sub(self.sent) as f64;
+        Some(Duration::from_secs_f64(left / rate))
+    }
+
+    /// "45% — 12.3 MB/s — ~2m10s remaining", or "calculating..." while ramping up
+    pub fn status_line(&self) -> String {
+        let percent = (self.fraction() * 100.0).floor();
+        match (self.smoothed_rate(), self.remaining()) {
+            (Some(rate), Some(remaining)) => format!(
+                "{:.0}% — {:.1} MB/s — ~{} remaining",
+                percent,
+                rate / (1024.0 * 1024.0),
+                format_remaining(remaining)
+            ),
+            _ => format!("{:.0}% — calculating...", percent),
+        }
+    }
+}
+
+fn rate(bytes: u64, elapsed: Duration) -> Option<f64> {
+    let secs = elapsed.as_secs_f64();
+    (secs > 0.0 && bytes > 0).then(|| bytes as f64 / secs)
+}
+
+fn format_remaining(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Reader that feeds the bytes it hands out into [`UploadStats`]
+pub struct ProgressReader<R> {
+    inner: R,
+    sent: u64,
+    stats: UploadStats,
+    last_report: Option<Instant>,
+    on_progress: ProgressFn,
+}
+
+impl<R: Read> ProgressReader<R> {
+    pub fn new(inner: R, total: u64, on_progress: ProgressFn) -> Self {
+        Self {
+            inner,
+            sent: 0,
+            stats: UploadStats::new(total),
+            last_report: None,
+            on_progress,
+        }
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+
+        let now = Instant::now();
+        let due = self
+            .last_report
+            .is_none_or(|last| now.duration_since(last) >= REPORT_INTERVAL);
+        if due || self.sent >= self.stats.total {
+            self.stats.record_at(self.sent, now);
+            self.last_report = Some(now);
+            (self.on_progress)(&self.stats);
+        }
+        Ok(n)
+    }
+}
+
+/// Progress printer for the terminal; redraws one line on stderr
+pub fn terminal_progress() -> ProgressFn {
+    use std::io::{IsTerminal, Write};
+
+    let interactive = io::stderr().is_terminal();
+    Arc::new(move |stats: &UploadStats| {
+        if !interactive {
+            return;
+        }
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K  {}", stats.status_line());
+        if stats.is_done() {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_line() {
+        const MB: u64 = 1024 * 1024;
+        let start = Instant::now();
+        let mut stats = UploadStats::starting_at(100 * MB, start);
+
+        stats.record_at(5 * MB, start + Duration::from_secs(1));
+        assert_eq!(stats.status_line(), "5% — calculating...");
+
+        for second in 2..=10 {
+            stats.record_at(5 * MB * second, start + Duration::from_secs(second));
+        }
+        assert_eq!(stats.smoothed_rate(), Some(5.0 * MB as f64));
+        assert_eq!(stats.status_line(), "50% — 5.0 MB/s — ~10s remaining");
+    }
+}
@@ -2,23 +2,71 @@ use anyhow::{Context, Result, bail};
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use log::{debug, info, warn};
 use rand::Rng;
-use reqwest::blocking::{Client, multipart};
+use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::fs;
-use std::io::{BufRead, BufReader, Write};
-use std::net::TcpListener;
-use std::path::Path;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use url::Url;
 
-use crate::config::{SoundcloudCredentials, TokenInfo, TokenStorage};
+use crate::cli::Platform;
+use crate::config::{Credentials, TokenInfo, TokenStorage, resolve_credentials};
+use crate::platforms::form::{FieldNames, UploadForm};
+use crate::platforms::progress::ProgressFn;
+use crate::platforms::{AuthCancel, AuthClient, Deadline, UploadOutcome, UploadRequest};
 
-const OAUTH_AUTHORIZE_URL: &str = "https://secure.soundcloud.com/authorize";
+pub const OAUTH_AUTHORIZE_URL: &str = "https://secure.soundcloud.com/authorize";
 const OAUTH_TOKEN_URL: &str = "https://secure.soundcloud.com/oauth/token";
 const UPLOAD_URL: &str = "https://api.soundcloud.com/tracks";
+const ME_URL: &str = "https://api.soundcloud.com/me";
+/// Local port the OAuth redirect comes back to
+pub const CALLBACK_PORT: u16 = 8889;
 const REDIRECT_URI: &str = "http://localhost:8889/callback";
 
+/// Shown in the browser once authorization succeeds, unless
+/// `auth_success_page` in settings.json points at a page of your own
+const SUCCESS_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>Authorization Successful</title>
+    <style>
+        body {
+            font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
+            display: flex;
+            justify-content: center;
+            align-items: center;
+            height: 100vh;
+            margin: 0;
+            background: linear-gradient(135deg, #ff8800 0%, #ff3300 100%);
+            color: white;
+        }
+        .container {
+            text-align: center;
+            padding: 2rem;
+            background: rgba(255, 255, 255, 0.1);
+            border-radius: 10px;
+            backdrop-filter: blur(10px);
+        }
+        h1 { margin: 0 0 1rem 0; }
+        p { margin: 0; opacity: 0.9; }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>✓ Authorization Successful!</h1>
+        <p>You can close this window and return to the terminal.</p>
+        <p style="margin-top: 1rem; font-size: 0.9em;">This window will close automatically...</p>
+    </div>
+    <script>
+        setTimeout(function() { window.close(); }, 2000);
+    </script>
+</body>
+</html>
+"#;
+
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
     access_token: String,
@@ -36,6 +84,21 @@ pub struct UploadResponse {
     pub title: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Set when the bytes sent didn't match the file size
+    #[serde(skip)]
+    pub size_warning: Option<String>,
+    /// The response exactly as the API returned it
+    #[serde(skip)]
+    pub raw: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SoundcloudUser {
+    pub id: i64,
+    pub username: String,
+    /// Subscription plan, e.g. "Free" or "Pro Unlimited"
+    #[serde(default)]
+    pub plan: Option<String>,
 }
 
 /// Generate PKCE code verifier (random string)
@@ -53,33 +116,49 @@ fn generate_code_challenge(verifier: &str) -> String {
     URL_SAFE_NO_PAD.encode(hash)
 }
 
+/// Authorization URL plus the per-attempt secrets needed to finish the flow
+pub struct AuthRequest {
+    pub url: Url,
+    /// CSRF token echoed back on the callback
+    pub state: String,
+    /// PKCE verifier sent with the code exchange; keep it private
+    pub code_verifier: String,
+}
+
 pub struct SoundcloudClient {
     client: Client,
-    credentials: SoundcloudCredentials,
-    token_storage: TokenStorage,
+    credentials: Credentials,
+    /// Named account to use, or None for the default account
+    account: Option<String>,
+    fields: FieldNames,
+    /// Overall time budget for the current upload
+    deadline: Option<Deadline>,
+    progress: Option<ProgressFn>,
 }
 
 impl SoundcloudClient {
-    pub fn new() -> Result<Self> {
+    /// Create a client bound to a named account, or the default one for None
+    pub fn for_account(account: Option<&str>) -> Result<Self> {
         let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(300))
+            .timeout(super::REQUEST_TIMEOUT)
             .build()
             .context("Failed to create HTTP client")?;
 
-        let credentials = SoundcloudCredentials::new();
-        let token_storage = TokenStorage::load()?;
+        let credentials = resolve_credentials(Platform::Soundcloud)?;
 
         Ok(Self {
             client,
             credentials,
-            token_storage,
+            account: account.map(str::to_string),
+            fields: FieldNames::for_platform(Platform::Soundcloud)?,
+            deadline: None,
+            progress: None,
         })
     }
 
-    pub fn authorize() -> Result<()> {
-        info!("Starting SoundCloud OAuth2 authorization with PKCE...");
-
-        let credentials = SoundcloudCredentials::new();
+    /// Build the authorization URL with a fresh PKCE pair and state
+    pub fn authorization_request() -> Result<AuthRequest> {
+        let credentials = resolve_credentials(Platform::Soundcloud)?;
 
         // Generate PKCE values
         let code_verifier = generate_code_verifier();
@@ -103,6 +182,26 @@ impl SoundcloudClient {
             .append_pair("code_challenge_method", "S256")
             .append_pair("state", &state);
 
+        Ok(AuthRequest {
+            url: auth_url,
+            state,
+            code_verifier,
+        })
+    }
+
+    /// Run the OAuth flow; `cancel` can abort it from another thread
+    pub fn authorize(account: Option<&str>, cancel: &AuthCancel) -> Result<()> {
+        info!("Starting SoundCloud OAuth2 authorization with PKCE...");
+
+        let credentials = resolve_credentials(Platform::Soundcloud)?;
+        // Check a custom page before the browser opens, not after
+        let success_page = super::auth_success_page(SUCCESS_PAGE)?;
+        let AuthRequest {
+            url: auth_url,
+            state,
+            code_verifier,
+        } = Self::authorization_request()?;
+
         println!("\nOpening browser for authorization...");
         println!("If the browser doesn't open, visit this URL:\n");
         println!("{}\n", auth_url);
@@ -113,12 +212,11 @@ impl SoundcloudClient {
         }
 
         // Start local server to receive callback
-        let listener = TcpListener::bind("127.0.0.1:8889")
-            .context("Failed to start callback server. Is port 8889 already in use?")?;
+        let listener = super::bind_callback(CALLBACK_PORT)?;
 
         println!("Waiting for authorization...");
 
-        let (mut stream, _) = listener.accept()?;
+        let mut stream = cancel.accept(&listener)?;
         let buf_reader = BufReader::new(&stream);
         let request_line = buf_reader
             .lines()
@@ -135,53 +233,7 @@ impl SoundcloudClient {
         }
 
         // Send success response to browser
-        let html = r#"
-<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <title>Authorization Successful</title>
-    <style>
-        body {
-            font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
-            display: flex;
-            justify-content: center;
-            align-items: center;
-            height: 100vh;
-            margin: 0;
-            background: linear-gradient(135deg, #ff8800 0%, #ff3300 100%);
-            color: white;
-        }
-        .container {
-            text-align: center;
-            padding: 2rem;
-            background: rgba(255, 255, 255, 0.1);
-            border-radius: 10px;
-            backdrop-filter: blur(10px);
-        }
-        h1 { margin: 0 0 1rem 0; }
-        p { margin: 0; opacity: 0.9; }
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>✓ Authorization Successful!</h1>
-        <p>You can close this window and return to the terminal.</p>
-        <p style="margin-top: 1rem; font-size: 0.9em;">This window will close automatically...</p>
-    </div>
-    <script>
-        setTimeout(function() { window.close(); }, 2000);
-    </script>
-</body>
-</html>
-"#;
-
-        let response = format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
-            html.len(),
-            html
-        );
-        stream.write_all(response.as_bytes())?;
+        super::respond_html(&mut stream, &success_page)?;
 
         info!("Received authorization code, exchanging for access token...");
 
@@ -195,20 +247,24 @@ impl SoundcloudClient {
         params.insert("code", code);
         params.insert("code_verifier", code_verifier);
 
+        super::log_request(
+            "POST",
+            OAUTH_TOKEN_URL,
+            super::RequestAuth::ClientCredentials,
+        );
         let response = http_client
             .post(OAUTH_TOKEN_URL)
             .form(&params)
             .send()
-            .context("Failed to exchange authorization code")?;
+            .map_err(|e| super::network_error("Failed to exchange authorization code", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let body = response.text().unwrap_or_default();
+            let body = super::response_text(response).unwrap_or_default();
             bail!("Token exchange failed with status {}: {}", status, body);
         }
 
-        let token_response: TokenResponse =
-            response.json().context("Failed to parse token response")?;
+        let token_response: TokenResponse = super::parse_json(response, "token")?;
 
         // Save tokens to storage
         let token_info = TokenInfo::new(
@@ -217,12 +273,9 @@ impl SoundcloudClient {
             token_response.expires_in,
         );
 
-        let mut storage = TokenStorage::load().unwrap_or(TokenStorage {
-            mixcloud: None,
-            soundcloud: None,
-        });
-        storage.soundcloud = Some(token_info);
-        storage.save()?;
+        TokenStorage::update_shared(|storage| {
+            storage.set_token(Platform::Soundcloud, account, token_info)
+        })?;
 
         // Bring the app back to the foreground
         super::activate_app();
@@ -265,12 +318,15 @@ impl SoundcloudClient {
         Ok((code, state))
     }
 
+    fn token(&self) -> Result<TokenInfo> {
+        TokenStorage::shared()?
+            .token(Platform::Soundcloud, self.account.as_deref())
+            .cloned()
+            .context("Not authorized with SoundCloud. Run 'dj-uploader auth soundcloud' first")
+    }
+
     fn refresh_token_if_needed(&mut self) -> Result<()> {
-        let token_info = self
-            .token_storage
-            .soundcloud
-            .as_ref()
-            .context("No SoundCloud token available")?;
+        let token_info = self.token()?;
 
         if token_info.is_expired() {
             warn!("Access token is expired or expiring soon, refreshing...");
@@ -285,16 +341,28 @@ impl SoundcloudClient {
             params.insert("client_secret", self.credentials.client_secret.clone());
             params.insert("refresh_token", refresh_token.clone());
 
-            let response = self
-                .client
-                .post(OAUTH_TOKEN_URL)
-                .form(&params)
-                .send()
-                .context("Failed to refresh token")?;
+            super::log_request(
+                "POST",
+                OAUTH_TOKEN_URL,
+                super::RequestAuth::ClientCredentials,
+            );
+            let response = super::send_with_retry(
+                self.deadline,
+                "Failed to refresh token",
+                true,
+                |timeout| {
+                    self.client
+                        .post(OAUTH_TOKEN_URL)
+                        .timeout(timeout)
+                        .form(&params)
+                        .send()
+                },
+            )?;
 
             if !response.status().is_success() {
                 let status = response.status();
-                let body = response.text().unwrap_or_default();
+                super::breaker::observe_refresh(Platform::Soundcloud, status);
+                let body = super::response_text(response).unwrap_or_default();
                 bail!(
                     "Token refresh failed with status {}: {}. Please re-authorize.",
                     status,
@@ -302,19 +370,23 @@ impl SoundcloudClient {
                 );
             }
 
-            let token_response: TokenResponse = response
-                .json()
-                .context("Failed to parse token refresh response")?;
+            let token_response: TokenResponse = super::parse_json(response, "token refresh")?;
 
             // Update token storage
             let new_token_info = TokenInfo::new(
                 token_response.access_token,
                 token_response.refresh_token.or(Some(refresh_token.clone())),
                 token_response.expires_in,
-            );
+            )
+            .with_profile_cache(&token_info);
 
-            self.token_storage.soundcloud = Some(new_token_info);
-            self.token_storage.save()?;
+            TokenStorage::update_shared(|storage| {
+                storage.set_token(
+                    Platform::Soundcloud,
+                    self.account.as_deref(),
+                    new_token_info,
+                )
+            })?;
 
             info!("Token refreshed successfully");
         }
@@ -322,120 +394,369 @@ impl SoundcloudClient {
         Ok(())
     }
 
-    pub fn upload(
-        &mut self,
-        file_path: &Path,
-        title: &str,
-        description: Option<&str>,
-        image_path: Option<&Path>,
-        tags: Option<Vec<String>>,
-    ) -> Result<UploadResponse> {
+    /// Fetch the authorized user's profile
+    pub fn me(&mut self) -> Result<SoundcloudUser> {
+        self.refresh_token_if_needed()?;
+
+        let token_info = self.token()?;
+
+        super::log_request("GET", ME_URL, super::RequestAuth::OAuthHeader);
+        let response = self
+            .client
+            .get(ME_URL)
+            .header(
+                "Authorization",
+                format!("OAuth {}", token_info.access_token),
+            )
+            .send()
+            .map_err(|e| super::network_error("Failed to fetch SoundCloud profile", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = super::response_text(response).unwrap_or_default();
+            bail!("Profile request failed with status {}: {}", status, body);
+        }
+
+        super::parse_json(response, "SoundCloud profile")
+    }
+
+    /// Make sure we're authorized before building the form
+    fn prepare_upload(&mut self, file_path: &Path) -> Result<()> {
+        super::breaker::check(Platform::Soundcloud)?;
+
         // Check if we have a token, if not, authorize first
-        if self.token_storage.soundcloud.is_none() {
+        if self.token().is_err() {
             println!("\nNo authorization found. Starting OAuth2 flow...\n");
-            Self::authorize()?;
-            // Reload token storage after authorization
-            self.token_storage = TokenStorage::load()?;
+            Self::authorize(self.account.as_deref(), &AuthCancel::default())?;
         }
 
         // Refresh token if needed
         self.refresh_token_if_needed()?;
 
-        let token_info = self
-            .token_storage
-            .soundcloud
-            .as_ref()
-            .context("No SoundCloud token")?;
-
-        info!("Uploading {} to SoundCloud...", file_path.display());
-
         if !file_path.exists() {
             bail!("File not found: {}", file_path.display());
         }
 
-        // Build multipart form
-        let mut form = multipart::Form::new();
-
-        // Add audio file
-        let file_name = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .context("Invalid file name")?
-            .to_string();
-
-        let file_bytes = fs::read(file_path).context("Failed to read audio file")?;
-
-        let file_part = multipart::Part::bytes(file_bytes)
-            .file_name(file_name.clone())
-            .mime_str("audio/mpeg")?;
+        Ok(())
+    }
 
-        form = form.part("track[asset_data]", file_part);
+    #[allow(clippy::too_many_arguments)]
+    fn upload_form(
+        &self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        isrc: Option<&str>,
+        language: Option<&str>,
+        remix: bool,
+    ) -> Result<UploadForm> {
+        let fields = &self.fields;
+        let mut form = UploadForm::with_audio(&fields.audio, file_path)?;
+        form.progress = self.progress.clone();
 
         // Add metadata
-        form = form.text("track[title]", title.to_string());
+        form.text(&fields.title, title);
 
         if let Some(desc) = description {
-            form = form.text("track[description]", desc.to_string());
+            form.text(&fields.description, desc);
         }
 
-        // Add artwork if provided
-        if let Some(img_path) = image_path
-            && img_path.exists()
+        // Add as many images as the platform accepts, primary first
+        for img_path in image_paths
+            .iter()
+            .take(super::max_images(Platform::Soundcloud))
         {
-            let img_bytes = fs::read(img_path).context("Failed to read image file")?;
+            if img_path.exists() {
+                form.image(&fields.image, img_path, "artwork.jpg")?;
+            }
+        }
 
-            let img_name = img_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("artwork.jpg")
-                .to_string();
+        // Add tags if provided (space-separated, multi-word tags quoted)
+        if let Some(tag_list) = tags {
+            let tags_string = tag_list
+                .iter()
+                .map(|tag| {
+                    if tag.contains(' ') {
+                        format!("\"{}\"", tag)
+                    } else {
+                        tag.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            form.text(&fields.tags, tags_string);
+        }
 
-            let img_part = multipart::Part::bytes(img_bytes).file_name(img_name);
+        if let Some(isrc) = isrc {
+            form.text(&fields.isrc, isrc);
+        }
 
-            form = form.part("track[artwork_data]", img_part);
+        if let Some(language) = language {
+            form.text(&fields.language, language);
         }
 
-        // Add tags if provided
-        if let Some(tag_list) = tags {
-            let tags_string = tag_list.join(" ");
-            form = form.text("track[tag_list]", tags_string);
+        // The API has no field for the original work, only the track type
+        if remix {
+            form.text(&fields.track_type, "remix");
         }
 
         // Set sharing to public
-        form = form.text("track[sharing]", "public");
-
-        debug!("Sending upload request...");
+        form.text(&fields.sharing, "public");
 
-        // Send upload request with OAuth token
-        let response = self
-            .client
-            .post(UPLOAD_URL)
-            .header(
-                "Authorization",
-                format!("OAuth {}", token_info.access_token),
-            )
-            .multipart(form)
-            .send()
-            .context("Failed to upload file")?;
+        Ok(form)
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().unwrap_or_default();
+    /// Turn the upload response into a result and check the bytes sent
+    fn finish_upload(
+        file_path: &Path,
+        form_sizes: (u64, u64),
+        status: reqwest::StatusCode,
+        body: String,
+    ) -> Result<UploadResponse> {
+        if !status.is_success() {
             bail!("Upload failed with status {}: {}", status, body);
         }
 
-        // Get response text first for debugging
-        let response_text = response.text().context("Failed to read response body")?;
-
         println!("\nSoundCloud API Response:");
-        println!("{}", response_text);
+        println!("{}", body);
         println!();
 
-        let upload_response: UploadResponse =
-            serde_json::from_str(&response_text).context("Failed to parse upload response")?;
+        let mut upload_response: UploadResponse =
+            serde_json::from_str(&body).context("Failed to parse upload response")?;
+        upload_response.raw = serde_json::from_str(&body).unwrap_or_default();
+
+        let (size_before, bytes_sent) = form_sizes;
+        upload_response.size_warning = super::check_upload_size(file_path, size_before, bytes_sent);
+        if let Some(warning) = &upload_response.size_warning {
+            warn!("{}", warning);
+        }
 
         info!("Upload successful!");
 
         Ok(upload_response)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn upload(
+        &mut self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        isrc: Option<&str>,
+        language: Option<&str>,
+        remix: bool,
+    ) -> Result<UploadResponse> {
+        self.prepare_upload(file_path)?;
+
+        let token_info = self.token()?;
+
+        info!("Uploading {} to SoundCloud...", file_path.display());
+
+        let form = self.upload_form(
+            file_path,
+            title,
+            description,
+            image_paths,
+            tags,
+            isrc,
+            language,
+            remix,
+        )?;
+        let form_sizes = (form.size_before, form.bytes_sent);
+        let body_len = form.body_len();
+
+        debug!("Sending upload request...");
+
+        // Send upload request with OAuth token
+        super::log_request("POST", UPLOAD_URL, super::RequestAuth::OAuthHeader);
+        let response =
+            super::send_with_retry(self.deadline, "Failed to upload file", false, |timeout| {
+                let request = self
+                    .client
+                    .post(UPLOAD_URL)
+                    .timeout(timeout)
+                    .header(
+                        "Authorization",
+                        format!("OAuth {}", token_info.access_token),
+                    )
+                    .multipart(form.clone().into_blocking()?);
+                let started = Instant::now();
+                let result = request.send();
+                super::throughput::record_attempt(
+                    Platform::Soundcloud,
+                    body_len,
+                    started.elapsed(),
+                    result.is_ok(),
+                );
+                result
+            })?;
+
+        let status = response.status();
+        super::breaker::observe_upload(Platform::Soundcloud, status);
+        let headers = response.headers().clone();
+        let body = if status.is_success() {
+            super::response_text(response).context("Failed to read response body")?
+        } else {
+            super::response_text(response).unwrap_or_default()
+        };
+        super::check_rate_limit(status, &headers)?;
+        let content_type = super::content_type(&headers);
+        if status.is_success() {
+            super::ensure_json(content_type.as_deref(), &body, "upload")?;
+        }
+
+        Self::finish_upload(file_path, form_sizes, status, body)
+    }
+
+    /// Non-blocking variant of [`upload`](Self::upload).
+    /// Authorization and token refresh still run on the blocking client, so
+    /// this must be called from a multi-threaded tokio runtime.
+    #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upload_async(
+        &mut self,
+        file_path: &Path,
+        title: &str,
+        description: Option<&str>,
+        image_paths: &[PathBuf],
+        tags: Option<Vec<String>>,
+        isrc: Option<&str>,
+        language: Option<&str>,
+        remix: bool,
+    ) -> Result<UploadResponse> {
+        tokio::task::block_in_place(|| self.prepare_upload(file_path))?;
+
+        let access_token = self.token()?.access_token;
+
+        info!("Uploading {} to SoundCloud...", file_path.display());
+
+        let form = self.upload_form(
+            file_path,
+            title,
+            description,
+            image_paths,
+            tags,
+            isrc,
+            language,
+            remix,
+        )?;
+        let form_sizes = (form.size_before, form.bytes_sent);
+        let body_len = form.body_len();
+
+        let client = reqwest::Client::builder()
+            .timeout(super::REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        super::log_request("POST", UPLOAD_URL, super::RequestAuth::OAuthHeader);
+        let started = Instant::now();
+        let result = client
+            .post(UPLOAD_URL)
+            .timeout(super::request_timeout(self.deadline)?)
+            .header("Authorization", format!("OAuth {}", access_token))
+            .multipart(form.into_async()?)
+            .send()
+            .await;
+        super::throughput::record_attempt(
+            Platform::Soundcloud,
+            body_len,
+            started.elapsed(),
+            result.is_ok(),
+        );
+        let response = result.map_err(|e| super::network_error("Failed to upload file", e))?;
+
+        let status = response.status();
+        super::breaker::observe_upload(Platform::Soundcloud, status);
+        let headers = response.headers().clone();
+        let body = if status.is_success() {
+            super::response_text_async(response)
+                .await
+                .context("Failed to read response body")?
+        } else {
+            super::response_text_async(response)
+                .await
+                .unwrap_or_default()
+        };
+        super::check_rate_limit(status, &headers)?;
+        let content_type = super::content_type(&headers);
+        if status.is_success() {
+            super::ensure_json(content_type.as_deref(), &body, "upload")?;
+        }
+
+        Self::finish_upload(file_path, form_sizes, status, body)
+    }
+}
+
+impl AuthClient for SoundcloudClient {
+    fn describe_upload(&self, request: &UploadRequest) -> Result<String> {
+        let form = self.upload_form(
+            request.file_path,
+            request.title,
+            request.description,
+            request.image_paths,
+            request.tags.clone(),
+            request.isrc,
+            request.language,
+            request.remix_of.is_some(),
+        )?;
+        Ok(format!(
+            "POST {}\nAuthorization: OAuth <redacted>\n{}",
+            UPLOAD_URL,
+            form.describe()
+        ))
+    }
+
+    fn platform(&self) -> Platform {
+        Platform::Soundcloud
+    }
+
+    fn authorize(&mut self) -> Result<()> {
+        Self::authorize(self.account.as_deref(), &AuthCancel::default())
+    }
+
+    fn refresh(&mut self) -> Result<()> {
+        self.refresh_token_if_needed()
+    }
+
+    fn me(&mut self) -> Result<String> {
+        Ok(SoundcloudClient::me(self)?.username)
+    }
+
+    fn upload(&mut self, request: &UploadRequest) -> Result<UploadOutcome> {
+        self.deadline = request.deadline;
+        self.progress = request.progress.clone();
+        let response = SoundcloudClient::upload(
+            self,
+            request.file_path,
+            request.title,
+            request.description,
+            request.image_paths,
+            request.tags.clone(),
+            request.isrc,
+            request.language,
+            request.remix_of.is_some(),
+        )?;
+
+        let mut details = vec![("ID", response.id.to_string()), ("Title", response.title)];
+        if let Some(url) = &response.permalink_url {
+            details.push(("URL", url.clone()));
+        }
+        if let Some(desc) = response.description {
+            details.push(("Description", desc));
+        }
+
+        Ok(UploadOutcome {
+            platform: Platform::Soundcloud,
+            id: response.id.to_string(),
+            url: response.permalink_url,
+            scheduled: false,
+            details,
+            size_warning: response.size_warning,
+            raw: response.raw,
+        })
+    }
 }
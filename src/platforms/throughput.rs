@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cli::Platform;
+use crate::config::config_dir;
+use crate::output::format_size;
+
+/// How many recent attempts the rolling rate is taken over
+const RECENT_ATTEMPTS: usize = 5;
+/// Attempts shorter than this say more about latency than bandwidth
+const MIN_ATTEMPT: Duration = Duration::from_millis(500);
+
+/// One upload request that got a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub platform: String,
+    pub bytes: u64,
+    pub secs: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// Recent upload throughput, kept in throughput.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Throughput {
+    /// Newest last
+    pub attempts: Vec<Attempt>,
+}
+
+impl Throughput {
+    pub fn path() -> Result<PathBuf> {
+        Ok(config_dir()?.join("throughput.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn push(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+        let excess = self.attempts.len().saturating_sub(RECENT_ATTEMPTS);
+        self.attempts.drain(..excess);
+    }
+
+    /// Bytes per second over the recent attempts, weighted by their size
+    pub fn rate(&self) -> Option<f64> {
+        weighted_rate(&self.attempts.iter().collect::<Vec<_>>())
+    }
+
+    /// [`rate`](Self::rate) over only the recent attempts to `platform`
+    pub fn platform_rate(&self, platform: Platform) -> Option<f64> {
+        let name = platform.to_string();
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|a| a.platform == name)
+            .collect();
+        weighted_rate(&attempts)
+    }
+}
+
+fn weighted_rate(attempts: &[&Attempt]) -> Option<f64> {
+    let bytes: u64 = attempts.iter().map(|a| a.bytes).sum();
+    let secs: f64 = attempts.iter().map(|a| a.secs).sum();
+    (bytes > 0 && secs > 0.0).then(|| bytes as f64 / secs)
+}
+
+/// Log the effective throughput of one upload request and fold it into the
+/// rolling figure. `completed` is false when no response came back, in which
+/// case it's logged but not stored, since we don't know how much was sent.
+pub fn record_attempt(platform: Platform, bytes: u64, elapsed: Duration, completed: bool) {
+    let secs = elapsed.as_secs_f64();
+    if !completed {
+        info!(
+            "{} upload attempt failed after {:.1}s ({} request)",
+            platform,
+            secs,
+            format_size(bytes)
+        );
+        return;
+    }
+
+    info!(
+        "{} upload attempt: {} in {:.1}s ({:.2} MB/s)",
+        platform,
+        format_size(bytes),
+        secs,
+        bytes as f64 / secs.max(f64::EPSILON) / (1024.0 * 1024.0)
+    );
+    if elapsed < MIN_ATTEMPT {
+        return;
+    }
+
+    // Losing a sample is harmless, so none of this fails the upload
+    let mut throughput = Throughput::load().unwrap_or_default();
+    throughput.push(Attempt {
+        platform: platform.to_string(),
+        bytes,
+        secs,
+        at: Utc::now(),
+    });
+    if let Err(e) = throughput.save() {
+        debug!("Failed to save upload throughput: {:#}", e);
+    }
+}
+
+/// Recent bytes per second, if any upload has been measured
+pub fn recent_rate() -> Option<f64> {
+    Throughput::load().ok()?.rate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_is_weighted_over_recent_attempts() {
+        let attempt = |bytes, secs| Attempt {
+            platform: "Mixcloud".to_string(),
+            bytes,
+            secs,
+            at: Utc::now(),
+        };
+        let mut throughput = Throughput::default();
+        assert_eq!(throughput.rate(), None);
+
+        // A slow outlier beyond the window no longer counts
+        throughput.push(attempt(1_000, 100.0));
+        for _ in 0..RECENT_ATTEMPTS {
+            throughput.push(attempt(10_000, 1.0));
+        }
+        assert_eq!(throughput.attempts.len(), RECENT_ATTEMPTS);
+        assert_eq!(throughput.rate(), Some(10_000.0));
+
+        throughput.push(attempt(0, 4.0));
+        assert_eq!(throughput.rate(), Some(40_000.0 / 8.0));
+        assert_eq!(throughput.platform_rate(Platform::Soundcloud), None);
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// File every request and response is written to, set by --trace-to
+static TRACE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Body characters kept per response; enough for any API error message
+const MAX_BODY_CHARS: usize = 2000;
+
+/// Query, form and JSON keys whose values are never written out
+const SECRET_KEYS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "code",
+    "code_verifier",
+];
+
+/// Headers whose values are never written out
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Start writing a trace of every HTTP call in this run to `path`,
+/// replacing any file already there
+pub fn start(path: &Path) -> Result<()> {
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to create trace file {}", path.display()))?;
+    writeln!(
+        file,
+        "# dj-uploader {} trace, {} {}, started {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        chrono::Utc::now().to_rfc3339()
+    )?;
+    *TRACE.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+/// Record a request about to be sent
+pub fn request(method: &str, url: &str, auth: &str) {
+    write_entry(&format!(
+        "{} → {} {} (auth: {})\n",
+        chrono::Utc::now().to_rfc3339(),
+        method,
+        scrub(url),
+        auth
+    ));
+}
+
+/// Record a response with its headers and the start of its body
+pub fn response(url: &str, status: StatusCode, headers: &HeaderMap, body: &str) {
+    let mut entry = format!(
+        "{} ← {} {}\n",
+        chrono::Utc::now().to_rfc3339(),
+        status,
+        scrub(url)
+    );
+    for (name, value) in headers {
+        let value = if SECRET_HEADERS.contains(&name.as_str()) {
+            "<redacted>".to_string()
+        } else {
+            scrub(&String::from_utf8_lossy(value.as_bytes()))
+        };
+        entry.push_str(&format!("  {}: {}\n", name, value));
+    }
+    if !body.is_empty() {
+        entry.push_str(&format!("\n{}\n", truncate(&scrub(body))));
+    }
+    entry.push('\n');
+    write_entry(&entry);
+}
+
+/// Entries are written whole so concurrent uploads don't interleave lines.
+/// Tracing is best effort and never fails the call it records.
+fn write_entry(entry: &str) {
+    if let Some(file) = TRACE.lock().unwrap().as_mut() {
+        let _ = file.write_all(entry.as_bytes());
+    }
+}
+
+/// Replace the values of [`SECRET_KEYS`] in query strings, form bodies
+/// (`key=value`) and JSON (`"key": "value"`)
+fn scrub(text: &str) -> String {
+    let mut scrubbed = text.to_string();
+    for key in SECRET_KEYS {
+        let mut from = 0;
+        while let Some(found) = scrubbed[from..].find(key) {
+            let start = from + found;
+            let after_key = start + key.len();
+            from = after_key;
+
+            // Only whole keys: `code` shouldn't match inside `status_code`
+            let before = scrubbed[..start].chars().next_back();
+            if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                continue;
+            }
+            let rest = &scrubbed[after_key..];
+            let value = if let Some(value) = rest.strip_prefix('=') {
+                let len = value
+                    .find(|c: char| c == '&' || c == '"' || c.is_whitespace())
+                    .unwrap_or(value.len());
+                Some((after_key + 1, len))
+            } else if let Some(value) = rest.strip_prefix('"') {
+                // `"key": "value"`, skipping to the opening quote
+                let trimmed = value.trim_start();
+                trimmed.strip_prefix(':').and_then(|value| {
+                    let value = value.trim_start().strip_prefix('"')?;
+                    let offset = scrubbed.len() - value.len();
+                    let len = value.find('"').unwrap_or(value.len());
+                    Some((offset, len))
+                })
+            } else {
+                None
+            };
+
+            if let Some((offset, len)) = value
+                && len > 0
+            {
+                scrubbed.replace_range(offset..offset + len, "<redacted>");
+                from = offset + "<redacted>".len();
+            }
+        }
+    }
+    scrubbed
+}
+
+fn truncate(body: &str) -> String {
+    let len = body.chars().count();
+    if len <= MAX_BODY_CHARS {
+        return body.to_string();
+    }
+    let kept: String = body.chars().take(MAX_BODY_CHARS).collect();
+    format!("{}… ({} more characters)", kept, len - MAX_BODY_CHARS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub() {
+        assert_eq!(
+            scrub("https://api.mixcloud.com/upload/?access_token=abc123&x=1"),
+            "https://api.mixcloud.com/upload/?access_token=<redacted>&x=1"
+        );
+        assert_eq!(
+            scrub(r#"{"access_token": "abc", "refresh_token":"def", "expires_in": 3600}"#),
+            r#"{"access_token": "<redacted>", "refresh_token":"<redacted>", "expires_in": 3600}"#
+        );
+        assert_eq!(
+            scrub("client_id=id&client_secret=s3cret&code=xyz"),
+            "client_id=id&client_secret=<redacted>&code=<redacted>"
+        );
+        assert_eq!(
+            scrub(r#"{"status_code": "400", "error": "invalid code"}"#),
+            r#"{"status_code": "400", "error": "invalid code"}"#
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let body = "é".repeat(MAX_BODY_CHARS + 5);
+        let truncated = truncate(&body);
+        assert!(truncated.ends_with("… (5 more characters)"));
+        assert_eq!(truncated.chars().count(), MAX_BODY_CHARS + 21);
+    }
+}
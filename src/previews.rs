@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::audio::{self, PreviewPlacement, PreviewProgress, SilenceCompression};
+use crate::cli::{ExistingOutput, FadeCurve, PreviewFormat, PreviewMode, WavSampleFormat};
+use crate::output;
+
+/// How to generate preview snippets for one file
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    pub durations: Vec<u64>,
+    pub format: PreviewFormat,
+    pub sample_format: WavSampleFormat,
+    /// Where the snippets go; next to the file when None
+    pub output_dir: Option<PathBuf>,
+    pub placement: PreviewPlacement,
+    pub fade_curve: FadeCurve,
+    /// Shorten quiet passages in each chunk; None leaves them alone
+    pub compress_silence: Option<SilenceCompression>,
+    pub existing: ExistingOutput,
+    /// Disk space in MB to leave free
+    pub min_free_space: u64,
+}
+
+/// Durations selected by the preview flags, or None if no previews were asked for.
+/// Explicit durations win, then one preview covering every explicit chunk
+/// start time, then `auto`, then the standard set.
+pub fn select_durations(
+    file: &Path,
+    generate_previews: bool,
+    mode: Option<PreviewMode>,
+    durations: Option<Vec<u64>>,
+    timestamps: Option<&[f64]>,
+) -> Option<Vec<u64>> {
+    if let Some(durations) = durations {
+        Some(durations)
+    } else if let Some(timestamps) = timestamps {
+        Some(vec![timestamps.len() as u64 * audio::PREVIEW_CHUNK_SECS])
+    } else if let Some(PreviewMode::Auto) = mode {
+        let durations = audio::get_audio_duration(file)
+            .map(audio::auto_preview_durations)
+            .unwrap_or_else(|_| audio::DEFAULT_PREVIEW_DURATIONS.to_vec());
+        Some(durations)
+    } else if generate_previews || mode.is_some() {
+        Some(audio::DEFAULT_PREVIEW_DURATIONS.to_vec())
+    } else {
+        None
+    }
+}
+
+/// Write the snippets, checking first that they fit on disk
+pub fn generate(
+    file: &Path,
+    options: &PreviewOptions,
+    on_progress: &dyn Fn(&PreviewProgress),
+) -> Result<Vec<PathBuf>> {
+    let preview_dir = match &options.output_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            dir.as_path()
+        }
+        None => file
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new(".")),
+    };
+    if let Some(compression) = options.compress_silence
+        && (compression.max_gap_secs <= 0.0 || compression.threshold_db >= 0.0)
+    {
+        anyhow::bail!(
+            "The silence threshold must be below 0 dBFS and the maximum gap above 0 seconds"
+        );
+    }
+    let total_secs = options.durations.iter().sum::<u64>() as f64;
+    let metadata = audio::probe_metadata(file)?;
+    if let Some(warning) = metadata.track_warning() {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+    let needed = match options.format {
+        // The estimate is for 16-bit samples
+        PreviewFormat::Wav => {
+            audio::estimate_wav_bytes(&metadata, total_secs, false)
+                * options.sample_format.bytes_per_sample()
+                / 2
+        }
+        PreviewFormat::Mp3 => total_secs as u64 * audio::MP3_PREVIEW_KBPS * 1000 / 8,
+    };
+    output::ensure_free_space(preview_dir, needed, options.min_free_space)?;
+
+    audio::create_preview_snippets(
+        file,
+        preview_dir,
+        options.format,
+        options.sample_format,
+        &options.durations,
+        &options.placement,
+        options.fade_curve,
+        options.compress_silence,
+        options.existing,
+        on_progress,
+    )
+}
+
+/// Print what was generated. A failure is only a warning, since previews
+/// are never worth stopping an upload for.
+pub fn report(result: Result<Vec<PathBuf>>) {
+    match result {
+        Ok(snippets) => {
+            println!("✓ Generated {} preview snippets:", snippets.len());
+            for snippet in &snippets {
+                println!("  - {}", snippet.display());
+            }
+        }
+        Err(e) => eprintln!("⚠ Warning: Failed to generate previews: {}", e),
+    }
+}
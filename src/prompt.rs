@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the global --yes flag
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// Whether --yes was given, so prompts answer themselves
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Whether there is someone at a terminal to answer questions
+pub fn interactive() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// Ask a yes/no question. --yes answers yes; without a terminal the answer
+/// is `default`, which callers set to the safe choice for destructive steps.
+/// An empty answer also takes the default.
+pub fn confirm(question: &str, default: bool) -> Result<bool> {
+    if assume_yes() {
+        return Ok(true);
+    }
+    if !interactive() {
+        return Ok(default);
+    }
+
+    print!("{} {} ", question, if default { "[Y/n]" } else { "[y/N]" });
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
@@ -0,0 +1,212 @@
+use log::info;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::cli::Platform;
+use crate::config::Settings;
+
+/// Pause used after a 429 that didn't say how long to wait
+const DEFAULT_PAUSE: Duration = Duration::from_secs(60);
+/// Longest pause a Retry-After can ask for; anything beyond is clamped
+const MAX_PAUSE: Duration = Duration::from_secs(60 * 60);
+
+/// Token bucket shared by every batch worker uploading to one platform.
+/// Workers take a token before each upload; a 429 empties the bucket and
+/// holds everyone back until the platform's reset time.
+pub struct RateLimiter {
+    platform: Platform,
+    burst: f64,
+    per_sec: f64,
+    state: Mutex<State>,
+}
+
+struct State {
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(platform: Platform, burst: u32, per_minute: f64) -> Self {
+        let burst = burst.max(1) as f64;
+        info!(
+            "{} rate limit: {:.1} uploads/minute, bursts of {}",
+            platform, per_minute, burst
+        );
+        Self {
+            platform,
+            burst,
+            per_sec: per_minute.max(0.01) / 60.0,
+            state: Mutex::new(State {
+                tokens: burst,
+                refilled_at: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    /// Limits for `platform`: settings.json values, or cautious defaults.
+    /// Neither platform publishes an upload quota; both answer 429 with a
+    /// Retry-After header when a client goes too fast.
+    pub fn for_platform(platform: Platform, settings: &Settings) -> Self {
+        let (burst, per_minute) = match platform {
+            Platform::Mixcloud => (2, 4.0),
+            Platform::Soundcloud => (3, 6.0),
+        };
+        Self::new(
+            platform,
+            settings.batch_burst.unwrap_or(burst),
+            settings.batch_uploads_per_minute.unwrap_or(per_minute),
+        )
+    }
+
+    /// Take a token, or return how long to wait before trying again
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(until) = state.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            state.paused_until = None;
+            state.refilled_at = now;
+        }
+
+        let elapsed = now
+            .saturating_duration_since(state.refilled_at)
+            .as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.per_sec).min(self.burst);
+        state.refilled_at = now;
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - state.tokens) / self.per_sec))
+        }
+    }
+
+    /// Block the calling thread until a token is available
+    #[cfg(not(feature = "async"))]
+    pub fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            info!(
+                "{} rate limit: waiting {:.1}s before the next upload",
+                self.platform,
+                wait.as_secs_f64()
+            );
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Wait for a token without blocking the async runtime
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self) {
+        while let Err(wait) = self.try_acquire() {
+            info!(
+                "{} rate limit: waiting {:.1}s before the next upload",
+                self.platform,
+                wait.as_secs_f64()
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Hold back every worker after a 429, for `retry_after` if the platform
+    /// said how long. Returns the pause applied.
+    pub fn pause(&self, retry_after: Option<Duration>) -> Duration {
+        self.pause_at(Instant::now(), retry_after)
+    }
+
+    fn pause_at(&self, now: Instant, retry_after: Option<Duration>) -> Duration {
+        let pause = retry_after.unwrap_or(DEFAULT_PAUSE).min(MAX_PAUSE);
+        let mut state = self.state.lock().unwrap();
+        if let Some(until) = now.checked_add(pause) {
+            state.paused_until = Some(
+                state
+                    .paused_until
+                    .map_or(until, |current| current.max(until)),
+            );
+        }
+        state.tokens = 0.0;
+        info!(
+            "{} rate limited, pausing uploads for {}s",
+            self.platform,
+            pause.as_secs()
+        );
+        pause
+    }
+}
+
+/// The limiter for each platform in a batch
+pub struct RateLimiters {
+    mixcloud: RateLimiter,
+    soundcloud: RateLimiter,
+}
+
+impl RateLimiters {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            mixcloud: RateLimiter::for_platform(Platform::Mixcloud, settings),
+            soundcloud: RateLimiter::for_platform(Platform::Soundcloud, settings),
+        }
+    }
+
+    pub fn get(&self, platform: Platform) -> &RateLimiter {
+        match platform {
+            Platform::Mixcloud => &self.mixcloud,
+            Platform::Soundcloud => &self.soundcloud,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(Platform::Soundcloud, 2, 6.0);
+        let start = Instant::now();
+
+        // The burst is available straight away, then one token every 10s
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        let wait = limiter.try_acquire_at(start).unwrap_err();
+        assert_eq!(wait.as_secs(), 10);
+        assert!(
+            limiter
+                .try_acquire_at(start + Duration::from_secs(10))
+                .is_ok()
+        );
+
+        // A 429 holds everything until the reset time
+        let paused_at = start + Duration::from_secs(20);
+        limiter.pause_at(paused_at, Some(Duration::from_secs(30)));
+        let wait = limiter
+            .try_acquire_at(paused_at + Duration::from_secs(5))
+            .unwrap_err();
+        assert_eq!(wait.as_secs(), 25);
+        let resumed = paused_at + Duration::from_secs(30);
+        assert!(limiter.try_acquire_at(resumed).is_err());
+        assert!(
+            limiter
+                .try_acquire_at(resumed + Duration::from_secs(10))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_huge_retry_after_is_clamped() {
+        let limiter = RateLimiter::new(Platform::Mixcloud, 1, 6.0);
+        let now = Instant::now();
+        let pause = limiter.pause_at(now, Some(Duration::from_secs(u64::MAX)));
+        assert_eq!(pause, MAX_PAUSE);
+        let wait = limiter.try_acquire_at(now).unwrap_err();
+        assert_eq!(wait, MAX_PAUSE);
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::audio;
+
+/// Everything known about one audio file. The probe fields are always
+/// filled in; levels and tempo need a full decode and are only present
+/// when asked for.
+#[derive(Debug, Serialize)]
+pub struct FileReport {
+    pub file: String,
+    pub size_bytes: u64,
+    pub mime: Option<String>,
+    pub codec: Option<String>,
+    pub duration_secs: Option<f64>,
+    /// "container", "bitrate estimate" or "counted"
+    pub duration_source: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+    pub bitrate_kbps: Option<u64>,
+    pub has_cover: bool,
+    pub audio_tracks: usize,
+    /// Things about the file that may give surprising results
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    pub tags: Vec<Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub levels: Option<LevelReport>,
+    /// Only present when the tempo was measured; null means no clear beat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<Option<f64>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LevelReport {
+    pub peak_dbfs: f64,
+    pub rms_dbfs: f64,
+    pub clipped_samples: u64,
+}
+
+/// Probe a file, plus the slow analyses that were asked for
+pub fn describe_file(
+    path: &Path,
+    levels: bool,
+    bpm: bool,
+    exact_duration: bool,
+) -> Result<FileReport> {
+    let size_bytes = std::fs::metadata(path)
+        .with_context(|| format!("File not found: {}", path.display()))?
+        .len();
+    let mut metadata = audio::probe_metadata(path)?;
+
+    // Replace an estimate with a count of every packet
+    if exact_duration && metadata.duration_source != Some(audio::DurationSource::Container) {
+        let duration = audio::measure_duration(path, true)?;
+        metadata.duration_secs = Some(duration.secs);
+        metadata.duration_source = Some(duration.source);
+    }
+
+    let levels = if levels {
+        let measured = audio::analyze_levels(path)?;
+        Some(LevelReport {
+            peak_dbfs: measured.peak_dbfs,
+            rms_dbfs: measured.rms_dbfs,
+            clipped_samples: measured.clipped_samples,
+        })
+    } else {
+        None
+    };
+    let bpm = if bpm {
+        Some(audio::estimate_bpm(path)?.map(|bpm| (bpm * 10.0).round() / 10.0))
+    } else {
+        None
+    };
+
+    let warnings = metadata.track_warning().into_iter().collect();
+
+    Ok(FileReport {
+        file: path.display().to_string(),
+        size_bytes,
+        mime: audio::audio_mime(path).ok().map(str::to_string),
+        codec: metadata.codec,
+        duration_secs: metadata.duration_secs,
+        duration_source: metadata.duration_source.map(|source| source.to_string()),
+        sample_rate: metadata.sample_rate,
+        channels: metadata.channels,
+        bitrate_kbps: metadata.bitrate_kbps,
+        has_cover: metadata.has_cover,
+        audio_tracks: metadata.audio_tracks,
+        warnings,
+        tags: metadata
+            .tags
+            .into_iter()
+            .map(|(key, value)| Tag { key, value })
+            .collect(),
+        levels,
+        bpm,
+    })
+}
+
+/// Human-readable version of the report
+pub fn print_report(report: &FileReport) {
+    let unknown = || "unknown".to_string();
+
+    println!("File: {}", report.file);
+    println!(
+        "  Size:        {}",
+        crate::output::format_size(report.size_bytes)
+    );
+    println!(
+        "  Format:      {} ({})",
+        report.codec.clone().unwrap_or_else(unknown),
+        report.mime.clone().unwrap_or_else(unknown)
+    );
+    println!(
+        "  Duration:    {}",
+        report
+            .duration_secs
+            .map(|secs| match &report.duration_source {
+                Some(source) => format!("{:.1}s ({})", secs, source),
+                None => format!("{:.1}s", secs),
+            })
+            .unwrap_or_else(unknown)
+    );
+    println!(
+        "  Sample rate: {}",
+        report
+            .sample_rate
+            .map(|rate| format!("{} Hz", rate))
+            .unwrap_or_else(unknown)
+    );
+    println!(
+        "  Channels:    {}",
+        report
+            .channels
+            .map(|channels| channels.to_string())
+            .unwrap_or_else(unknown)
+    );
+    println!(
+        "  Bitrate:     {}",
+        report
+            .bitrate_kbps
+            .map(|kbps| format!("~{} kbps", kbps))
+            .unwrap_or_else(unknown)
+    );
+    println!(
+        "  Cover:       {}",
+        if report.has_cover { "embedded" } else { "none" }
+    );
+    if report.audio_tracks > 1 {
+        println!("  Tracks:      {} audio", report.audio_tracks);
+    }
+    for warning in &report.warnings {
+        println!("  ⚠ {}", warning);
+    }
+
+    if let Some(levels) = &report.levels {
+        println!("  Peak:        {}", format_dbfs(levels.peak_dbfs));
+        println!("  RMS:         {}", format_dbfs(levels.rms_dbfs));
+        println!("  Clipped:     {} samples", levels.clipped_samples);
+    }
+    if let Some(bpm) = report.bpm {
+        match bpm {
+            Some(bpm) => println!("  Tempo:       ~{:.1} BPM", bpm),
+            None => println!("  Tempo:       no clear beat"),
+        }
+    }
+
+    if !report.tags.is_empty() {
+        println!("  Tags:");
+        for tag in &report.tags {
+            println!("    {}: {}", tag.key, tag.value);
+        }
+    }
+}
+
+/// A level in dBFS, or "silent" at the floor `analyze_levels` clamps to
+fn format_dbfs(dbfs: f64) -> String {
+    if dbfs <= audio::SILENCE_DBFS {
+        "silent".to_string()
+    } else {
+        format!("{:.2} dBFS", dbfs)
+    }
+}
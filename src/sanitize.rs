@@ -0,0 +1,257 @@
+use crate::cli::{Platform, SanitizeMode};
+use crate::limits::Limits;
+
+/// Which piece of metadata is being cleaned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Description,
+    Tags,
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::Title => write!(f, "Title"),
+            Field::Description => write!(f, "Description"),
+            Field::Tags => write!(f, "Tags"),
+        }
+    }
+}
+
+/// Something the sanitizer changed, or would change with `SanitizeMode::Strip`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub field: Field,
+    pub message: String,
+    /// Whether the text was changed to fix it
+    pub fixed: bool,
+}
+
+/// Clean up a title or description for `platform`. Control characters are
+/// always removed (descriptions keep line breaks and tabs). Links in titles
+/// and excess emoji are reported (see [`Limits::urls_in_title`] and
+/// [`Limits::title_emoji_max`]), and only removed with `SanitizeMode::Strip`.
+///
+/// Mixcloud rejects titles with links and mangles emoji-heavy ones in
+/// listings; on SoundCloud links in titles aren't clickable and get tracks
+/// flagged as spam.
+pub fn sanitize_for(
+    platform: Platform,
+    limits: &Limits,
+    field: Field,
+    text: &str,
+    mode: SanitizeMode,
+) -> (String, Vec<Warning>) {
+    let mut warnings = Vec::new();
+    let strip = mode == SanitizeMode::Strip;
+
+    // Line breaks and tabs in a title become spaces, other controls go
+    let mut removed = 0;
+    let mut text: String = text
+        .chars()
+        .filter_map(|c| {
+            if !c.is_control() || (field == Field::Description && matches!(c, '\n' | '\t')) {
+                return Some(c);
+            }
+            removed += 1;
+            c.is_whitespace().then_some(' ')
+        })
+        .collect();
+    if removed > 0 {
+        warnings.push(Warning {
+            field,
+            message: format!("removed {} control character(s)", removed),
+            fixed: true,
+        });
+    }
+
+    if field == Field::Title {
+        if !limits.urls_in_title {
+            let urls: Vec<&str> = text.split_whitespace().filter(|w| is_url(w)).collect();
+            if !urls.is_empty() {
+                let message = format!(
+                    "{} doesn't allow links in titles ({})",
+                    platform,
+                    urls.join(", ")
+                );
+                if strip {
+                    text = text
+                        .split_whitespace()
+                        .filter(|w| !is_url(w))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                }
+                warnings.push(Warning {
+                    field,
+                    message,
+                    fixed: strip,
+                });
+            }
+        }
+
+        let emoji = text.chars().filter(|&c| is_emoji(c)).count();
+        if emoji > limits.title_emoji_max {
+            let message = format!(
+                "{} emoji, {} shows at most {} cleanly",
+                emoji, platform, limits.title_emoji_max
+            );
+            if strip {
+                let mut kept = 0;
+                text = text
+                    .chars()
+                    .filter(|&c| {
+                        if !is_emoji(c) {
+                            return true;
+                        }
+                        kept += 1;
+                        kept <= limits.title_emoji_max
+                    })
+                    .collect();
+                text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            }
+            warnings.push(Warning {
+                field,
+                message,
+                fixed: strip,
+            });
+        }
+    }
+
+    (text, warnings)
+}
+
+fn is_url(word: &str) -> bool {
+    let word = word.to_ascii_lowercase();
+    word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www.")
+}
+
+/// Characters drawn as emoji by default (`Emoji_Presentation=Yes` in the
+/// Unicode emoji-data.txt); symbols like ★ or ♫ that are text by default
+/// don't count
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x231A..=0x231B
+            | 0x23E9..=0x23EC
+            | 0x23F0
+            | 0x23F3
+            | 0x25FD..=0x25FE
+            | 0x2614..=0x2615
+            | 0x2648..=0x2653
+            | 0x267F
+            | 0x2693
+            | 0x26A1
+            | 0x26AA..=0x26AB
+            | 0x26BD..=0x26BE
+            | 0x26C4..=0x26C5
+            | 0x26CE
+            | 0x26D4
+            | 0x26EA
+            | 0x26F2..=0x26F3
+            | 0x26F5
+            | 0x26FA
+            | 0x26FD
+            | 0x2705
+            | 0x270A..=0x270B
+            | 0x2728
+            | 0x274C
+            | 0x274E
+            | 0x2753..=0x2755
+            | 0x2757
+            | 0x2795..=0x2797
+            | 0x27B0
+            | 0x27BF
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+            | 0x1F004
+            | 0x1F0CF
+            | 0x1F18E
+            | 0x1F191..=0x1F19A
+            | 0x1F1E6..=0x1F1FF
+            | 0x1F201
+            | 0x1F21A
+            | 0x1F22F
+            | 0x1F232..=0x1F236
+            | 0x1F238..=0x1F23A
+            | 0x1F250..=0x1F251
+            | 0x1F300..=0x1F64F
+            | 0x1F680..=0x1F6FF
+            | 0x1F7E0..=0x1F7EB
+            | 0x1F900..=0x1F9FF
+            | 0x1FA70..=0x1FAFF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_for() {
+        let mixcloud = Limits::defaults(Platform::Mixcloud);
+        let (text, warnings) = sanitize_for(
+            Platform::Mixcloud,
+            &mixcloud,
+            Field::Description,
+            "Line one\nLine\u{7} two",
+            SanitizeMode::Warn,
+        );
+        assert_eq!(text, "Line one\nLine two");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].fixed);
+
+        let title = "Sunday Set https://example.com 🔥🔥🔥🔥";
+        let (text, warnings) = sanitize_for(
+            Platform::Mixcloud,
+            &mixcloud,
+            Field::Title,
+            title,
+            SanitizeMode::Warn,
+        );
+        assert_eq!(text, title);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| !w.fixed));
+
+        let (text, warnings) = sanitize_for(
+            Platform::Mixcloud,
+            &mixcloud,
+            Field::Title,
+            title,
+            SanitizeMode::Strip,
+        );
+        assert_eq!(text, "Sunday Set 🔥🔥🔥");
+        assert!(warnings.iter().all(|w| w.fixed));
+
+        let (text, warnings) = sanitize_for(
+            Platform::Soundcloud,
+            &Limits::defaults(Platform::Soundcloud),
+            Field::Title,
+            "Tab\tTitle",
+            SanitizeMode::Warn,
+        );
+        assert_eq!(text, "Tab Title");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_is_emoji() {
+        assert!(is_emoji('🔥'));
+        assert!(is_emoji('⚡'));
+        assert!(!is_emoji('★'));
+        assert!(!is_emoji('♫'));
+        assert!(!is_emoji('é'));
+
+        // Symbols don't use up the emoji allowance
+        let mixcloud = Limits::defaults(Platform::Mixcloud);
+        let (_, warnings) = sanitize_for(
+            Platform::Mixcloud,
+            &mixcloud,
+            Field::Title,
+            "★ Sunday Set ♫♫♫ 🔥",
+            SanitizeMode::Warn,
+        );
+        assert!(warnings.is_empty());
+    }
+}
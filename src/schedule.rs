@@ -0,0 +1,96 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use std::path::Path;
+
+/// Convert a local date/time into the UTC format Mixcloud expects
+/// (YYYY-MM-DDTHH:MM:SSZ)
+pub fn local_to_utc_publish_date(naive_datetime: &NaiveDateTime) -> Result<String> {
+    let local_datetime = Local
+        .from_local_datetime(naive_datetime)
+        .single()
+        .ok_or_else(|| anyhow::anyhow!("Ambiguous local time"))?;
+
+    let utc_datetime = local_datetime.with_timezone(&chrono::Utc);
+    Ok(utc_datetime.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Read a publish date from the start of a file name, e.g.
+/// `2024-06-01 2000 Mix.mp3` -> 2024-06-01 20:00.
+/// Recognizes YYYY-MM-DD, YYYY_MM_DD, YYYY.MM.DD and YYYYMMDD, optionally
+/// followed by HHMM, HH:MM, HH.MM or HHhMM. Without a time, midnight is used.
+pub fn parse_filename_schedule(file_path: &Path) -> Option<NaiveDateTime> {
+    let stem = file_path.file_stem()?.to_str()?;
+    parse_leading_datetime(stem)
+}
+
+fn parse_leading_datetime(stem: &str) -> Option<NaiveDateTime> {
+    let mut tokens = stem.split_whitespace();
+
+    let first = tokens.next()?;
+    let (date, rest) = parse_leading_date(first)?;
+
+    // The time can share the token ("2024-06-01_2000") or be the next one
+    let time_token = rest.trim_start_matches(['_', '-', 'T', '.']);
+    let time = if time_token.is_empty() {
+        tokens.next().and_then(parse_time)
+    } else {
+        parse_time(time_token)
+    };
+
+    Some(NaiveDateTime::new(date, time.unwrap_or(NaiveTime::MIN)))
+}
+
+/// Parse a date at the start of `token`, returning it and the unparsed rest
+fn parse_leading_date(token: &str) -> Option<(NaiveDate, &str)> {
+    if let (Some(candidate), Some(rest)) = (token.get(..10), token.get(10..)) {
+        for separator in ['-', '_', '.'] {
+            let format = format!("%Y{0}%m{0}%d", separator);
+            if let Ok(date) = NaiveDate::parse_from_str(candidate, &format) {
+                return Some((date, rest));
+            }
+        }
+    }
+
+    let candidate = token.get(..8)?;
+    if candidate.chars().all(|c| c.is_ascii_digit()) {
+        let date = NaiveDate::parse_from_str(candidate, "%Y%m%d").ok()?;
+        return Some((date, token.get(8..)?));
+    }
+
+    None
+}
+
+fn parse_time(token: &str) -> Option<NaiveTime> {
+    ["%H%M", "%H:%M", "%H.%M", "%Hh%M"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(token, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_leading_datetime() {
+        assert_eq!(
+            parse_leading_datetime("2024-06-01 2000 Mix"),
+            Some(at("2024-06-01", "20:00"))
+        );
+        assert_eq!(
+            parse_leading_datetime("2024_06_01_21:30 Sunset Set"),
+            Some(at("2024-06-01", "21:30"))
+        );
+        assert_eq!(
+            parse_leading_datetime("20240601 Warmup"),
+            Some(at("2024-06-01", "00:00"))
+        );
+        assert_eq!(parse_leading_datetime("Mix 2024-06-01"), None);
+    }
+}
@@ -0,0 +1,248 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried next to the audio file, in order
+const SIDECAR_EXTENSIONS: &[&str] = &["json", "yaml", "yml"];
+
+/// Fields a sidecar may set
+const KNOWN_FIELDS: &[&str] = &["title", "description", "tags", "genre", "publish_date"];
+
+/// Metadata from a sidecar file (`mix.json` or `mix.yaml` next to
+/// `mix.mp3`). Every field is optional and command-line flags win.
+///
+/// ```yaml
+/// title: Sunday Set
+/// genre: Deep House
+/// tags: [house, vinyl]
+/// publish_date: 2026-03-14 18:00
+/// description: |
+///   Three hours of records.
+///   Recorded live.
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct SidecarMeta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// A list, or one comma-separated string like `--tags`
+    #[serde(default, deserialize_with = "tags_or_list")]
+    pub tags: Option<Vec<String>>,
+    /// Sent as the first tag; Mixcloud tags are its genres
+    pub genre: Option<String>,
+    /// Local time as YYYY-MM-DD HH:MM, like --publish-date
+    pub publish_date: Option<String>,
+}
+
+impl SidecarMeta {
+    /// Tags with the genre first, unless it's already among them
+    pub fn tags_with_genre(&self) -> Option<Vec<String>> {
+        let mut tags = self.tags.clone().unwrap_or_default();
+        if let Some(genre) = &self.genre
+            && !tags.iter().any(|tag| tag.eq_ignore_ascii_case(genre))
+        {
+            tags.insert(0, genre.clone());
+        }
+        (!tags.is_empty()).then_some(tags)
+    }
+}
+
+fn tags_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tags {
+        List(Vec<String>),
+        Joined(String),
+    }
+    Ok(
+        Option::<Tags>::deserialize(deserializer)?.map(|tags| match tags {
+            Tags::List(list) => list,
+            Tags::Joined(joined) => crate::tags::parse_tags(&joined),
+        }),
+    )
+}
+
+/// Sidecar file for an audio file, if there is one
+pub fn sidecar_path(audio_path: &Path) -> Option<PathBuf> {
+    SIDECAR_EXTENSIONS
+        .iter()
+        .map(|extension| audio_path.with_extension(extension))
+        .find(|path| path.is_file())
+}
+
+/// Read and validate a sidecar, warning about fields it doesn't know.
+/// `explicit` is a path from --sidecar, which has to be valid; one found next
+/// to `audio_path` that doesn't parse is skipped with a warning. None when
+/// there's no usable sidecar.
+pub fn load_sidecar(audio_path: &Path, explicit: Option<&Path>) -> Result<Option<SidecarMeta>> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => match sidecar_path(audio_path) {
+            Some(path) => path,
+            None => return Ok(None),
+        },
+    };
+
+    let meta = match read_sidecar(&path) {
+        Ok(meta) => meta,
+        Err(e) if explicit.is_none() => {
+            eprintln!("⚠ Warning: Sidecar ignored: {:#}", e);
+            return Ok(None);
+        }
+        Err(e) => return Err(e),
+    };
+    println!("✓ Read metadata from {}", path.display());
+    Ok(Some(meta))
+}
+
+fn read_sidecar(path: &Path) -> Result<SidecarMeta> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension == "json");
+
+    let (meta, unknown) =
+        parse_sidecar(&contents, is_json).with_context(|| format!("Invalid {}", path.display()))?;
+    for field in unknown {
+        eprintln!(
+            "⚠ Warning: {}: unknown field \"{}\" ignored (known: {})",
+            path.display(),
+            field,
+            KNOWN_FIELDS.join(", ")
+        );
+    }
+    Ok(meta)
+}
+
+/// The metadata and the names of any fields that aren't part of the schema
+fn parse_sidecar(contents: &str, is_json: bool) -> Result<(SidecarMeta, Vec<String>)> {
+    let value: Value = if is_json {
+        serde_json::from_str(contents)?
+    } else {
+        serde_yaml::from_str(contents)?
+    };
+    let mut fields = match value {
+        Value::Object(fields) => fields,
+        // An empty YAML document
+        Value::Null if !is_json => Default::default(),
+        _ => bail!(
+            "expected {} of fields",
+            if is_json {
+                "a JSON object"
+            } else {
+                "a YAML mapping"
+            }
+        ),
+    };
+
+    let unknown: Vec<String> = fields
+        .keys()
+        .filter(|key| !KNOWN_FIELDS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    for key in &unknown {
+        fields.remove(key);
+    }
+
+    let mut meta: SidecarMeta = serde_json::from_value(Value::Object(fields))?;
+    // A YAML `|` block ends in a newline
+    meta.description = meta
+        .description
+        .map(|description| description.trim_end().to_string());
+    if let Some(date) = &meta.publish_date {
+        chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M")
+            .with_context(|| format!("publish_date \"{}\" is not YYYY-MM-DD HH:MM", date))?;
+    }
+    if meta
+        .title
+        .as_deref()
+        .is_some_and(|title| title.trim().is_empty())
+    {
+        bail!("title is empty");
+    }
+    Ok((meta, unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml_sidecar() {
+        let yaml = "\
+# exported from the library
+title: \"Sunday Set: Live\"
+genre: \"Deep House\"  # main style
+tags:
+  - house
+  - 'vinyl only'
+publish_date: 2026-03-14 18:00
+description: |
+  Three hours of records.
+
+  Recorded live.
+bpm: 124
+";
+        let (meta, unknown) = parse_sidecar(yaml, false).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Sunday Set: Live"));
+        assert_eq!(
+            meta.description.as_deref(),
+            Some("Three hours of records.\n\nRecorded live.")
+        );
+        assert_eq!(
+            meta.tags_with_genre(),
+            Some(vec![
+                "Deep House".to_string(),
+                "house".to_string(),
+                "vinyl only".to_string()
+            ])
+        );
+        assert_eq!(unknown, vec!["bpm"]);
+
+        // Flow lists keep quoted commas, and double quotes take escapes
+        let (meta, _) = parse_sidecar(
+            "tags: [\"drum, bass\", jungle]\ntitle: \"Set \\u2605 \\\"Live\\\"\"",
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            meta.tags,
+            Some(vec!["drum, bass".to_string(), "jungle".to_string()])
+        );
+        assert_eq!(meta.title.as_deref(), Some("Set ★ \"Live\""));
+        assert!(parse_sidecar("- just\n- a list\n", false).is_err());
+    }
+
+    #[test]
+    fn test_invalid_sidecar_warns_unless_explicit() {
+        let dir = crate::temp::TestDir::new("sidecar");
+        let audio = dir.join("mix.mp3");
+        let yaml = dir.join("mix.yaml");
+        fs::write(&yaml, "title: [unclosed\n").unwrap();
+
+        assert_eq!(load_sidecar(&audio, None).unwrap(), None);
+        assert!(load_sidecar(&audio, Some(&yaml)).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_sidecar() {
+        let json =
+            r#"{"title": "Set", "tags": "house, techno", "publish_date": "2026-03-14 18:00"}"#;
+        let (meta, unknown) = parse_sidecar(json, true).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Set"));
+        assert_eq!(
+            meta.tags,
+            Some(vec!["house".to_string(), "techno".to_string()])
+        );
+        assert!(unknown.is_empty());
+
+        assert!(parse_sidecar(r#"{"publish_date": "tomorrow"}"#, true).is_err());
+        assert!(parse_sidecar(r#"{"tags": 5}"#, true).is_err());
+        assert!(parse_sidecar("[1, 2]", true).is_err());
+    }
+}
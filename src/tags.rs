@@ -0,0 +1,184 @@
+use anyhow::{Result, bail};
+
+use crate::cli::{Platform, TagDedupe};
+use crate::limits::Limits;
+
+/// Split a comma-separated tag string into trimmed, non-empty tags
+pub fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Characters the platform's tag parser chokes on or drops
+fn forbidden_chars(platform: Platform) -> &'static [char] {
+    match platform {
+        // Mixcloud takes one tag per form field, so only separators matter
+        Platform::Mixcloud => &[',', ';'],
+        // SoundCloud joins tags with spaces and quotes multi-word tags
+        Platform::Soundcloud => &[',', '"', ';'],
+    }
+}
+
+/// Clean up a single tag for a platform. Mixcloud silently cuts long tags
+/// off, so they're cut to `max_len` here where it can be reported.
+fn sanitize_tag(platform: Platform, tag: &str, max_len: usize) -> String {
+    let forbidden = forbidden_chars(platform);
+    let cleaned: String = tag.chars().filter(|c| !forbidden.contains(c)).collect();
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match platform {
+        Platform::Mixcloud => cleaned.chars().take(max_len).collect(),
+        Platform::Soundcloud => cleaned,
+    }
+}
+
+/// Sanitize tags for a platform, warning about anything that changed.
+/// With `strict`, any change is an error instead of a warning.
+pub fn validate_tags(
+    platform: Platform,
+    limits: &Limits,
+    tags: &[String],
+    strict: bool,
+) -> Result<Vec<String>> {
+    let mut sanitized = Vec::new();
+    let mut changed = Vec::new();
+    let max_len = limits.tag_len_max;
+
+    for tag in tags {
+        let cleaned = sanitize_tag(platform, tag, max_len);
+        if cleaned != *tag {
+            changed.push(format!("\"{}\" -> \"{}\"", tag, cleaned));
+        }
+        if !cleaned.is_empty() {
+            sanitized.push(cleaned);
+        }
+    }
+
+    if !changed.is_empty() {
+        if strict {
+            bail!(
+                "Tags contain characters {} does not accept: {}",
+                platform,
+                changed.join(", ")
+            );
+        }
+
+        eprintln!("⚠ Warning: Some tags were adjusted for {}:", platform);
+        for change in &changed {
+            eprintln!("  {}", change);
+        }
+        eprintln!("  Sending: {}", sanitized.join(", "));
+    }
+
+    Ok(sanitized)
+}
+
+/// Comparison key for a tag under a dedupe mode
+fn dedupe_key(tag: &str, mode: TagDedupe) -> String {
+    let lower = tag.trim().to_lowercase();
+    match mode {
+        TagDedupe::Exact => lower,
+        TagDedupe::Normalized => {
+            let words: Vec<String> = lower
+                .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+                .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect())
+                .filter(|word: &String| !word.is_empty())
+                .map(|word| stem(&word))
+                .collect();
+            words.join(" ")
+        }
+    }
+}
+
+/// Very simple plural stripping: "beats" -> "beat", "classes" -> "class"
+fn stem(word: &str) -> String {
+    if word.len() > 4 && word.ends_with("sses") {
+        word[..word.len() - 2].to_string()
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Drop tags that duplicate an earlier one, keeping the first spelling.
+/// Merged tags are reported on stderr.
+pub fn dedupe_tags(tags: Vec<String>, mode: TagDedupe) -> Vec<String> {
+    let mut kept: Vec<(String, String)> = Vec::new();
+    let mut merged = Vec::new();
+
+    for tag in tags {
+        let key = dedupe_key(&tag, mode);
+        match kept.iter().find(|(existing, _)| *existing == key) {
+            Some((_, first)) => {
+                if *first != tag {
+                    merged.push(format!("\"{}\" -> \"{}\"", tag, first));
+                }
+            }
+            None => kept.push((key, tag)),
+        }
+    }
+
+    if !merged.is_empty() {
+        eprintln!("ℹ Merged duplicate tags:");
+        for merge in &merged {
+            eprintln!("  {}", merge);
+        }
+    }
+
+    kept.into_iter().map(|(_, tag)| tag).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_tags() {
+        let tags = parse_tags("deep house, drum \"n\" bass, ,techno");
+        assert_eq!(tags, vec!["deep house", "drum \"n\" bass", "techno"]);
+
+        let soundcloud_limits = Limits::defaults(Platform::Soundcloud);
+
+        let soundcloud =
+            validate_tags(Platform::Soundcloud, &soundcloud_limits, &tags, false).unwrap();
+        assert_eq!(soundcloud, vec!["deep house", "drum n bass", "techno"]);
+        assert!(validate_tags(Platform::Soundcloud, &soundcloud_limits, &tags, true).is_err());
+
+        let long_tag = vec!["a".repeat(100)];
+        let mixcloud_limits = Limits::defaults(Platform::Mixcloud);
+        let mixcloud =
+            validate_tags(Platform::Mixcloud, &mixcloud_limits, &long_tag, false).unwrap();
+        assert_eq!(mixcloud[0].len(), mixcloud_limits.tag_len_max);
+    }
+
+    #[test]
+    fn test_dedupe_tags() {
+        let tags =
+            parse_tags("Techno, techno , Deep-House, deep house, Beats, beat, house, houses");
+
+        let exact = dedupe_tags(tags.clone(), TagDedupe::Exact);
+        assert_eq!(
+            exact,
+            vec![
+                "Techno",
+                "Deep-House",
+                "deep house",
+                "Beats",
+                "beat",
+                "house",
+                "houses"
+            ]
+        );
+
+        let normalized = dedupe_tags(tags, TagDedupe::Normalized);
+        assert_eq!(normalized, vec!["Techno", "Deep-House", "Beats", "house"]);
+
+        // Distinct tags that only share a prefix stay separate
+        let tags = parse_tags("bass, bass house, glass");
+        assert_eq!(dedupe_tags(tags.clone(), TagDedupe::Normalized), tags);
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// Bytes read back from buffered stdin to guess its format; enough to get
+/// past an ID3 tag with embedded artwork
+const SNIFF_LEN: u64 = 1024 * 1024;
+
+/// Working directory for intermediate files such as normalized audio,
+/// tagged copies and generated covers. It's created inside `--temp-dir`
+/// (or the system temp dir) and removed on drop unless `--keep-temp` is set.
+pub struct TempDir {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl TempDir {
+    pub fn new(base: Option<&Path>, keep: bool) -> Result<Self> {
+        let base = base.map_or_else(std::env::temp_dir, Path::to_path_buf);
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let path = base.join(format!("dj-uploader-{}-{}", stamp, std::process::id()));
+
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create temp directory: {}", path.display()))?;
+
+        Ok(Self { path, keep })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Buffer audio piped to stdin into a file named after `title`, since
+    /// probing and the upload form both need a seekable file of known length.
+    /// The audio is streamed to disk and the extension guessed from its
+    /// first bytes afterwards.
+    pub fn save_stdin(&self, title: &str) -> Result<PathBuf> {
+        let mut stdin = io::stdin().lock();
+        if stdin.is_terminal() {
+            bail!("--file - reads audio from a pipe, but stdin is a terminal");
+        }
+
+        let partial = self.path.join("stdin.part");
+        let mut file = fs::File::create(&partial)
+            .with_context(|| format!("Failed to create {}", partial.display()))?;
+        let received =
+            io::copy(&mut stdin, &mut file).context("Failed to read audio from stdin")?;
+        drop(file);
+        if received == 0 {
+            bail!("No audio received on stdin");
+        }
+
+        let mut header = Vec::new();
+        fs::File::open(&partial)
+            .and_then(|file| file.take(SNIFF_LEN).read_to_end(&mut header))
+            .with_context(|| format!("Failed to read {}", partial.display()))?;
+
+        let stem: String = title
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let extension = crate::audio::extension_from_header(&header).unwrap_or("bin");
+        let path = self.path.join(format!("{}.{}", stem, extension));
+        fs::rename(&partial, &path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if self.keep {
+            println!("Intermediate files kept in {}", self.path.display());
+        } else if let Err(e) = fs::remove_dir_all(&self.path) {
+            log::debug!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Scratch directory for a test, named after it so tests running in
+/// parallel don't share one, and removed on drop even if the test fails
+#[cfg(test)]
+pub struct TestDir(PathBuf);
+
+#[cfg(test)]
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("dj-uploader-{}-{}", name, std::process::id()));
+        // Left over from an earlier run that was killed
+        fs::remove_dir_all(&path).ok();
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
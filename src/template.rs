@@ -0,0 +1,114 @@
+use anyhow::{Context, Result, anyhow, bail};
+
+/// A placeholder value; numbers can be zero-padded with `{name:02}`
+#[derive(Debug, Clone)]
+pub enum Value {
+    Text(String),
+    Number(u64),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Text(text) => write!(f, "{}", text),
+            Value::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+/// Fill `{name}` placeholders from `values`. `{name:N}` pads to N characters
+/// and `{name:0N}` zero-pads a number; `{{` and `}}` are literal braces.
+/// Unknown placeholders are an error, so a typo shows up before any upload.
+pub fn render(template: &str, values: &[(&str, Value)]) -> Result<String> {
+    let mut rendered = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                rendered.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                rendered.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => bail!("Unclosed {{ in template \"{}\"", template),
+                    }
+                }
+
+                let (name, spec) = match placeholder.split_once(':') {
+                    Some((name, spec)) => (name, Some(spec)),
+                    None => (placeholder.as_str(), None),
+                };
+                let value = values
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, value)| value)
+                    .with_context(|| {
+                        let names: Vec<String> =
+                            values.iter().map(|(n, _)| format!("{{{}}}", n)).collect();
+                        format!(
+                            "Unknown placeholder {{{}}} in template (available: {})",
+                            name,
+                            names.join(", ")
+                        )
+                    })?;
+                rendered.push_str(&format_value(value, spec)?);
+            }
+            _ => rendered.push(c),
+        }
+    }
+
+    Ok(rendered)
+}
+
+/// Check a template against the placeholder names it may use
+pub fn validate(template: &str, names: &[&str]) -> Result<()> {
+    let values: Vec<(&str, Value)> = names.iter().map(|&n| (n, Value::Number(0))).collect();
+    render(template, &values).map(|_| ())
+}
+
+fn format_value(value: &Value, spec: Option<&str>) -> Result<String> {
+    let Some(spec) = spec else {
+        return Ok(value.to_string());
+    };
+    let width: usize = spec
+        .parse()
+        .map_err(|_| anyhow!("Invalid format \":{}\"; use a width like :2 or :02", spec))?;
+
+    Ok(match value {
+        Value::Number(number) if spec.starts_with('0') => format!("{:0width$}", number),
+        Value::Number(number) => format!("{:>width$}", number),
+        Value::Text(text) => format!("{:width$}", text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let values = [
+            ("number", Value::Number(7)),
+            ("stem", Value::Text("late_night".to_string())),
+        ];
+        assert_eq!(
+            render("{number:02} - {stem} {{live}}", &values).unwrap(),
+            "07 - late_night {live}"
+        );
+        assert_eq!(render("#{number:3}", &values).unwrap(), "#  7");
+
+        let err = render("{nubmer} - {stem}", &values).unwrap_err();
+        assert!(err.to_string().contains("Unknown placeholder {nubmer}"));
+        assert!(render("{stem", &values).is_err());
+        assert!(validate("{number:x}", &["number"]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+
+use crate::cli::Platform;
+use crate::limits::Limits;
+use crate::template::Value;
+
+/// Mix length for titles: "2h15m", or "45m" under an hour
+pub fn format_duration_label(total_secs: f64) -> String {
+    let minutes = (total_secs / 60.0).round() as u64;
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Default suffix for --append-duration/--append-date
+pub fn default_suffix_format(duration: bool, date: bool) -> &'static str {
+    match (duration, date) {
+        (true, true) => "({date} — {duration})",
+        (true, false) => "({duration})",
+        _ => "({date})",
+    }
+}
+
+/// Fill `{duration}` and `{date}` (YYYY-MM-DD) into a suffix format. Only
+/// the values being appended are available.
+pub fn render_suffix(
+    format: &str,
+    duration_secs: Option<f64>,
+    date: Option<NaiveDate>,
+) -> Result<String> {
+    let mut values = Vec::new();
+    if let Some(secs) = duration_secs {
+        values.push(("duration", Value::Text(format_duration_label(secs))));
+    }
+    if let Some(date) = date {
+        values.push(("date", Value::Text(date.format("%Y-%m-%d").to_string())));
+    }
+    crate::template::render(format, &values)
+}
+
+/// Trim a title and check that it's non-empty and within the platform limit
+pub fn validate_title(platform: Platform, limits: &Limits, title: &str) -> Result<String> {
+    let title = title.trim();
+    if title.is_empty() {
+        bail!("Title cannot be empty");
+    }
+
+    let len = title.chars().count();
+    let max = limits.title_max;
+    if len > max {
+        bail!(
+            "Title is {} characters, {} allows at most {}",
+            len,
+            platform,
+            max
+        );
+    }
+
+    Ok(title.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_title() {
+        let mixcloud = Limits::defaults(Platform::Mixcloud);
+        assert_eq!(
+            validate_title(Platform::Mixcloud, &mixcloud, "  Sunday Set ").unwrap(),
+            "Sunday Set"
+        );
+        assert!(validate_title(Platform::Mixcloud, &mixcloud, " \t ").is_err());
+        let soundcloud = Limits::defaults(Platform::Soundcloud);
+        assert!(validate_title(Platform::Soundcloud, &soundcloud, &"a".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn test_render_suffix() {
+        assert_eq!(format_duration_label(8100.0), "2h15m");
+        assert_eq!(format_duration_label(45.0 * 60.0 + 10.0), "45m");
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14);
+        assert_eq!(
+            render_suffix(default_suffix_format(true, true), Some(3600.0), date).unwrap(),
+            "(2026-03-14 — 1h00m)"
+        );
+        assert_eq!(
+            render_suffix("Live @ Venue — {duration}", Some(5400.0), None).unwrap(),
+            "Live @ Venue — 1h30m"
+        );
+        // {date} without --append-date
+        assert!(render_suffix("{duration} {date}", Some(5400.0), None).is_err());
+    }
+}
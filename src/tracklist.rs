@@ -0,0 +1,204 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::ExistingOutput;
+use crate::id3;
+
+/// One entry of a mix tracklist
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Offset into the mix where this track starts
+    pub start_secs: u64,
+    pub artist: String,
+    pub song: String,
+}
+
+impl Section {
+    /// Human-readable "Artist - Song" label
+    pub fn label(&self) -> String {
+        if self.artist.is_empty() {
+            self.song.clone()
+        } else {
+            format!("{} - {}", self.artist, self.song)
+        }
+    }
+}
+
+/// Parse a tracklist where each line looks like `MM:SS Artist - Song`
+/// (or `HH:MM:SS ...`). Blank lines and lines starting with `#` are skipped.
+pub fn parse_tracklist(contents: &str) -> Result<Vec<Section>> {
+    let mut sections: Vec<Section> = Vec::new();
+
+    for (line_no, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (timestamp, rest) = line
+            .split_once(char::is_whitespace)
+            .with_context(|| format!("Line {}: expected 'MM:SS Artist - Song'", line_no + 1))?;
+
+        let start_secs = parse_timestamp(timestamp)
+            .with_context(|| format!("Line {}: invalid timestamp '{}'", line_no + 1, timestamp))?;
+
+        if let Some(previous) = sections.last()
+            && start_secs <= previous.start_secs
+        {
+            bail!(
+                "Line {}: timestamps must be in ascending order",
+                line_no + 1
+            );
+        }
+
+        let rest = rest.trim();
+        let (artist, song) = match rest.split_once(" - ") {
+            Some((artist, song)) => (artist.trim().to_string(), song.trim().to_string()),
+            None => (String::new(), rest.to_string()),
+        };
+
+        sections.push(Section {
+            start_secs,
+            artist,
+            song,
+        });
+    }
+
+    Ok(sections)
+}
+
+/// Read and parse a tracklist file
+pub fn load_tracklist(path: &Path) -> Result<Vec<Section>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tracklist: {}", path.display()))?;
+    parse_tracklist(&contents)
+}
+
+/// Parse `SS`, `MM:SS` or `HH:MM:SS` into seconds
+pub fn parse_timestamp(timestamp: &str) -> Result<u64> {
+    let mut total = 0u64;
+    let parts: Vec<&str> = timestamp.split(':').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        bail!("Expected MM:SS or HH:MM:SS");
+    }
+
+    for part in parts {
+        let value: u64 = part.parse().context("Not a number")?;
+        total = total * 60 + value;
+    }
+
+    Ok(total)
+}
+
+/// Write the tracklist into a copy of the audio file as chapter metadata.
+/// MP3 files get ID3v2 CHAP/CTOC frames; other formats are returned unchanged
+/// with a warning. Returns the path of the file that should be uploaded.
+pub fn embed_tracklist(
+    path: &Path,
+    sections: &[Section],
+    output_dir: &Path,
+    existing: ExistingOutput,
+) -> Result<PathBuf> {
+    let is_mp3 = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("mp3"));
+
+    if !is_mp3 {
+        eprintln!(
+            "⚠ Warning: Chapter embedding is only implemented for MP3, uploading {} unchanged",
+            path.display()
+        );
+        return Ok(path.to_path_buf());
+    }
+
+    if sections.is_empty() {
+        return Ok(path.to_path_buf());
+    }
+
+    if sections.len() > 255 {
+        bail!("ID3 chapters support at most 255 tracks");
+    }
+
+    // Exact, so the last chapter ends where the audio does
+    let total_ms = crate::audio::audio_duration(path)?.as_millis() as u32;
+    let data = fs::read(path).context("Failed to read audio file")?;
+
+    let (version, mut frames, audio_start) = id3::split_tag(&data, &[b"CHAP", b"CTOC"]);
+    frames.extend(build_chapter_frames(version, sections, total_ms));
+    let output = id3::write_tag(version, &frames, &data[audio_start..]);
+
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid file name")?;
+    let output_path = output_dir.join(format!("{}_tracklist.mp3", stem));
+    if !crate::output::should_write(&output_path, existing)? {
+        return Ok(output_path);
+    }
+
+    fs::write(&output_path, output).context("Failed to write tagged audio file")?;
+
+    Ok(output_path)
+}
+
+/// Build a CTOC frame plus one CHAP frame (with a TIT2 title) per section
+fn build_chapter_frames(version: u8, sections: &[Section], total_ms: u32) -> Vec<u8> {
+    let mut frames = Vec::new();
+
+    // Table of contents: top-level, ordered, listing every chapter
+    let mut toc = b"toc\0".to_vec();
+    toc.push(0x03);
+    toc.push(sections.len() as u8);
+    for index in 0..sections.len() {
+        toc.extend_from_slice(format!("chp{}\0", index).as_bytes());
+    }
+    frames.extend(id3::frame(version, b"CTOC", &toc));
+
+    for (index, section) in sections.iter().enumerate() {
+        let start_ms = (section.start_secs * 1000) as u32;
+        let end_ms = sections
+            .get(index + 1)
+            .map(|next| (next.start_secs * 1000) as u32)
+            .unwrap_or(total_ms)
+            .max(start_ms);
+
+        let mut chap = format!("chp{}\0", index).into_bytes();
+        chap.extend_from_slice(&start_ms.to_be_bytes());
+        chap.extend_from_slice(&end_ms.to_be_bytes());
+        // Byte offsets unused, times are authoritative
+        chap.extend_from_slice(&u32::MAX.to_be_bytes());
+        chap.extend_from_slice(&u32::MAX.to_be_bytes());
+        chap.extend(id3::frame(
+            version,
+            b"TIT2",
+            &id3::encode_text(&section.label()),
+        ));
+
+        frames.extend(id3::frame(version, b"CHAP", &chap));
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tracklist() {
+        let sections = parse_tracklist(
+            "# My mix\n00:00 Intro\n03:15 Artist A - Track A\n1:02:03 Artist B - Track B\n",
+        )
+        .unwrap();
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].label(), "Intro");
+        assert_eq!(sections[1].start_secs, 195);
+        assert_eq!(sections[2].start_secs, 3723);
+        assert_eq!(sections[2].label(), "Artist B - Track B");
+
+        assert!(parse_tracklist("05:00 B\n01:00 A").is_err());
+    }
+}
@@ -52,6 +52,7 @@ pub fn check_for_update() -> Result<Option<UpdateInfo>> {
         .timeout(std::time::Duration::from_secs(10))
         .build()?;
 
+    crate::platforms::log_request("GET", GITHUB_API_URL, crate::platforms::RequestAuth::None);
     let response = client
         .get(GITHUB_API_URL)
         .header("User-Agent", format!("dj-uploader/{}", current_version))
@@ -63,9 +64,10 @@ pub fn check_for_update() -> Result<Option<UpdateInfo>> {
         return Ok(None);
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .context("Failed to parse GitHub release response")?;
+    let body = crate::platforms::response_text(response)
+        .context("Failed to read GitHub release response")?;
+    let release: GitHubRelease =
+        serde_json::from_str(&body).context("Failed to parse GitHub release response")?;
 
     let remote_version = release
         .tag_name
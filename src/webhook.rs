@@ -0,0 +1,141 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::cli::{Platform, WebhookFormat};
+use crate::template::Value;
+
+/// Default message for Slack/Discord webhooks
+pub const DEFAULT_TEMPLATE: &str = "{status_icon} {title} on {platform}: {url}";
+
+/// Placeholders a message template may use
+pub const PLACEHOLDERS: &[&str] = &["platform", "title", "url", "status_icon", "status", "error"];
+
+/// What gets reported after each upload
+#[derive(Debug, Serialize)]
+pub struct UploadPayload {
+    pub platform: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// "success" or "failed"
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl UploadPayload {
+    pub fn new(platform: Platform, title: &str, outcome: Result<Option<String>, String>) -> Self {
+        let (url, status, error) = match outcome {
+            Ok(url) => (url, "success", None),
+            Err(e) => (None, "failed", Some(e)),
+        };
+
+        Self {
+            platform: platform.to_string(),
+            title: title.to_string(),
+            url,
+            status,
+            error,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Fill in `{platform}`, `{title}`, `{url}`, `{status}`, `{status_icon}`
+    /// and `{error}` in a message template
+    pub fn render(&self, template: &str) -> Result<String> {
+        let status_icon = if self.status == "success" {
+            "✓"
+        } else {
+            "✗"
+        };
+        let text = |value: &str| Value::Text(value.to_string());
+        crate::template::render(
+            template,
+            &[
+                ("platform", text(&self.platform)),
+                ("title", text(&self.title)),
+                ("url", text(self.url.as_deref().unwrap_or("-"))),
+                ("status_icon", text(status_icon)),
+                ("status", text(self.status)),
+                ("error", text(self.error.as_deref().unwrap_or(""))),
+            ],
+        )
+    }
+}
+
+/// Where and how to send upload notifications
+pub struct Webhook {
+    pub url: String,
+    pub format: WebhookFormat,
+    /// Message template for Slack/Discord; ignored for raw JSON
+    pub template: String,
+}
+
+impl Webhook {
+    /// POST the payload. Best effort: failures are printed, never returned.
+    pub fn notify(&self, payload: &UploadPayload) {
+        if let Err(e) = self.send(payload) {
+            eprintln!("⚠ Warning: Webhook notification failed: {:#}", e);
+        }
+    }
+
+    fn send(&self, payload: &UploadPayload) -> Result<()> {
+        let body = match self.format {
+            WebhookFormat::Json => serde_json::to_value(payload)?,
+            WebhookFormat::Slack => json!({ "text": payload.render(&self.template)? }),
+            WebhookFormat::Discord => json!({ "content": payload.render(&self.template)? }),
+        };
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create HTTP client")?;
+        // Webhook URLs carry their secret in the path, so only the host is logged
+        let logged_url = format!(
+            "{}/<redacted>",
+            self.url.split('/').take(3).collect::<Vec<_>>().join("/")
+        );
+        crate::platforms::log_request("POST", &logged_url, crate::platforms::RequestAuth::None);
+        let response = client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .map_err(|e| crate::platforms::network_error("Failed to call webhook", e))?;
+
+        let status = response.status();
+        crate::platforms::trace::response(&logged_url, status, response.headers(), "");
+        if !status.is_success() {
+            bail!("Webhook returned status {}", status);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let payload = UploadPayload::new(
+            Platform::Mixcloud,
+            "Sunday Set",
+            Ok(Some("https://www.mixcloud.com/dj/sunday-set/".to_string())),
+        );
+        assert_eq!(
+            payload.render(DEFAULT_TEMPLATE).unwrap(),
+            "✓ Sunday Set on Mixcloud: https://www.mixcloud.com/dj/sunday-set/"
+        );
+
+        let failed = UploadPayload::new(Platform::Soundcloud, "Mix", Err("timeout".to_string()));
+        assert_eq!(
+            failed.render("{status}: {error}").unwrap(),
+            "failed: timeout"
+        );
+        assert!(failed.render("{stauts}").is_err());
+    }
+}